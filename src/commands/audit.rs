@@ -0,0 +1,535 @@
+use std::{collections::HashSet, time::Duration};
+
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    controllers::project::resolve_project_id_or_name,
+    table::ListTable,
+    util::{progress::create_spinner_if, time::parse_time},
+    workspace::{pick_workspace, workspaces},
+};
+
+use super::*;
+
+const PAGE_SIZE: i64 = 100;
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Query the workspace audit log
+#[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway audit --workspace acme --since 30d\n  railway audit --event-type 'variable.*' --actor alice@example.com\n  railway audit --project api --since 2024-05-01 --until 2024-06-01 --format csv > audit.csv\n  railway audit --format ndjson --limit 0 | jq .eventType\n  railway audit --event-type 'variable.*' --follow\n  railway audit --list-event-types\n\nNotes:\n  --event-type accepts exact types or `*` globs and may be repeated.\n  --actor matches (case-insensitively) any value in the event's context, e.g. an email or user ID.\n  --follow polls for new events every 10s until interrupted."
+)]
+pub struct Args {
+    /// Workspace ID or name (defaults to your only workspace, or prompts)
+    #[clap(short, long)]
+    workspace: Option<String>,
+
+    /// Only show events for this project ID or name
+    #[clap(short, long)]
+    project: Option<String>,
+
+    /// Only show events at or after this time (e.g. 30m, 2h, 7d, 2024-05-01)
+    #[clap(long, default_value = "7d")]
+    since: String,
+
+    /// Only show events before this time (e.g. 1d, 2024-06-01T12:00:00Z)
+    #[clap(long, conflicts_with = "follow")]
+    until: Option<String>,
+
+    /// Event type or glob to include (e.g. variable.*), can be repeated
+    #[clap(long = "event-type", short = 't', value_name = "TYPE")]
+    event_types: Vec<String>,
+
+    /// Only show events whose actor or context matches this value
+    #[clap(long)]
+    actor: Option<String>,
+
+    /// Maximum number of events to fetch (0 for no limit)
+    #[clap(short = 'n', long, default_value_t = 100)]
+    limit: usize,
+
+    /// Output format
+    #[clap(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+
+    /// Output as NDJSON (shorthand for --format ndjson)
+    #[clap(long, conflicts_with = "format")]
+    json: bool,
+
+    /// Keep polling and print new events as they happen
+    #[clap(short, long)]
+    follow: bool,
+
+    /// List the known event types and exit
+    #[clap(long, exclusive = true)]
+    list_event_types: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Table,
+    Ndjson,
+    Csv,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditEvent {
+    id: String,
+    created_at: DateTime<Utc>,
+    event_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<String>,
+    context: Value,
+    payload: Value,
+}
+
+struct Query {
+    workspace_id: String,
+    project_id: Option<String>,
+    event_types: Option<Vec<String>>,
+    patterns: Vec<String>,
+    actor: Option<String>,
+}
+
+pub async fn command(args: Args) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+
+    if args.list_event_types {
+        return list_event_types(&client, &configs).await;
+    }
+
+    let format = if args.json {
+        Format::Ndjson
+    } else {
+        args.format
+    };
+    let since = parse_time(&args.since).context("Invalid --since value")?;
+    let until = args
+        .until
+        .as_deref()
+        .map(parse_time)
+        .transpose()
+        .context("Invalid --until value")?;
+    if until.is_some_and(|until| until <= since) {
+        bail!("--until must be later than --since");
+    }
+
+    let workspace = pick_workspace(workspaces().await?, args.workspace)?;
+    let project_id = match &args.project {
+        Some(project) => Some(resolve_project_id_or_name(&client, &configs, project).await?),
+        None => None,
+    };
+    let event_types = expand_event_types(&client, &configs, &args.event_types).await?;
+    let query = Query {
+        workspace_id: workspace.id().to_string(),
+        project_id,
+        event_types,
+        patterns: args.event_types,
+        actor: args.actor,
+    };
+    let limit = (args.limit > 0).then_some(args.limit);
+
+    let spinner = create_spinner_if(
+        format == Format::Table && !args.follow,
+        "Fetching audit log...".into(),
+    );
+    let events = fetch_events(
+        &client,
+        &configs,
+        &query,
+        since,
+        until,
+        queries::audit_logs::SortOrder::desc,
+        limit,
+    )
+    .await;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+    let mut events = events?;
+
+    let mut csv = (format == Format::Csv).then(|| csv::Writer::from_writer(std::io::stdout()));
+    if let Some(writer) = csv.as_mut() {
+        writer.write_record(CSV_HEADERS)?;
+    }
+
+    if !args.follow {
+        return print_events(&events, format, csv.as_mut(), true);
+    }
+
+    // Followed output is chronological so new events append naturally.
+    events.reverse();
+    if format == Format::Table {
+        eprintln!(
+            "{}",
+            format!(
+                "Following audit log for workspace {} (Ctrl+C to stop)",
+                workspace.name()
+            )
+            .dimmed()
+        );
+    }
+    print_events(&events, format, csv.as_mut(), false)?;
+
+    let mut seen: HashSet<String> = events.iter().map(|e| e.id.clone()).collect();
+    let mut cursor = events.last().map(|e| e.created_at).unwrap_or(since);
+    loop {
+        tokio::time::sleep(FOLLOW_INTERVAL).await;
+        let new = match fetch_events(
+            &client,
+            &configs,
+            &query,
+            cursor,
+            None,
+            queries::audit_logs::SortOrder::asc,
+            None,
+        )
+        .await
+        {
+            Ok(new) => new,
+            Err(e) => {
+                eprintln!("{}: {e}", "Failed to poll audit log".yellow());
+                continue;
+            }
+        };
+        let new: Vec<_> = new
+            .into_iter()
+            .filter(|event| seen.insert(event.id.clone()))
+            .collect();
+        if let Some(last) = new.last() {
+            cursor = cursor.max(last.created_at);
+        }
+        print_events(&new, format, csv.as_mut(), false)?;
+    }
+}
+
+async fn list_event_types(client: &reqwest::Client, configs: &Configs) -> Result<()> {
+    let types = post_graphql::<queries::AuditLogEventTypes, _>(
+        client,
+        configs.get_backboard(),
+        queries::audit_log_event_types::Variables {},
+    )
+    .await?
+    .audit_log_event_type_info;
+    for info in types {
+        println!("{}  {}", info.event_type.bold(), info.description.dimmed());
+    }
+    Ok(())
+}
+
+/// Resolves `--event-type` values into the exact types the API filters on.
+/// Globs are expanded against the known event types; `None` means no filter.
+async fn expand_event_types(
+    client: &reqwest::Client,
+    configs: &Configs,
+    patterns: &[String],
+) -> Result<Option<Vec<String>>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    if !patterns.iter().any(|p| p.contains('*')) {
+        return Ok(Some(patterns.to_vec()));
+    }
+
+    let known = post_graphql::<queries::AuditLogEventTypes, _>(
+        client,
+        configs.get_backboard(),
+        queries::audit_log_event_types::Variables {},
+    )
+    .await?
+    .audit_log_event_type_info
+    .into_iter()
+    .map(|info| info.event_type)
+    .collect::<Vec<_>>();
+
+    let mut types = Vec::new();
+    for pattern in patterns {
+        let matched: Vec<_> = known
+            .iter()
+            .filter(|t| glob_match(pattern, t))
+            .cloned()
+            .collect();
+        if matched.is_empty() && pattern.contains('*') {
+            bail!(
+                "No audit event types match '{pattern}'. Run `railway audit --list-event-types` to see them."
+            );
+        }
+        if matched.is_empty() {
+            types.push(pattern.clone());
+        }
+        types.extend(matched);
+    }
+    types.sort();
+    types.dedup();
+    Ok(Some(types))
+}
+
+async fn fetch_events(
+    client: &reqwest::Client,
+    configs: &Configs,
+    query: &Query,
+    since: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+    sort: queries::audit_logs::SortOrder,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEvent>> {
+    let mut events = Vec::new();
+    let mut after = None;
+    loop {
+        let connection = post_graphql::<queries::AuditLogs, _>(
+            client,
+            configs.get_backboard(),
+            queries::audit_logs::Variables {
+                workspace_id: query.workspace_id.clone(),
+                filter: Some(queries::audit_logs::AuditLogFilterInput {
+                    project_id: query.project_id.clone(),
+                    environment_id: None,
+                    event_types: query.event_types.clone(),
+                    start_date: Some(since),
+                    end_date: until,
+                }),
+                sort: Some(sort.clone()),
+                first: Some(PAGE_SIZE),
+                after,
+            },
+        )
+        .await?
+        .audit_logs;
+
+        for edge in connection.edges {
+            let node = edge.node;
+            let context = node.context.unwrap_or(Value::Null);
+            let actor = actor_label(&context);
+            let event = AuditEvent {
+                id: node.id,
+                created_at: node.created_at,
+                event_type: node.event_type,
+                actor,
+                project_id: node.project_id,
+                project: node.project.map(|p| p.name),
+                environment_id: node.environment_id,
+                environment: node.environment.map(|e| e.name),
+                context,
+                payload: node.payload.unwrap_or(Value::Null),
+            };
+            if query.matches(&event) {
+                events.push(event);
+            }
+            if limit.is_some_and(|limit| events.len() >= limit) {
+                return Ok(events);
+            }
+        }
+
+        match connection.page_info.end_cursor {
+            Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+            _ => return Ok(events),
+        }
+    }
+}
+
+impl Query {
+    /// Client-side filters the API doesn't support (actor) or may not apply
+    /// exactly (event type globs).
+    fn matches(&self, event: &AuditEvent) -> bool {
+        if !self.patterns.is_empty()
+            && !self
+                .patterns
+                .iter()
+                .any(|p| glob_match(p, &event.event_type))
+        {
+            return false;
+        }
+        match &self.actor {
+            Some(actor) => context_matches(&event.context, &actor.to_lowercase()),
+            None => true,
+        }
+    }
+}
+
+const CSV_HEADERS: [&str; 9] = [
+    "createdAt",
+    "eventType",
+    "actor",
+    "project",
+    "environment",
+    "id",
+    "projectId",
+    "environmentId",
+    "payload",
+];
+
+fn print_events(
+    events: &[AuditEvent],
+    format: Format,
+    csv: Option<&mut csv::Writer<std::io::Stdout>>,
+    table: bool,
+) -> Result<()> {
+    match format {
+        Format::Ndjson => {
+            for event in events {
+                println!("{}", serde_json::to_string(event)?);
+            }
+        }
+        Format::Csv => {
+            let writer = csv.context("CSV writer not initialised")?;
+            for event in events {
+                writer.write_record([
+                    event.created_at.to_rfc3339(),
+                    event.event_type.clone(),
+                    event.actor.clone().unwrap_or_default(),
+                    event.project.clone().unwrap_or_default(),
+                    event.environment.clone().unwrap_or_default(),
+                    event.id.clone(),
+                    event.project_id.clone().unwrap_or_default(),
+                    event.environment_id.clone().unwrap_or_default(),
+                    serde_json::to_string(&event.payload)?,
+                ])?;
+            }
+            writer.flush()?;
+        }
+        Format::Table if table => {
+            if events.is_empty() {
+                println!("No audit events found");
+                return Ok(());
+            }
+            let rows = events.iter().map(|e| table_row(e).to_vec()).collect();
+            ListTable::new(
+                format!("Audit log ({} events)", events.len()),
+                &["Time", "Event", "Actor", "Project", "Environment"],
+                rows,
+            )
+            .print();
+        }
+        Format::Table => {
+            for event in events {
+                let [time, event_type, actor, project, environment] = table_row(event);
+                println!(
+                    "{}  {}  {}  {}",
+                    time.dimmed(),
+                    event_type.bold(),
+                    actor,
+                    format!("{project} {environment}").trim().dimmed()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn table_row(event: &AuditEvent) -> [String; 5] {
+    [
+        DateTime::<Local>::from(event.created_at)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        event.event_type.clone(),
+        event.actor.clone().unwrap_or_else(|| "-".to_string()),
+        event.project.clone().unwrap_or_else(|| "-".to_string()),
+        event.environment.clone().unwrap_or_default(),
+    ]
+}
+
+/// Matches `*` wildcards (any run of characters, including none).
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<_> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Best-effort human label for who triggered an event, taken from the
+/// event context (email, then name, then ID).
+fn actor_label(context: &Value) -> Option<String> {
+    let candidates = ["actor", "user", "member", "initiator"];
+    for key in candidates {
+        match context.get(key) {
+            Some(Value::String(s)) if !s.is_empty() => return Some(s.clone()),
+            Some(Value::Object(_)) => {
+                if let Some(label) = actor_label(&context[key]) {
+                    return Some(label);
+                }
+            }
+            _ => {}
+        }
+    }
+    [
+        "email",
+        "userEmail",
+        "actorEmail",
+        "name",
+        "userName",
+        "userId",
+        "actorId",
+        "id",
+    ]
+    .iter()
+    .find_map(|key| context.get(*key)?.as_str())
+    .filter(|s| !s.is_empty())
+    .map(str::to_string)
+}
+
+fn context_matches(value: &Value, needle: &str) -> bool {
+    match value {
+        Value::String(s) => s.to_lowercase().contains(needle),
+        Value::Array(items) => items.iter().any(|v| context_matches(v, needle)),
+        Value::Object(map) => map.values().any(|v| context_matches(v, needle)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn glob_matches_event_types() {
+        assert!(glob_match("variable.*", "variable.created"));
+        assert!(glob_match("*.deleted", "service.deleted"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("project.member.*ed", "project.member.removed"));
+        assert!(glob_match("variable.created", "variable.created"));
+        assert!(!glob_match("variable.*", "service.created"));
+        assert!(!glob_match("variable", "variable.created"));
+        assert!(!glob_match("a*a", "a"));
+    }
+
+    #[test]
+    fn actor_is_taken_from_context() {
+        let context = json!({ "actor": { "id": "u1", "email": "alice@example.com" } });
+        assert_eq!(actor_label(&context).as_deref(), Some("alice@example.com"));
+        assert_eq!(
+            actor_label(&json!({ "userId": "u2" })).as_deref(),
+            Some("u2")
+        );
+        assert_eq!(actor_label(&json!({ "ip": "1.2.3.4" })), None);
+
+        assert!(context_matches(&context, "alice@"));
+        assert!(!context_matches(&context, "bob@"));
+    }
+}
@@ -7,6 +7,7 @@ use std::{
     process::Command as ProcessCommand,
 };
 
+use clap::ValueEnum;
 use is_terminal::IsTerminal;
 use sha2::{Digest, Sha256};
 
@@ -44,6 +45,66 @@ enum Command {
 
     /// Translate railway.json / railway.toml into .railway/railway.ts
     Migrate(migrate::MigrateArgs),
+
+    /// Report changes made to the linked environment outside .railway/railway.ts. Exits 2 on drift.
+    Drift(DriftArgs),
+
+    /// Print the JSON Schema for .railway/railway.toml, .yaml, and .json
+    Schema,
+}
+
+/// Authoring format written by `railway config pull`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ConfigFormat {
+    /// TypeScript (.railway/railway.ts)
+    Ts,
+    /// Declarative TOML (.railway/railway.toml); no language runtime needed
+    Toml,
+    /// Declarative YAML (.railway/railway.yaml); no language runtime needed
+    Yaml,
+    /// Declarative JSON (.railway/railway.json); no language runtime needed
+    Json,
+}
+
+impl ConfigFormat {
+    fn declarative(self) -> Option<crate::iac::DeclarativeFormat> {
+        match self {
+            ConfigFormat::Ts => None,
+            ConfigFormat::Toml => Some(crate::iac::DeclarativeFormat::Toml),
+            ConfigFormat::Yaml => Some(crate::iac::DeclarativeFormat::Yaml),
+            ConfigFormat::Json => Some(crate::iac::DeclarativeFormat::Json),
+        }
+    }
+
+    fn file_name(self) -> String {
+        match self.declarative() {
+            Some(format) => format!("railway.{}", format.extension()),
+            None => "railway.ts".to_string(),
+        }
+    }
+}
+
+#[derive(Parser)]
+struct DriftArgs {
+    /// Path to the Railway configuration file. Defaults to nearest .railway/railway.ts.
+    #[clap(long)]
+    file: Option<PathBuf>,
+
+    /// Output a machine-readable drift report.
+    #[clap(long)]
+    json: bool,
+
+    /// Ask Railway to decrypt variables while comparing, when authorized.
+    #[clap(long)]
+    decrypt_variables: bool,
+
+    /// Also exit 2 when the file has changes that haven't been applied yet.
+    #[clap(long)]
+    fail_on_pending: bool,
+
+    /// Environment to check (defaults to the linked environment). Selects its overlay block.
+    #[clap(short, long)]
+    environment: Option<String>,
 }
 
 #[derive(Parser, Clone)]
@@ -87,6 +148,18 @@ struct SharedArgs {
     /// Print variable values in the plan instead of redacting them.
     #[clap(long)]
     show_values: bool,
+
+    /// Environment to plan against (defaults to the linked environment). Selects its overlay block.
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Write the versioned plan, with stable change IDs, to this file (plan only).
+    #[clap(long)]
+    out: Option<PathBuf>,
+
+    /// Apply a plan saved with `railway config plan --out`, refusing if it's out of date (apply only).
+    #[clap(long)]
+    plan: Option<PathBuf>,
 }
 
 #[derive(Clone, Copy)]
@@ -136,6 +209,10 @@ struct PullArgs {
     /// Ask an agent to turn imported state into idiomatic railway.ts code.
     #[clap(long)]
     agent: bool,
+
+    /// File format to write. Declarative formats need no language runtime.
+    #[clap(long, value_enum, default_value = "ts")]
+    format: ConfigFormat,
 }
 
 pub async fn command(args: Args) -> Result<()> {
@@ -162,6 +239,9 @@ pub async fn command(args: Args) -> Result<()> {
             if args.confirm_destructive {
                 bail!("--confirm-destructive is only valid with `railway config apply`.");
             }
+            if args.plan.is_some() {
+                bail!("--plan is only valid with `railway config apply`.");
+            }
             run_sync(args, false, false).await
         }
         Command::Stage(_args) => bail!(
@@ -171,11 +251,41 @@ pub async fn command(args: Args) -> Result<()> {
             if args.detailed_exit_code {
                 bail!("--detailed-exit-code is only valid with `railway config plan`.");
             }
+            if args.out.is_some() {
+                bail!("--out is only valid with `railway config plan`.");
+            }
             run_sync(args, false, true).await
         }
         Command::Init(args) => init_config(args).await,
         Command::Pull(args) => pull_config(args).await,
         Command::Migrate(args) => migrate::migrate_config(args).await,
+        Command::Schema => {
+            print!("{}", crate::iac::PROJECT_SCHEMA);
+            Ok(())
+        }
+        Command::Drift(args) => {
+            runner::run_drift_command(
+                runner::Args {
+                    file: args.file,
+                    stage: false,
+                    json: args.json,
+                    yes: false,
+                    confirm_destructive: false,
+                    apply: false,
+                    decrypt_variables: args.decrypt_variables,
+                    include_types: false,
+                    runner: None,
+                    verbose: false,
+                    detailed_exit_code: false,
+                    show_values: false,
+                    environment: args.environment,
+                    out: None,
+                    plan: None,
+                },
+                args.fail_on_pending,
+            )
+            .await
+        }
     }
 }
 
@@ -375,7 +485,7 @@ fn write_new(path: &Path, contents: &str, force: bool) -> Result<()> {
 
 async fn pull_config(args: PullArgs) -> Result<()> {
     let cwd = std::env::current_dir().context("Unable to get current directory")?;
-    let railway_file = cwd.join(".railway").join("railway.ts");
+    let railway_file = cwd.join(".railway").join(args.format.file_name());
     let readme_file = cwd.join(".railway").join("README.md");
 
     if args.json {
@@ -384,14 +494,40 @@ async fn pull_config(args: PullArgs) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(other) = find_railway_files(&cwd)
+        .into_iter()
+        .find(|path| path.file_name() != railway_file.file_name())
+    {
+        bail!(
+            "{} already exists. Railway reads one configuration file, so remove it before pulling as {}.",
+            other.display(),
+            args.format.file_name()
+        );
+    }
+
     create_parent(&railway_file)?;
-    write_pulled_config(
-        &railway_file,
-        args.force,
-        args.runner,
-        !args.omit_preserved_variables,
-    )
-    .await?;
+    match args.format.declarative() {
+        Some(format) => {
+            let graph = load_current_graph(args.runner).await?;
+            write_new(
+                &railway_file,
+                &render_graph_as_declarative(&graph, format, !args.omit_preserved_variables)?,
+                args.force,
+            )?;
+            let schema_file = cwd.join(".railway").join("railway.schema.json");
+            fs::write(&schema_file, crate::iac::PROJECT_SCHEMA)
+                .with_context(|| format!("Failed to write {}", schema_file.display()))?;
+        }
+        None => {
+            write_pulled_config(
+                &railway_file,
+                args.force,
+                args.runner,
+                !args.omit_preserved_variables,
+            )
+            .await?
+        }
+    }
     let wrote_readme =
         write_asset_if_missing(&readme_file, include_str!("../../../assets/iac/README.md"))?;
 
@@ -413,7 +549,7 @@ async fn pull_config(args: PullArgs) -> Result<()> {
     println!(
         "  {} Review {} and remove anything you do not want managed from code.",
         "•".cyan(),
-        ".railway/railway.ts".cyan()
+        format!(".railway/{}", args.format.file_name()).cyan()
     );
     println!(
         "  {} Run {} to verify it matches Railway.",
@@ -480,6 +616,9 @@ async fn load_current_graph(runner: Option<String>) -> Result<runner::DesiredGra
         verbose: false,
         detailed_exit_code: false,
         show_values: false,
+        environment: None,
+        out: None,
+        plan: None,
     };
     let response = runner::run(&args, "current").await?;
     // `temp_dir` cleans itself up on drop, including on the error paths below.
@@ -509,6 +648,39 @@ async fn load_current_graph(runner: Option<String>) -> Result<runner::DesiredGra
         .context("Railway did not return current project state")
 }
 
+fn render_graph_as_declarative(
+    graph: &runner::DesiredGraph,
+    format: crate::iac::DeclarativeFormat,
+    preserve_variables: bool,
+) -> Result<String> {
+    let mut resources = Vec::new();
+    for resource in &graph.resources {
+        let mut value = serde_json::to_value(resource)?;
+        if !preserve_variables {
+            if let Some(variables) = value
+                .get_mut("variables")
+                .and_then(serde_json::Value::as_object_mut)
+            {
+                variables.retain(|_, value| {
+                    value.get("type").and_then(|value| value.as_str()) != Some("preserve")
+                });
+            }
+        }
+        resources.push(value);
+    }
+    let name = graph
+        .project
+        .as_ref()
+        .map(|project| project.name.as_str())
+        .unwrap_or("railway-project");
+    let environments: Vec<String> = graph
+        .environments
+        .iter()
+        .map(|environment| environment.name.clone())
+        .collect();
+    crate::iac::render_definition(name, &environments, resources, &graph.edges, format)
+}
+
 fn render_graph_as_railway_ts(graph: &runner::DesiredGraph, preserve_variables: bool) -> String {
     let mut imports = vec!["defineRailway", "project", "service"];
     if graph
@@ -1505,6 +1677,9 @@ async fn run_sync(args: SharedArgs, stage: bool, apply: bool) -> Result<()> {
         verbose: args.verbose,
         detailed_exit_code: args.detailed_exit_code,
         show_values: args.show_values,
+        environment: args.environment,
+        out: args.out,
+        plan: args.plan,
     })
     .await
 }
@@ -1518,7 +1693,7 @@ async fn ensure_config_initialized(args: &SharedArgs) -> Result<()> {
     let found = find_railway_files(&cwd);
     if found.len() > 1 {
         bail!(
-            "Multiple Railway configuration files found ({}). Keep only one of .railway/railway.{{ts,py,go}}.",
+            "Multiple Railway configuration files found ({}). Keep only one of .railway/railway.{{ts,py,go,toml,yaml,json}}.",
             found
                 .iter()
                 .map(|p| p.display().to_string())
@@ -1562,10 +1737,19 @@ fn find_railway_file(start: &Path) -> Option<PathBuf> {
     find_railway_files(start).into_iter().next()
 }
 
-/// Discover IaC authoring files (`.ts` / `.py` / `.go`). Prefer a single file;
+/// Discover IaC authoring files (`.ts` / `.py` / `.go`, or declarative
+/// `.toml` / `.yaml` / `.json`). Prefer a single file;
 /// when multiple exist, callers should error.
 fn find_railway_files(start: &Path) -> Vec<PathBuf> {
-    const NAMES: &[&str] = &["railway.ts", "railway.py", "railway.go"];
+    const NAMES: &[&str] = &[
+        "railway.ts",
+        "railway.py",
+        "railway.go",
+        "railway.toml",
+        "railway.yaml",
+        "railway.yml",
+        "railway.json",
+    ];
     for directory in start.ancestors() {
         let mut found = Vec::new();
         let railway_dir =
@@ -63,6 +63,18 @@ pub struct Args {
     /// Print variable values in the plan instead of redacting them.
     #[clap(long)]
     pub(super) show_values: bool,
+
+    /// Environment to plan against (defaults to the linked environment). Selects its overlay block.
+    #[clap(short, long)]
+    pub(super) environment: Option<String>,
+
+    /// Write the versioned plan, with stable change IDs, to this file (plan only).
+    #[clap(long)]
+    pub(super) out: Option<PathBuf>,
+
+    /// Apply a plan saved with `railway config plan --out`, refusing if it's out of date (apply only).
+    #[clap(long)]
+    pub(super) plan: Option<PathBuf>,
 }
 
 #[derive(Deserialize, serde::Serialize)]
@@ -81,6 +93,10 @@ pub(super) struct RunnerResponse {
     apply_result: Option<ChangeSetApplyResult>,
     deployment_id: Option<String>,
     staged_patch_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    drift: Option<crate::iac::DriftReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    plan: Option<crate::iac::SavedPlan>,
 }
 
 #[derive(Deserialize, serde::Serialize)]
@@ -136,7 +152,11 @@ struct ChangeOperationResult {
 #[serde(rename_all = "camelCase")]
 pub(super) struct DesiredGraph {
     pub(super) project: Option<DesiredProject>,
+    #[serde(default)]
+    pub(super) environments: Vec<DesiredEnvironment>,
     pub(super) resources: Vec<DesiredResource>,
+    #[serde(default)]
+    pub(super) edges: Vec<crate::iac::Edge>,
 }
 
 #[derive(Deserialize, serde::Serialize)]
@@ -145,6 +165,11 @@ pub(super) struct DesiredProject {
     pub(super) name: String,
 }
 
+#[derive(Deserialize, serde::Serialize)]
+pub(super) struct DesiredEnvironment {
+    pub(super) name: String,
+}
+
 #[derive(Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct DesiredResource {
@@ -224,7 +249,7 @@ pub(super) async fn run_command(args: Args) -> Result<()> {
         let destructive = has_destructive_changes(&preview);
         guard_destructive_apply(&args, destructive)?;
 
-        if !args.yes && !args.json {
+        if !args.yes && args.plan.is_none() && !args.json {
             print_response_with_options_and_next(&preview, args.verbose, false);
             println!();
             let prompt = if destructive {
@@ -258,6 +283,7 @@ pub(super) async fn run_command(args: Args) -> Result<()> {
         if !output.ok {
             bail!(runner_diagnostics_message(&output));
         }
+        save_plan(&args, &output, false)?;
         maybe_detailed_exit(&args, command, &output);
         return Ok(());
     }
@@ -266,12 +292,172 @@ pub(super) async fn run_command(args: Args) -> Result<()> {
     if !output.ok {
         bail!(runner_diagnostics_message(&output));
     }
+    save_plan(&args, &output, true)?;
 
     maybe_detailed_exit(&args, command, &output);
 
     Ok(())
 }
 
+fn load_saved_plan(path: &std::path::Path) -> Result<crate::iac::SavedPlan> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read plan {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| {
+        format!(
+            "{} is not a plan written by `railway config plan --out`",
+            path.display()
+        )
+    })
+}
+
+fn save_plan(args: &Args, output: &RunnerResponse, announce: bool) -> Result<()> {
+    let Some(path) = &args.out else {
+        return Ok(());
+    };
+    let plan = output
+        .plan
+        .as_ref()
+        .context("Railway did not return a plan to save")?;
+    crate::util::write_atomic(path, &format!("{}\n", serde_json::to_string_pretty(plan)?))
+        .with_context(|| format!("Failed to write plan to {}", path.display()))?;
+    if announce {
+        println!();
+        println!(
+            "{} Saved plan to {}. Apply it with {}.",
+            "✓".green(),
+            path.display().to_string().cyan(),
+            format!("railway config apply --plan {}", path.display()).cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Report where the linked environment and the config file disagree. Exits 2
+/// when the live environment was changed outside the file (or `fail_on_pending`
+/// is set and the file has unapplied changes), so scheduled jobs can alert.
+pub(super) async fn run_drift_command(args: Args, fail_on_pending: bool) -> Result<()> {
+    if crate::iac::use_legacy_ts_runner(args.runner.as_deref()) {
+        bail!(
+            "`railway config drift` is only supported by the CLI engine. Unset RAILWAY_IAC_TS_BIN and RAILWAY_IAC_ENGINE."
+        );
+    }
+    let (configs, linked_project, token, auth_type) = ensure_config_context().await?;
+
+    let mut spinner = create_spinner_if(
+        !args.json && std::io::stdout().is_terminal(),
+        "Checking Railway configuration for drift".into(),
+    );
+    let output =
+        invoke_runner(&args, &configs, &linked_project, &token, auth_type, "drift").await?;
+    if let Some(spinner) = &mut spinner {
+        if output.ok {
+            success_spinner(spinner, "Checked Railway configuration for drift".into());
+        } else {
+            fail_spinner(spinner, "Could not read Railway configuration".into());
+        }
+    }
+
+    if !output.ok {
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            print_response_with_options_and_next(&output, args.verbose, false);
+        }
+        bail!(runner_diagnostics_message(&output));
+    }
+    let report = output
+        .drift
+        .as_ref()
+        .context("Railway did not return a drift report")?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+    } else {
+        print_drift_report(&output, report);
+    }
+
+    if report.drifted || (fail_on_pending && report.file_ahead > 0) {
+        std::process::exit(2);
+    }
+    Ok(())
+}
+
+fn print_drift_report(response: &RunnerResponse, report: &crate::iac::DriftReport) {
+    use crate::iac::DriftOrigin;
+
+    println!();
+    println!("{}", "Railway configuration drift".bold());
+    println!(
+        "{} {}",
+        "Using".dimmed(),
+        display_file_path(&response.file).cyan()
+    );
+    if let Some(environment) = &response.current_environment {
+        let environment_name = environment
+            .environment_name
+            .as_deref()
+            .unwrap_or(&environment.environment_id);
+        println!("{} {}", "Environment".dimmed(), environment_name.cyan());
+    }
+    println!();
+
+    if report.entries.is_empty() {
+        println!(
+            "{}",
+            "✓ The live environment matches your Railway configuration.".green()
+        );
+        return;
+    }
+
+    for (origin, heading) in [
+        (DriftOrigin::Live, "Modified outside the file"),
+        (
+            DriftOrigin::Unknown,
+            "Differs (no applied baseline recorded)",
+        ),
+        (DriftOrigin::File, "File is ahead (not applied yet)"),
+    ] {
+        let entries: Vec<_> = report
+            .entries
+            .iter()
+            .filter(|entry| entry.origin == origin)
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        let heading = match origin {
+            DriftOrigin::File => heading.cyan().bold(),
+            _ => heading.yellow().bold(),
+        };
+        println!("{} {}", heading, format!("({})", entries.len()).dimmed());
+        for entry in entries {
+            let marker = match entry.kind.as_str() {
+                "resource.create" | "variable.set" | "domain.create" => "+".green().bold(),
+                "resource.delete" | "variable.delete" => "-".red().bold(),
+                _ => "~".yellow().bold(),
+            };
+            println!("  {} {}", marker, entry.summary);
+        }
+        println!();
+    }
+
+    if report.out_of_band > 0 {
+        println!(
+            "  {} Run {} to bring the file up to date, or {} to revert the out-of-band changes.",
+            "•".cyan(),
+            "railway config pull".cyan(),
+            "railway config apply".cyan()
+        );
+    }
+    if report.file_ahead > 0 {
+        println!(
+            "  {} Run {} to apply the pending file changes.",
+            "•".cyan(),
+            "railway config apply".cyan()
+        );
+    }
+}
+
 /// Terraform-style `-detailed-exitcode`: on a successful `plan`, exit 2 if changes
 /// are pending (0 if none). Opt-in via --detailed-exit-code, so default behavior is
 /// unchanged and existing CI keeps working. Errors still surface as a non-zero
@@ -297,7 +483,7 @@ async fn preview_before_apply(
     token: &str,
     auth_type: &str,
 ) -> Result<RunnerResponse> {
-    if !args.yes && !args.json && !std::io::stdout().is_terminal() {
+    if !args.yes && args.plan.is_none() && !args.json && !std::io::stdout().is_terminal() {
         bail!("Run `railway config apply --yes` to apply changes non-interactively.");
     }
 
@@ -329,7 +515,7 @@ fn guard_destructive_apply(args: &Args, destructive: bool) -> Result<()> {
         return Ok(());
     }
 
-    if args.yes || args.json || crate::telemetry::is_agent() {
+    if args.yes || args.plan.is_some() || args.json || crate::telemetry::is_agent() {
         bail!(
             "Destructive Railway configuration changes require explicit confirmation. Review `railway config plan`, then re-run with `railway config apply --confirm-destructive` if the removals are expected."
         );
@@ -392,6 +578,8 @@ async fn invoke_runner(
                 file: args.file.clone(),
                 decrypt_variables: args.decrypt_variables,
                 show_values: args.show_values,
+                environment: args.environment.clone(),
+                expected_plan: args.plan.as_deref().map(load_saved_plan).transpose()?,
             },
             configs,
             linked_project,
@@ -401,6 +589,16 @@ async fn invoke_runner(
         return serde_json::from_value(value)
             .context("Native IaC engine returned a response the CLI could not parse");
     }
+    if args.environment.is_some() {
+        bail!(
+            "--environment is only supported by the CLI engine. Unset RAILWAY_IAC_TS_BIN and RAILWAY_IAC_ENGINE, and drop --runner."
+        );
+    }
+    if args.out.is_some() || args.plan.is_some() {
+        bail!(
+            "Saved plans are only supported by the CLI engine. Unset RAILWAY_IAC_TS_BIN and RAILWAY_IAC_ENGINE, and drop --runner."
+        );
+    }
     let runner = resolve_runner(args.runner.as_deref(), &cwd_path);
 
     let cwd = cwd_path.to_string_lossy().to_string();
@@ -1,12 +1,27 @@
+use std::time::Duration;
+
 use super::*;
 use crate::client::post_graphql;
-use crate::controllers::project::resolve_service_context;
-use crate::gql::queries::deployments::{DeploymentStatus, ResponseData, Variables};
+use crate::controllers::deployment_timeline::{
+    DeploymentTimeline, TimelineComparison, compare_timelines, fetch_timeline, format_duration,
+};
+use crate::controllers::project::{ServiceContext, resolve_service_context};
+use crate::gql::queries::deployments::{
+    DeploymentStatus, DeploymentsDeploymentsEdgesNode, ResponseData, Variables,
+};
+use crate::util::{
+    progress::{create_spinner_if, fail_spinner, success_spinner},
+    prompt::prompt_confirm_with_default,
+};
 use chrono::{DateTime, Local, Utc};
+use is_terminal::IsTerminal;
 use serde::Serialize;
 
 /// Manage deployments
 #[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway deployment list --service api --json\n  railway deployment rollback --service api --yes\n  railway deployment rollback --to <deployment-id> --service api --json --yes\n  railway deployment cancel --service api --yes\n  railway deployment stop <deployment-id> --yes\n  railway deployment approve --service api --yes\n  railway deployment timeline --service api\n  railway deployment timeline <deployment-id> --compare <other-id> --json"
+)]
 pub struct Args {
     #[clap(subcommand)]
     command: Commands,
@@ -27,6 +42,135 @@ enum Commands {
 
     /// Redeploy the latest deployment of a service
     Redeploy(crate::commands::redeploy::Args),
+
+    /// Roll back to a previous successful deployment and wait for it to go live
+    Rollback(RollbackArgs),
+
+    /// Cancel a deployment that is still queued, building or deploying
+    Cancel(LifecycleArgs),
+
+    /// Stop a running deployment
+    Stop(LifecycleArgs),
+
+    /// Approve a deployment that is waiting for approval
+    Approve(LifecycleArgs),
+
+    /// Show where a deployment spent its time, phase by phase
+    Timeline(TimelineArgs),
+}
+
+#[derive(Parser)]
+struct TimelineArgs {
+    /// Deployment ID (defaults to the service's latest deployment)
+    deployment_id: Option<String>,
+
+    /// Another deployment ID to compare against, explaining what got slower
+    #[clap(long, value_name = "DEPLOYMENT_ID")]
+    compare: Option<String>,
+
+    /// Service name or ID (defaults to linked service)
+    #[clap(short, long)]
+    service: Option<String>,
+
+    /// Environment (defaults to linked environment)
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Project ID or name (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT")]
+    project: Option<String>,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct TargetArgs {
+    /// Service name or ID (defaults to linked service)
+    #[clap(short, long)]
+    service: Option<String>,
+
+    /// Environment (defaults to linked environment)
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Project ID or name (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT")]
+    project: Option<String>,
+
+    /// Skip confirmation dialog
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct RollbackArgs {
+    /// Deployment ID to roll back to
+    #[clap(long, value_name = "DEPLOYMENT_ID", conflicts_with = "previous")]
+    to: Option<String>,
+
+    /// Roll back to the deployment before the one currently live (default)
+    #[clap(long)]
+    previous: bool,
+
+    #[clap(flatten)]
+    target: TargetArgs,
+}
+
+#[derive(Parser)]
+struct LifecycleArgs {
+    /// Deployment ID (defaults to the service's most recent matching deployment)
+    deployment_id: Option<String>,
+
+    #[clap(flatten)]
+    target: TargetArgs,
+}
+
+#[derive(Clone, Copy)]
+enum Lifecycle {
+    Cancel,
+    Stop,
+    Approve,
+}
+
+impl Lifecycle {
+    fn verb(self) -> &'static str {
+        match self {
+            Lifecycle::Cancel => "Cancel",
+            Lifecycle::Stop => "Stop",
+            Lifecycle::Approve => "Approve",
+        }
+    }
+
+    fn past(self) -> &'static str {
+        match self {
+            Lifecycle::Cancel => "Cancelled",
+            Lifecycle::Stop => "Stopped",
+            Lifecycle::Approve => "Approved",
+        }
+    }
+
+    /// Whether a deployment in `status` can be acted on.
+    fn applies_to(self, status: &DeploymentStatus) -> bool {
+        match self {
+            Lifecycle::Cancel => is_in_progress(status),
+            Lifecycle::Stop => matches!(status, DeploymentStatus::SUCCESS),
+            Lifecycle::Approve => matches!(status, DeploymentStatus::NEEDS_APPROVAL),
+        }
+    }
+
+    fn nothing_to_do(self) -> &'static str {
+        match self {
+            Lifecycle::Cancel => "No queued, building or deploying deployment found",
+            Lifecycle::Stop => "No running deployment found",
+            Lifecycle::Approve => "No deployment is waiting for approval",
+        }
+    }
 }
 
 #[derive(Parser)]
@@ -81,6 +225,11 @@ pub async fn command(args: Args) -> Result<()> {
             // Call the existing redeploy command implementation
             crate::commands::redeploy::command(redeploy_args).await
         }
+        Commands::Rollback(rollback_args) => rollback_deployment(rollback_args).await,
+        Commands::Cancel(args) => change_deployment(args, Lifecycle::Cancel).await,
+        Commands::Stop(args) => change_deployment(args, Lifecycle::Stop).await,
+        Commands::Approve(args) => change_deployment(args, Lifecycle::Approve).await,
+        Commands::Timeline(args) => show_timeline(args).await,
     }
 }
 
@@ -190,3 +339,576 @@ async fn list_deployments(
 
     Ok(())
 }
+
+fn is_in_progress(status: &DeploymentStatus) -> bool {
+    matches!(
+        status,
+        DeploymentStatus::QUEUED
+            | DeploymentStatus::WAITING
+            | DeploymentStatus::INITIALIZING
+            | DeploymentStatus::BUILDING
+            | DeploymentStatus::DEPLOYING
+    )
+}
+
+async fn fetch_deployments(
+    ctx: &ServiceContext,
+    first: i64,
+) -> Result<Vec<DeploymentsDeploymentsEdgesNode>> {
+    let response: ResponseData = post_graphql::<crate::gql::queries::Deployments, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        Variables {
+            input: crate::gql::queries::deployments::DeploymentListInput {
+                service_id: Some(ctx.service_id.clone()),
+                environment_id: Some(ctx.environment_id.clone()),
+                project_id: Some(ctx.project_id.clone()),
+                status: None,
+                include_deleted: None,
+            },
+            first: Some(first),
+        },
+    )
+    .await?;
+    Ok(response
+        .deployments
+        .edges
+        .into_iter()
+        .map(|edge| edge.node)
+        .collect())
+}
+
+/// The deployment `rollback` should restore, from newest-first `deployments`:
+/// the most recent one Railway can roll back to that was created before the
+/// deployment currently live.
+fn previous_deployment(
+    deployments: &[DeploymentsDeploymentsEdgesNode],
+) -> Option<&DeploymentsDeploymentsEdgesNode> {
+    let live = deployments
+        .iter()
+        .position(|d| d.status == DeploymentStatus::SUCCESS);
+    deployments
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| live.is_none_or(|live| *index > live))
+        .map(|(_, deployment)| deployment)
+        .find(|d| d.can_rollback)
+}
+
+fn confirm(yes: bool, prompt: &str) -> Result<bool> {
+    if yes {
+        Ok(true)
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default(prompt, false)
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    }
+}
+
+async fn rollback_deployment(args: RollbackArgs) -> Result<()> {
+    let target = args.target;
+    let ctx = resolve_service_context(target.project, target.service, target.environment).await?;
+    let deployments = fetch_deployments(&ctx, 50).await?;
+
+    let rollback_to = match &args.to {
+        Some(id) => deployments.iter().find(|d| &d.id == id).with_context(|| {
+            format!(
+                "Deployment {id} is not one of the recent deployments of service {}",
+                ctx.service_name
+            )
+        })?,
+        None => previous_deployment(&deployments).with_context(|| {
+            format!(
+                "No previous deployment of service {} can be rolled back to",
+                ctx.service_name
+            )
+        })?,
+    };
+    if !rollback_to.can_rollback {
+        bail!(
+            "Deployment {} ({:?}) cannot be rolled back to",
+            rollback_to.id,
+            rollback_to.status
+        );
+    }
+
+    let created_at: DateTime<Local> = DateTime::from(rollback_to.created_at);
+    let prompt = format!(
+        "Roll back service {} in environment {} to deployment {} from {}?",
+        ctx.service_name,
+        ctx.environment_name,
+        rollback_to.id,
+        created_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    if !confirm(target.yes, &prompt)? {
+        return Ok(());
+    }
+
+    let known: Vec<String> = deployments.iter().map(|d| d.id.clone()).collect();
+    let requested_at = Utc::now();
+    let mut spinner = create_spinner_if(
+        !target.json,
+        format!("Rolling back service {}...", ctx.service_name),
+    );
+    post_graphql::<mutations::DeploymentRollback, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        mutations::deployment_rollback::Variables {
+            id: rollback_to.id.clone(),
+        },
+    )
+    .await?;
+
+    let (deployment_id, status) =
+        follow_rollback(&ctx, rollback_to, &known, requested_at, spinner.as_mut()).await?;
+    let succeeded = status == DeploymentStatus::SUCCESS;
+
+    if target.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "rolledBackTo": rollback_to.id,
+                "deploymentId": deployment_id,
+                "status": format!("{status:?}"),
+            }))?
+        );
+    } else if let Some(spinner) = &mut spinner {
+        if succeeded {
+            success_spinner(
+                spinner,
+                format!(
+                    "Rolled back service {} to deployment {}",
+                    ctx.service_name.green(),
+                    rollback_to.id
+                ),
+            );
+        } else {
+            fail_spinner(
+                spinner,
+                format!("Rollback deployment {deployment_id} ended as {status:?}"),
+            );
+        }
+    }
+
+    if status == DeploymentStatus::NEEDS_APPROVAL {
+        bail!(
+            "Rollback deployment {deployment_id} is waiting for approval. Approve it with `railway deployment approve {deployment_id}`"
+        );
+    }
+    if !succeeded {
+        bail!("Rollback did not complete: deployment {deployment_id} is {status:?}");
+    }
+    Ok(())
+}
+
+/// How long `rollback` waits for the new deployment to appear and settle.
+const ROLLBACK_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Wait for the deployment the rollback created to appear, then poll it until
+/// it settles or needs approval, keeping the spinner on the latest status.
+async fn follow_rollback(
+    ctx: &ServiceContext,
+    rollback_to: &DeploymentsDeploymentsEdgesNode,
+    known: &[String],
+    requested_at: DateTime<Utc>,
+    mut spinner: Option<&mut indicatif::ProgressBar>,
+) -> Result<(String, DeploymentStatus)> {
+    let deadline = tokio::time::Instant::now() + ROLLBACK_TIMEOUT;
+    let mut followed: Option<String> = None;
+    let mut last_status = None;
+    loop {
+        if tokio::time::Instant::now() >= deadline {
+            match &followed {
+                Some(id) => bail!(
+                    "Timed out after {} minutes waiting for rollback deployment {id} to finish (last status {:?}). Check it with `railway deployment list`",
+                    ROLLBACK_TIMEOUT.as_secs() / 60,
+                    last_status.unwrap_or(DeploymentStatus::QUEUED)
+                ),
+                None => bail!(
+                    "Timed out after {} minutes waiting for the rollback deployment of service {} to appear. Check `railway deployment list`",
+                    ROLLBACK_TIMEOUT.as_secs() / 60,
+                    ctx.service_name
+                ),
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let deployments = fetch_deployments(ctx, 10).await?;
+        let deployment = match &followed {
+            Some(id) => deployments.iter().find(|d| &d.id == id),
+            None => rollback_candidate(&deployments, rollback_to, known, requested_at),
+        };
+        let Some(deployment) = deployment else {
+            continue;
+        };
+        followed = Some(deployment.id.clone());
+        if last_status.as_ref() != Some(&deployment.status) {
+            if let Some(spinner) = spinner.as_deref_mut() {
+                spinner.set_message(format!(
+                    "Rolling back service {}: {:?}",
+                    ctx.service_name, deployment.status
+                ));
+            }
+            last_status = Some(deployment.status.clone());
+        }
+        if !is_in_progress(&deployment.status) {
+            return Ok((deployment.id.clone(), deployment.status.clone()));
+        }
+    }
+}
+
+/// Deployment metadata that identifies what was deployed, which a rollback
+/// copies from the deployment it restores.
+const SOURCE_META_KEYS: [&str; 2] = ["commitHash", "image"];
+
+/// The deployment `deploymentRollback` created, from newest-first
+/// `deployments`. The mutation only returns whether it succeeded, so this is
+/// the oldest deployment that wasn't listed before the request, was created
+/// after it, and deploys the same commit or image as `rollback_to`. Deploys
+/// someone else triggers meanwhile carry a different source and are skipped.
+fn rollback_candidate<'a>(
+    deployments: &'a [DeploymentsDeploymentsEdgesNode],
+    rollback_to: &DeploymentsDeploymentsEdgesNode,
+    known: &[String],
+    requested_at: DateTime<Utc>,
+) -> Option<&'a DeploymentsDeploymentsEdgesNode> {
+    // Allow for the API's clock running slightly behind ours.
+    let earliest = requested_at - chrono::Duration::seconds(30);
+    let source = |d: &DeploymentsDeploymentsEdgesNode| {
+        SOURCE_META_KEYS.map(|key| {
+            d.meta
+                .as_ref()
+                .and_then(|meta| meta.get(key))
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        })
+    };
+    let wanted = source(rollback_to);
+    deployments
+        .iter()
+        .rev()
+        .filter(|d| !known.contains(&d.id) && d.created_at >= earliest)
+        .find(|d| {
+            let found = source(d);
+            wanted
+                .iter()
+                .zip(&found)
+                .all(|(wanted, found)| wanted.is_none() || wanted == found)
+        })
+}
+
+async fn change_deployment(args: LifecycleArgs, action: Lifecycle) -> Result<()> {
+    let target = args.target;
+    let ctx = resolve_service_context(target.project, target.service, target.environment).await?;
+
+    let deployment_id = match args.deployment_id {
+        Some(id) => id,
+        None => fetch_deployments(&ctx, 20)
+            .await?
+            .into_iter()
+            .find(|d| action.applies_to(&d.status))
+            .map(|d| d.id)
+            .with_context(|| {
+                format!(
+                    "{} for service {}",
+                    action.nothing_to_do(),
+                    ctx.service_name
+                )
+            })?,
+    };
+
+    let prompt = format!(
+        "{} deployment {deployment_id} of service {} in environment {}?",
+        action.verb(),
+        ctx.service_name,
+        ctx.environment_name
+    );
+    if !confirm(target.yes, &prompt)? {
+        return Ok(());
+    }
+
+    let backboard = ctx.configs.get_backboard();
+    let id = deployment_id.clone();
+    match action {
+        Lifecycle::Cancel => {
+            post_graphql::<mutations::DeploymentCancel, _>(
+                &ctx.client,
+                backboard,
+                mutations::deployment_cancel::Variables { id },
+            )
+            .await?;
+        }
+        Lifecycle::Stop => {
+            post_graphql::<mutations::DeploymentStop, _>(
+                &ctx.client,
+                backboard,
+                mutations::deployment_stop::Variables { id },
+            )
+            .await?;
+        }
+        Lifecycle::Approve => {
+            post_graphql::<mutations::DeploymentApprove, _>(
+                &ctx.client,
+                backboard,
+                mutations::deployment_approve::Variables { id },
+            )
+            .await?;
+        }
+    }
+
+    if target.json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": deployment_id, "success": true })
+        );
+    } else {
+        println!(
+            "{} deployment {} of service {}",
+            action.past(),
+            deployment_id,
+            ctx.service_name.green()
+        );
+    }
+    Ok(())
+}
+
+async fn show_timeline(args: TimelineArgs) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let deployment_id = match args.deployment_id {
+        Some(id) => id,
+        None => {
+            let ctx = resolve_service_context(args.project, args.service, args.environment).await?;
+            fetch_deployments(&ctx, 1)
+                .await?
+                .into_iter()
+                .next()
+                .map(|d| d.id)
+                .with_context(|| format!("No deployments found for service {}", ctx.service_name))?
+        }
+    };
+
+    let backboard = configs.get_backboard();
+    let timeline = fetch_timeline(&client, &backboard, &deployment_id).await?;
+    let comparison = match &args.compare {
+        Some(baseline_id) => {
+            let baseline = fetch_timeline(&client, &backboard, baseline_id).await?;
+            Some(compare_timelines(&timeline, &baseline))
+        }
+        None => None,
+    };
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "timeline": timeline,
+                "comparison": comparison,
+            }))?
+        );
+        return Ok(());
+    }
+
+    print_timeline(&timeline);
+    if let Some(comparison) = &comparison {
+        println!();
+        print_comparison(comparison);
+    }
+    Ok(())
+}
+
+const TIMELINE_BAR_WIDTH: usize = 40;
+
+fn print_timeline(timeline: &DeploymentTimeline) {
+    let created_at: DateTime<Local> = DateTime::from(timeline.created_at);
+    println!(
+        "{} {} {}",
+        "Deployment".bold(),
+        timeline.deployment_id,
+        format!("({})", timeline.status).dimmed()
+    );
+    println!(
+        "{} {}, {} {}",
+        "Started".dimmed(),
+        created_at.format("%Y-%m-%d %H:%M:%S %Z"),
+        "total".dimmed(),
+        format_duration(timeline.total_ms)
+    );
+    println!();
+
+    if timeline.phases.is_empty() {
+        println!("No deployment events recorded yet");
+        return;
+    }
+
+    let total = timeline.total_ms.max(1) as f64;
+    let label_width = timeline
+        .phases
+        .iter()
+        .map(|phase| phase_label(phase).len())
+        .max()
+        .unwrap_or(0);
+    for phase in &timeline.phases {
+        let start = ((phase.offset_ms as f64 / total) * TIMELINE_BAR_WIDTH as f64) as usize;
+        let start = start.min(TIMELINE_BAR_WIDTH - 1);
+        let length = phase
+            .duration_ms
+            .map(|ms| ((ms as f64 / total) * TIMELINE_BAR_WIDTH as f64).round() as usize)
+            .unwrap_or(TIMELINE_BAR_WIDTH - start)
+            .clamp(1, TIMELINE_BAR_WIDTH - start);
+        let bar = "█".repeat(length);
+        let bar = if phase.error.is_some() {
+            bar.red()
+        } else if phase.duration_ms.is_none() {
+            bar.blue()
+        } else if phase.skipped {
+            bar.dimmed()
+        } else {
+            bar.green()
+        };
+        let duration = match phase.duration_ms {
+            Some(ms) => format_duration(ms),
+            None => "running".to_string(),
+        };
+        println!(
+            "  {:<label_width$}  {}{}{}  {}",
+            phase_label(phase),
+            " ".repeat(start),
+            bar,
+            " ".repeat(TIMELINE_BAR_WIDTH - start - length),
+            duration
+        );
+        if let Some(error) = &phase.error {
+            println!("  {:<label_width$}  {}", "", error.red());
+        }
+    }
+}
+
+fn phase_label(phase: &crate::controllers::deployment_timeline::TimelinePhase) -> String {
+    match (phase.attempt, phase.max_attempts) {
+        (Some(attempt), Some(max)) => format!("{} ({attempt}/{max})", phase.label),
+        (Some(attempt), None) => format!("{} (#{attempt})", phase.label),
+        _ if phase.skipped => format!("{} (skipped)", phase.label),
+        _ => phase.label.clone(),
+    }
+}
+
+fn print_comparison(comparison: &TimelineComparison) {
+    let total = if comparison.total_delta_ms > 0 {
+        format!("+{}", format_duration(comparison.total_delta_ms)).red()
+    } else {
+        format_duration(comparison.total_delta_ms).green()
+    };
+    println!(
+        "{} {} {} {}",
+        "Compared with".bold(),
+        comparison.baseline_id,
+        "total".dimmed(),
+        total
+    );
+    for delta in &comparison.phases {
+        let change = match delta.delta_ms {
+            0 => "±0ms".dimmed(),
+            ms if ms > 0 => format!("+{}", format_duration(ms)).red(),
+            ms => format_duration(ms).green(),
+        };
+        println!(
+            "  {:<24} {:>10} → {:<10} {}",
+            delta.label,
+            delta.baseline_ms.map(format_duration).unwrap_or("—".into()),
+            delta.current_ms.map(format_duration).unwrap_or("—".into()),
+            change
+        );
+    }
+    if !comparison.findings.is_empty() {
+        println!();
+        for finding in &comparison.findings {
+            println!("  {} {}", "•".yellow(), finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment_with(
+        id: &str,
+        commit: &str,
+        created_at: DateTime<Utc>,
+    ) -> DeploymentsDeploymentsEdgesNode {
+        DeploymentsDeploymentsEdgesNode {
+            id: id.to_string(),
+            created_at,
+            status: DeploymentStatus::BUILDING,
+            can_rollback: true,
+            meta: Some(serde_json::json!({ "commitHash": commit })),
+        }
+    }
+
+    #[test]
+    fn rollback_follows_the_deployment_of_the_restored_source() {
+        let requested_at = Utc::now();
+        let later = requested_at + chrono::Duration::seconds(5);
+        let target = deployment_with("target", "abc", requested_at - chrono::Duration::days(1));
+        // Newest first: someone else's deploy, then the rollback, then an old one.
+        let deployments = vec![
+            deployment_with("other", "def", later),
+            deployment_with("rollback", "abc", later),
+            deployment_with("live", "xyz", requested_at - chrono::Duration::hours(1)),
+        ];
+        let known = vec!["live".to_string()];
+        assert_eq!(
+            rollback_candidate(&deployments, &target, &known, requested_at)
+                .unwrap()
+                .id,
+            "rollback"
+        );
+
+        // Not created yet: keep waiting rather than follow an unrelated deploy.
+        assert!(rollback_candidate(&deployments[..1], &target, &known, requested_at).is_none());
+        // Deployments from before the request aren't ours either.
+        let stale = vec![deployment_with(
+            "stale",
+            "abc",
+            requested_at - chrono::Duration::minutes(5),
+        )];
+        assert!(rollback_candidate(&stale, &target, &[], requested_at).is_none());
+    }
+
+    fn deployment(
+        id: &str,
+        status: DeploymentStatus,
+        can_rollback: bool,
+    ) -> DeploymentsDeploymentsEdgesNode {
+        DeploymentsDeploymentsEdgesNode {
+            id: id.to_string(),
+            created_at: Utc::now(),
+            status,
+            can_rollback,
+            meta: None,
+        }
+    }
+
+    #[test]
+    fn rollback_skips_the_live_deployment() {
+        let deployments = vec![
+            deployment("failed", DeploymentStatus::FAILED, false),
+            deployment("live", DeploymentStatus::SUCCESS, true),
+            deployment("crashed", DeploymentStatus::CRASHED, false),
+            deployment("older", DeploymentStatus::REMOVED, true),
+        ];
+        assert_eq!(previous_deployment(&deployments).unwrap().id, "older");
+    }
+
+    #[test]
+    fn rollback_without_a_live_deployment_takes_the_newest_candidate() {
+        let deployments = vec![
+            deployment("crashed", DeploymentStatus::CRASHED, false),
+            deployment("removed", DeploymentStatus::REMOVED, true),
+        ];
+        assert_eq!(previous_deployment(&deployments).unwrap().id, "removed");
+        assert!(previous_deployment(&deployments[..1]).is_none());
+    }
+}
@@ -0,0 +1,396 @@
+use std::path::{Path, PathBuf};
+
+use is_terminal::IsTerminal;
+use serde::Serialize;
+use serde_json::Value;
+use similar::{ChangeTag, TextDiff};
+
+use crate::{
+    controllers::project::{ServiceContext, resolve_service_context},
+    util::{progress::create_spinner_if, prompt::prompt_confirm_with_default},
+};
+
+use super::*;
+
+/// Manage edge routing rules for a service
+#[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway edge rules pull --service web > edge-rules.yaml\n  railway edge rules validate edge-rules.yaml --service web\n  railway edge rules push edge-rules.yaml --service web\n  railway edge rules push edge-rules.yaml --environment production --yes --json\n\nAutomation notes:\n  Rules are evaluated at the edge before requests reach the service: redirects, header rewrites, path routing, blocks and cache overrides.\n  Push replaces the whole ruleset. An empty file clears it.\n  Rules without an `id` are assigned one on push; pull again afterwards to keep the file in sync."
+)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: Commands,
+
+    /// Service name or ID (defaults to linked service)
+    #[clap(short, long, global = true)]
+    service: Option<String>,
+
+    /// Environment to use (defaults to linked environment)
+    #[clap(short, long, global = true)]
+    environment: Option<String>,
+
+    /// Project ID to use (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT_ID", global = true)]
+    project: Option<String>,
+
+    /// Output in JSON format
+    #[clap(long, global = true)]
+    json: bool,
+}
+
+#[derive(Parser)]
+enum Commands {
+    /// Pull, validate and push edge rules
+    #[clap(subcommand, visible_alias = "rule")]
+    Rules(RulesCommands),
+}
+
+#[derive(Parser)]
+enum RulesCommands {
+    /// Print the service's current edge rules as YAML (or JSON with --json)
+    Pull(PullArgs),
+
+    /// Check a rules file against the server-side validator without applying it
+    Validate(FileArgs),
+
+    /// Replace the service's edge rules with the contents of a file
+    Push(PushArgs),
+}
+
+#[derive(Parser)]
+struct PullArgs {
+    /// Write to this file instead of stdout
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Parser)]
+struct FileArgs {
+    /// Rules file (YAML or JSON)
+    file: PathBuf,
+}
+
+#[derive(Parser)]
+struct PushArgs {
+    /// Rules file (YAML or JSON)
+    file: PathBuf,
+
+    /// Skip confirmation dialog
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Diagnostic {
+    path: String,
+    code: String,
+    message: String,
+}
+
+pub async fn command(args: Args) -> Result<()> {
+    let ctx = resolve_service_context(args.project, args.service, args.environment).await?;
+    match args.command {
+        Commands::Rules(RulesCommands::Pull(pull)) => pull_rules(&ctx, pull, args.json).await,
+        Commands::Rules(RulesCommands::Validate(file)) => {
+            validate_rules(&ctx, file, args.json).await
+        }
+        Commands::Rules(RulesCommands::Push(push)) => push_rules(&ctx, push, args.json).await,
+    }
+}
+
+async fn pull_rules(ctx: &ServiceContext, args: PullArgs, json: bool) -> Result<()> {
+    let rules = fetch_rules(ctx).await?;
+    let content = if json {
+        format!("{}\n", serde_json::to_string_pretty(&rules)?)
+    } else {
+        format!(
+            "# Edge rules for {} ({}). Apply with `railway edge rules push <file>`.\n{}",
+            ctx.service_name,
+            ctx.environment_name,
+            rules_to_yaml(&rules)?
+        )
+    };
+
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Wrote edge rules to {}", path.display().to_string().green());
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+async fn validate_rules(ctx: &ServiceContext, args: FileArgs, json: bool) -> Result<()> {
+    let rules = read_rules_file(&args.file)?
+        .context("The rules file is empty; there is nothing to validate")?;
+    let diagnostics = validate(ctx, &rules).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+    } else if diagnostics.is_empty() {
+        println!("{} is valid", args.file.display().to_string().green());
+    } else {
+        print_diagnostics(&args.file, &diagnostics);
+    }
+    if !diagnostics.is_empty() {
+        bail!(
+            "{} has {} problem(s)",
+            args.file.display(),
+            diagnostics.len()
+        );
+    }
+    Ok(())
+}
+
+async fn push_rules(ctx: &ServiceContext, args: PushArgs, json: bool) -> Result<()> {
+    let rules = read_rules_file(&args.file)?;
+    if let Some(rules) = &rules {
+        let diagnostics = validate(ctx, rules).await?;
+        if !diagnostics.is_empty() {
+            if !json {
+                print_diagnostics(&args.file, &diagnostics);
+            }
+            bail!(
+                "{} has {} problem(s); nothing was pushed",
+                args.file.display(),
+                diagnostics.len()
+            );
+        }
+    }
+
+    let current = fetch_rules(ctx).await?;
+    let new = rules.clone().unwrap_or(Value::Null);
+    let before = rules_to_yaml(&current)?;
+    let after = rules_to_yaml(&new)?;
+    if before == after {
+        if json {
+            println!("{}", serde_json::json!({ "changed": false }));
+        } else {
+            println!(
+                "Edge rules for {} are already up to date",
+                ctx.service_name.bold()
+            );
+        }
+        return Ok(());
+    }
+
+    if !json {
+        println!(
+            "Edge rules for {} ({}):\n",
+            ctx.service_name.bold(),
+            ctx.environment_name
+        );
+        print_diff(&render_diff(&before, &after, &args.file));
+        println!();
+    }
+
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default("Replace the live edge rules with these?", false)?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    let spinner = create_spinner_if(!json, "Pushing edge rules...".into());
+    let updated = post_graphql::<mutations::UpdateServiceEdgeRules, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        mutations::update_service_edge_rules::Variables {
+            input: mutations::update_service_edge_rules::UpdateServiceEdgeRulesInput {
+                service_id: ctx.service_id.clone(),
+                environment_id: ctx.environment_id.clone(),
+                edge_rules: rules,
+            },
+        },
+    )
+    .await?
+    .update_service_edge_rules;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+
+    let live = updated.edge_rules.unwrap_or(Value::Null);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "changed": true,
+                "edgeRules": live,
+            }))?
+        );
+        return Ok(());
+    }
+    println!("Pushed edge rules for {}", ctx.service_name.green());
+    if rules_to_yaml(&live)? != after {
+        println!(
+            "{}",
+            "Some rules were assigned IDs. Run `railway edge rules pull` to update your file."
+                .dimmed()
+        );
+    }
+    Ok(())
+}
+
+async fn fetch_rules(ctx: &ServiceContext) -> Result<Value> {
+    let response = post_graphql::<queries::ServiceEdgeRules, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        queries::service_edge_rules::Variables {
+            environment_id: ctx.environment_id.clone(),
+            service_id: ctx.service_id.clone(),
+        },
+    )
+    .await?;
+    Ok(response
+        .service_instance
+        .edge_config
+        .and_then(|config| config.edge_rules)
+        .unwrap_or(Value::Null))
+}
+
+async fn validate(ctx: &ServiceContext, rules: &Value) -> Result<Vec<Diagnostic>> {
+    let diagnostics = post_graphql::<queries::ValidateServiceEdgeRules, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        queries::validate_service_edge_rules::Variables {
+            input: queries::validate_service_edge_rules::ValidateServiceEdgeRulesInput {
+                service_id: ctx.service_id.clone(),
+                environment_id: ctx.environment_id.clone(),
+                edge_rules: rules.clone(),
+            },
+        },
+    )
+    .await?
+    .validate_service_edge_rules;
+    Ok(diagnostics
+        .into_iter()
+        .map(|d| Diagnostic {
+            path: d.path,
+            code: d.code,
+            message: d.message,
+        })
+        .collect())
+}
+
+fn print_diagnostics(file: &Path, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        let path = if diagnostic.path.is_empty() {
+            "(root)"
+        } else {
+            &diagnostic.path
+        };
+        eprintln!(
+            "{}: {} {} {}",
+            file.display(),
+            path.bold(),
+            diagnostic.message,
+            format!("[{}]", diagnostic.code).dimmed()
+        );
+    }
+}
+
+/// Reads a YAML or JSON rules file. An empty (or comment-only) file means "no
+/// rules" and is returned as `None`.
+fn read_rules_file(path: &Path) -> Result<Option<Value>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_rules(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn parse_rules(content: &str) -> Result<Option<Value>> {
+    let value: Value = serde_yaml::from_str(content)?;
+    Ok((!value.is_null()).then_some(value))
+}
+
+fn rules_to_yaml(rules: &Value) -> Result<String> {
+    if rules.is_null() {
+        return Ok(String::new());
+    }
+    Ok(serde_yaml::to_string(rules)?)
+}
+
+/// A unified diff of the live rules (`before`) against the file (`after`).
+fn render_diff(before: &str, after: &str, file: &Path) -> Vec<String> {
+    let diff = TextDiff::from_lines(before, after);
+    let mut lines = vec!["--- live".to_string(), format!("+++ {}", file.display())];
+    for group in diff.grouped_ops(3) {
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let sign = match change.tag() {
+                    ChangeTag::Delete => '-',
+                    ChangeTag::Insert => '+',
+                    ChangeTag::Equal => ' ',
+                };
+                lines.push(format!(
+                    "{sign}{}",
+                    change.to_string_lossy().trim_end_matches('\n')
+                ));
+            }
+        }
+    }
+    lines
+}
+
+fn print_diff(lines: &[String]) {
+    for line in lines {
+        match line.chars().next() {
+            Some('-') => println!("{}", line.red()),
+            Some('+') => println!("{}", line.green()),
+            _ => println!("{line}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_yaml_json_and_empty_files() {
+        let yaml = "# redirects\n- id: rul_1\n  when: path == \"/old\"\n  action:\n    type: redirect\n    to: /new\n";
+        assert_eq!(
+            parse_rules(yaml).unwrap(),
+            Some(json!([{
+                "id": "rul_1",
+                "when": "path == \"/old\"",
+                "action": { "type": "redirect", "to": "/new" }
+            }]))
+        );
+        assert_eq!(
+            parse_rules(r#"[{"action": {"type": "block"}}]"#).unwrap(),
+            Some(json!([{ "action": { "type": "block" } }]))
+        );
+        assert_eq!(parse_rules("").unwrap(), None);
+        assert_eq!(parse_rules("# no rules yet\n").unwrap(), None);
+        assert!(parse_rules("- [unclosed").is_err());
+    }
+
+    #[test]
+    fn diff_only_marks_changed_lines() {
+        let before = rules_to_yaml(&json!([{ "id": "rul_1", "action": "block" }])).unwrap();
+        let after = rules_to_yaml(&json!([{ "id": "rul_1", "action": "allow" }])).unwrap();
+        let diff = render_diff(&before, &after, Path::new("rules.yaml"));
+        assert_eq!(
+            diff,
+            vec![
+                "--- live",
+                "+++ rules.yaml",
+                "-- action: block",
+                "+- action: allow",
+                "   id: rul_1",
+            ]
+        );
+        assert_eq!(rules_to_yaml(&Value::Null).unwrap(), "");
+    }
+}
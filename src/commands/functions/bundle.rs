@@ -0,0 +1,387 @@
+//! Packs a function's entry file, the local modules it imports and its
+//! dependency manifest into one start command.
+//!
+//! Functions run as `./run.sh <base64 source>`, where the runtime decodes the
+//! source into a single file and runs it with Bun. A function that is just one
+//! file keeps shipping that file as-is. Anything bigger ships a small bootstrap
+//! script instead: it carries every file gzipped, unpacks them into a temp
+//! directory, installs dependencies when a `package.json` came along, then
+//! imports the real entry file. The bootstrap's first line records the bundle
+//! hash so `pull` and `link` can compare remote and local copies cheaply.
+
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+    path::{Component, Path, PathBuf},
+    sync::LazyLock,
+};
+
+use anyhow::{Context, bail};
+use base64::prelude::*;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+
+use super::*;
+
+const MARKER: &str = "// railway-function-bundle v1";
+
+/// Extensions tried, in order, for an import specifier without one.
+const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "mjs", "cjs", "jsx"];
+
+/// Dependency files shipped alongside the sources when they sit next to the
+/// entry file.
+const MANIFEST_FILES: &[&str] = &["package.json", "bun.lock", "bun.lockb"];
+
+/// `from "./x"`, `import "./x"`, `import("./x")` and `require("./x")`.
+static RELATIVE_IMPORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:\bfrom\s*|\bimport\s*\(?\s*|\brequire\s*\(\s*)["'](\.{1,2}/[^"'\n]+)["']"#)
+        .unwrap()
+});
+
+static PAYLOAD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"Buffer\.from\("([A-Za-z0-9+/=]*)", "base64"\)"#).unwrap());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    /// Entry file, relative to the bundle root
+    pub entry: String,
+    /// Every file in the bundle keyed by its `/`-separated path relative to
+    /// the entry file's directory. Includes the entry itself.
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+impl Bundle {
+    /// Collects `entry` plus everything it (transitively) imports through
+    /// relative specifiers, and the dependency manifest next to it.
+    pub fn collect(entry: &Path) -> Result<Self> {
+        let entry = entry
+            .canonicalize()
+            .with_context(|| format!("Failed to read {}", entry.display()))?;
+        let root = entry
+            .parent()
+            .context("The function path has no parent directory")?
+            .to_path_buf();
+
+        let mut files = BTreeMap::new();
+        let mut pending = vec![entry.clone()];
+        while let Some(file) = pending.pop() {
+            let key = relative_key(&root, &file)?;
+            if files.contains_key(&key) {
+                continue;
+            }
+            let content = std::fs::read(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            if is_source(&file) {
+                let source = String::from_utf8_lossy(&content);
+                for specifier in relative_imports(&source) {
+                    let resolved = resolve_import(&file, &specifier)
+                        .with_context(|| format!("Cannot resolve import '{specifier}' in {key}"))?;
+                    if !resolved.starts_with(&root) {
+                        bail!(
+                            "{key} imports '{specifier}', which is outside the function's directory ({}). Move shared modules next to the function.",
+                            root.display()
+                        );
+                    }
+                    pending.push(resolved);
+                }
+            }
+            files.insert(key, content);
+        }
+
+        for name in MANIFEST_FILES {
+            let path = root.join(name);
+            if path.is_file() {
+                let content = std::fs::read(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                files.insert((*name).to_string(), content);
+            }
+        }
+
+        Ok(Self {
+            entry: relative_key(&root, &entry)?,
+            files,
+        })
+    }
+
+    /// A bundle holding just one file ships that file directly.
+    pub fn single(entry: String, content: Vec<u8>) -> Self {
+        Self {
+            files: BTreeMap::from([(entry.clone(), content)]),
+            entry,
+        }
+    }
+
+    pub fn is_single_file(&self) -> bool {
+        self.files.len() == 1
+    }
+
+    pub fn entry_content(&self) -> &[u8] {
+        self.files
+            .get(&self.entry)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// SHA-256 over every path and its contents. Only the entry's content
+    /// counts for single-file functions, so renaming the local file doesn't
+    /// make it look different from the deployed copy.
+    pub fn hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        if self.is_single_file() {
+            hasher.update(self.entry_content());
+        } else {
+            for (path, content) in &self.files {
+                hasher.update(path.as_bytes());
+                hasher.update([0]);
+                hasher.update((content.len() as u64).to_le_bytes());
+                hasher.update(content);
+            }
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// The source handed to `run.sh`: the entry itself for single-file
+    /// functions, otherwise a bootstrap script carrying every file.
+    pub fn to_source(&self) -> Result<Vec<u8>> {
+        if self.is_single_file() {
+            return Ok(self.entry_content().to_vec());
+        }
+
+        let encoded: BTreeMap<&str, String> = self
+            .files
+            .iter()
+            .map(|(path, content)| (path.as_str(), BASE64_STANDARD.encode(content)))
+            .collect();
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::best());
+        gzip.write_all(&serde_json::to_vec(&encoded)?)?;
+        let payload = BASE64_STANDARD.encode(gzip.finish()?);
+
+        let hash = self.hash();
+        let entry = serde_json::to_string(&self.entry)?;
+        let install = if self.files.contains_key("package.json") {
+            "Bun.spawnSync([\"bun\", \"install\", \"--production\"], { cwd: root, stdout: \"inherit\", stderr: \"inherit\" });\n"
+        } else {
+            ""
+        };
+        Ok(format!(
+            "{MARKER} sha256={hash} entry={entry}\n\
+             import {{ mkdirSync, writeFileSync }} from \"node:fs\";\n\
+             import {{ dirname, join }} from \"node:path\";\n\
+             const root = \"/tmp/railway-function-{short}\";\n\
+             const files = JSON.parse(new TextDecoder().decode(Bun.gunzipSync(Buffer.from(\"{payload}\", \"base64\"))));\n\
+             for (const [path, content] of Object.entries(files)) {{\n  \
+               const target = join(root, path);\n  \
+               mkdirSync(dirname(target), {{ recursive: true }});\n  \
+               writeFileSync(target, Buffer.from(content, \"base64\"));\n\
+             }}\n\
+             {install}\
+             await import(join(root, {entry}));\n",
+            short = &hash[..12],
+        )
+        .into_bytes())
+    }
+
+    /// Reads back what [`Bundle::to_source`] produced. Sources without the
+    /// bundle marker are single-file functions and are keyed by `entry_name`.
+    pub fn from_source(source: &[u8], entry_name: &str) -> Result<Self> {
+        let text = String::from_utf8_lossy(source);
+        let Some(header) = text.lines().next().and_then(|l| l.strip_prefix(MARKER)) else {
+            return Ok(Self::single(entry_name.to_string(), source.to_vec()));
+        };
+
+        let entry: String = header
+            .split_once("entry=")
+            .map(|(_, entry)| serde_json::from_str(entry.trim()))
+            .transpose()?
+            .context("The function bundle has no entry file")?;
+        let payload = PAYLOAD
+            .captures(&text)
+            .and_then(|c| c.get(1))
+            .context("The function bundle has no payload")?;
+
+        let mut json = Vec::new();
+        GzDecoder::new(BASE64_STANDARD.decode(payload.as_str())?.as_slice())
+            .read_to_end(&mut json)
+            .context("Failed to decompress the function bundle")?;
+        let encoded: BTreeMap<String, String> = serde_json::from_slice(&json)?;
+        let files = encoded
+            .into_iter()
+            .map(|(path, content)| Ok((path, BASE64_STANDARD.decode(content)?)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self { entry, files })
+    }
+
+    /// Writes the bundle out, putting the entry at `entry_path` and every
+    /// other file relative to its directory. Returns the paths written.
+    pub fn write_to(&self, entry_path: &Path) -> Result<Vec<PathBuf>> {
+        let root = entry_path.parent().unwrap_or(Path::new("."));
+        let mut written = Vec::new();
+        for (key, content) in &self.files {
+            let path = if *key == self.entry {
+                entry_path.to_path_buf()
+            } else {
+                let relative = Path::new(key);
+                if relative
+                    .components()
+                    .any(|c| !matches!(c, Component::Normal(_)))
+                {
+                    bail!("The function bundle contains an unsafe path: {key}");
+                }
+                root.join(relative)
+            };
+            if let Some(parent) = path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+fn is_source(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+/// Whether a change to `path` can affect a bundle: sources and manifests
+/// outside `node_modules`.
+pub fn is_bundle_input(path: &Path) -> bool {
+    if path.components().any(|c| c.as_os_str() == "node_modules") {
+        return false;
+    }
+    is_source(path)
+        || path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| MANIFEST_FILES.contains(&name))
+}
+
+fn relative_imports(source: &str) -> Vec<String> {
+    RELATIVE_IMPORT
+        .captures_iter(source)
+        .map(|c| c[1].to_string())
+        .collect()
+}
+
+fn relative_key(root: &Path, path: &Path) -> Result<String> {
+    let relative = path
+        .strip_prefix(root)
+        .with_context(|| format!("{} is outside {}", path.display(), root.display()))?;
+    Ok(relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Resolves a relative specifier the way Bun does for local files: the exact
+/// path, then with each source extension, then `.js` swapped for `.ts`, then
+/// as a directory with an `index` file.
+fn resolve_import(from: &Path, specifier: &str) -> Option<PathBuf> {
+    let base = from.parent()?.join(specifier);
+    let mut candidates = vec![base.clone()];
+    for ext in SOURCE_EXTENSIONS {
+        let mut with_ext = base.clone().into_os_string();
+        with_ext.push(format!(".{ext}"));
+        candidates.push(with_ext.into());
+    }
+    if base.extension().is_some_and(|ext| ext == "js") {
+        candidates.push(base.with_extension("ts"));
+        candidates.push(base.with_extension("tsx"));
+    }
+    for ext in SOURCE_EXTENSIONS {
+        candidates.push(base.join(format!("index.{ext}")));
+    }
+    candidates
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+        .and_then(|found| found.canonicalize().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn collects_relative_imports_and_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "index.ts",
+            "import { greet } from \"./lib/greet.js\";\nimport config from './config.json';\nimport { Hono } from \"hono\";\nconst db = await import(\"./db\");\n",
+        );
+        write(root, "lib/greet.ts", "export * from \"../util\";\n");
+        write(root, "util/index.ts", "export const greet = () => 'hi';\n");
+        write(root, "config.json", "{}");
+        write(root, "db.ts", "export default {};\n");
+        write(root, "package.json", "{\"dependencies\":{\"hono\":\"^4\"}}");
+        write(root, "unused.ts", "");
+
+        let bundle = Bundle::collect(&root.join("index.ts")).unwrap();
+        assert_eq!(bundle.entry, "index.ts");
+        assert_eq!(
+            bundle.files.keys().collect::<Vec<_>>(),
+            vec![
+                "config.json",
+                "db.ts",
+                "index.ts",
+                "lib/greet.ts",
+                "package.json",
+                "util/index.ts"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_missing_and_escaping_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "shared.ts", "");
+        write(dir.path(), "fn/index.ts", "import \"../shared\";\n");
+        write(dir.path(), "fn/broken.ts", "import \"./missing\";\n");
+
+        let err = Bundle::collect(&dir.path().join("fn/index.ts")).unwrap_err();
+        assert!(err.to_string().contains("outside the function's directory"));
+        let err = Bundle::collect(&dir.path().join("fn/broken.ts")).unwrap_err();
+        assert!(format!("{err:#}").contains("Cannot resolve import './missing'"));
+    }
+
+    #[test]
+    fn source_round_trips() {
+        let single = Bundle::single("main.ts".into(), b"console.log(1)".to_vec());
+        assert_eq!(single.to_source().unwrap(), b"console.log(1)");
+        assert_eq!(
+            Bundle::from_source(&single.to_source().unwrap(), "other.ts")
+                .unwrap()
+                .hash(),
+            single.hash()
+        );
+
+        let bundle = Bundle {
+            entry: "index.ts".into(),
+            files: BTreeMap::from([
+                ("index.ts".into(), b"import './lib/a'".to_vec()),
+                ("lib/a.ts".into(), b"export {}".to_vec()),
+                ("package.json".into(), b"{}".to_vec()),
+            ]),
+        };
+        let source = bundle.to_source().unwrap();
+        let text = String::from_utf8(source.clone()).unwrap();
+        assert!(text.starts_with(&format!("{MARKER} sha256={}", bundle.hash())));
+        assert!(text.contains("bun\", \"install\""));
+        assert_eq!(Bundle::from_source(&source, "ignored.ts").unwrap(), bundle);
+    }
+}
@@ -5,8 +5,13 @@ use similar::{ChangeTag, TextDiff};
 
 use super::*;
 use base64::prelude::*;
-use std::{path::Path, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    str::FromStr,
+};
 
+use super::bundle::Bundle;
 use crate::{
     controllers::project::{
         ProjectEnvironmentInstances, ProjectServiceInstanceEdge, ProjectServiceInstanceNode,
@@ -58,14 +63,27 @@ pub fn confirm(arg: Option<bool>, terminal: bool, message: &str) -> Result<bool>
 }
 
 pub fn get_start_cmd(path: &Path) -> Result<String> {
-    let content = std::fs::read(path)?;
-    let cmd = format!("./run.sh {}", BASE64_STANDARD.encode(content));
+    Ok(get_start_cmd_with_bundle(path)?.0)
+}
+
+/// Bundles the function at `path` and returns the start command along with
+/// the bundle it encodes.
+pub fn get_start_cmd_with_bundle(path: &Path) -> Result<(String, Bundle)> {
+    let bundle = Bundle::collect(path)?;
+    let cmd = format!("./run.sh {}", BASE64_STANDARD.encode(bundle.to_source()?));
 
     if cmd.len() >= 96 * 1024 {
-        bail!("Your function is too large (must be smaller than 96kb base64)");
+        if bundle.is_single_file() {
+            bail!("Your function is too large (must be smaller than 96kb base64)");
+        }
+        bail!(
+            "Your function is too large: {} files compress to {}kb, and the limit is 96kb",
+            bundle.files.len(),
+            cmd.len() / 1024
+        );
     }
 
-    Ok(cmd)
+    Ok((cmd, bundle))
 }
 
 pub fn get_function_from_path(path: Option<PathBuf>) -> Result<(String, PathBuf)> {
@@ -108,9 +126,8 @@ pub fn get_function_from_path(path: Option<PathBuf>) -> Result<(String, PathBuf)
     Ok((id, path.clone()))
 }
 
-pub fn has_domains(function: &ProjectServiceInstanceEdge) -> bool {
-    !function.node.domains.custom_domains.is_empty()
-        || !function.node.domains.service_domains.is_empty()
+pub fn has_domains(function: &ProjectServiceInstanceNode) -> bool {
+    !function.domains.custom_domains.is_empty() || !function.domains.service_domains.is_empty()
 }
 
 pub fn find_service(
@@ -169,7 +186,43 @@ pub fn calculate_diff(old_content: &str, new_content: &str) -> DiffStats {
     }
 }
 
-pub fn extract_function_content(function: &ProjectServiceInstanceNode) -> Result<String> {
+/// Sums the line changes across every file in two bundles.
+pub fn calculate_bundle_diff(old: &Bundle, new: &Bundle) -> DiffStats {
+    let mut total = DiffStats {
+        insertions: 0,
+        deletions: 0,
+        changes: 0,
+    };
+    // Single-file bundles are keyed by their entry, which may be named
+    // differently locally and remotely
+    let key = |bundle: &Bundle, path: &String| {
+        if bundle.is_single_file() || *path == bundle.entry {
+            String::new()
+        } else {
+            path.clone()
+        }
+    };
+    let old_files: BTreeMap<String, &Vec<u8>> =
+        old.files.iter().map(|(p, c)| (key(old, p), c)).collect();
+    let new_files: BTreeMap<String, &Vec<u8>> =
+        new.files.iter().map(|(p, c)| (key(new, p), c)).collect();
+    let paths: BTreeSet<&String> = old_files.keys().chain(new_files.keys()).collect();
+    for path in paths {
+        let read = |files: &BTreeMap<String, &Vec<u8>>| {
+            files
+                .get(path)
+                .map(|c| String::from_utf8_lossy(c).into_owned())
+                .unwrap_or_default()
+        };
+        let stats = calculate_diff(&read(&old_files), &read(&new_files));
+        total.insertions += stats.insertions;
+        total.deletions += stats.deletions;
+        total.changes += stats.changes;
+    }
+    total
+}
+
+pub fn extract_function_content(function: &ProjectServiceInstanceNode) -> Result<Vec<u8>> {
     let cmd = function
         .start_command
         .as_ref()
@@ -179,6 +232,20 @@ pub fn extract_function_content(function: &ProjectServiceInstanceNode) -> Result
         anyhow::anyhow!("Function no longer uses the correct start command format")
     })?;
 
-    String::from_utf8(BASE64_STANDARD.decode(encoded)?)
+    BASE64_STANDARD
+        .decode(encoded)
         .map_err(|e| anyhow::anyhow!("Failed to decode function content: {}", e))
 }
+
+/// Decodes the deployed function into a bundle. Single-file functions are
+/// keyed by the local entry's file name.
+pub fn extract_function_bundle(
+    function: &ProjectServiceInstanceNode,
+    local_entry: &Path,
+) -> Result<Bundle> {
+    let entry_name = local_entry
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "index.ts".to_string());
+    Bundle::from_source(&extract_function_content(function)?, &entry_name)
+}
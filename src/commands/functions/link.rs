@@ -4,6 +4,8 @@ use crate::{
     util::prompt::{fake_select, prompt_path, prompt_select},
 };
 use anyhow::bail;
+
+use super::bundle::Bundle;
 use is_terminal::IsTerminal;
 
 use super::*;
@@ -48,8 +50,10 @@ pub async fn link(environment_instances: &ProjectEnvironmentInstances, args: Lin
         std::fs::write(&path, "")?;
     }
     link_function(&path, &function.service_id)?;
-    let local = common::get_start_cmd(&path)?;
-    let remote = function.start_command.unwrap_or_default();
+    let local = Bundle::collect(&path)?.hash();
+    let remote = common::extract_function_bundle(&function, &path)
+        .map(|bundle| bundle.hash())
+        .unwrap_or_default();
     println!(
         "Linked function {} to the local file {}",
         function.service_name.blue(),
@@ -93,7 +93,7 @@ fn append_runtime_info(entry: &mut String, function: &ProjectServiceInstanceEdge
     Ok(())
 }
 
-fn parse_runtime_from_image(image: &str) -> Option<(String, String)> {
+pub(super) fn parse_runtime_from_image(image: &str) -> Option<(String, String)> {
     let runtime_unparsed = image.split("function-").nth(1)?;
     let mut runtime_parts = runtime_unparsed.split(":");
     let runtime_name = runtime_parts.next()?.to_string();
@@ -110,7 +110,7 @@ fn append_next_cron_run(entry: &mut String, function: &ProjectServiceInstanceEdg
 }
 
 fn append_domain_info(entry: &mut String, function: &ProjectServiceInstanceEdge) -> Result<()> {
-    if common::has_domains(function) {
+    if common::has_domains(&function.node) {
         write!(entry, " ({})", "http".blue())?;
     }
     Ok(())
@@ -21,6 +21,7 @@ pub struct Args {
     environment: Option<String>,
 }
 
+mod bundle;
 mod common;
 mod delete;
 mod link;
@@ -28,6 +29,7 @@ mod list;
 mod new;
 mod pull;
 mod push;
+mod serve;
 
 structstruck::strike! {
     #[strikethrough[derive(Parser)]]
@@ -99,6 +101,24 @@ structstruck::strike! {
             path: Option<PathBuf>
         }),
 
+        /// Run a function locally with its service's variables, reloading on save
+        Serve(struct {
+            /// The path to the function (defaults to the function linked in this directory)
+            path: Option<PathBuf>,
+
+            /// Local port for functions with a domain
+            #[clap(long, default_value = "3000")]
+            port: u16,
+
+            /// Run a scheduled function once right away, then exit
+            #[clap(long)]
+            trigger_cron: bool,
+
+            /// Don't reload the function when its files change
+            #[clap(long)]
+            no_watch: bool,
+        }),
+
         /// Link a function manually
         Link(struct {
             /// The path to the file
@@ -148,6 +168,9 @@ pub async fn command(args: Args) -> Result<()> {
         Commands::Delete(args) => delete::delete(environment, &environment_instances, args).await,
         Commands::Link(link) => link::link(&environment_instances, link).await,
         Commands::Pull(pull) => pull::pull(&environment_instances, pull).await,
+        Commands::Serve(serve) => {
+            serve::serve(&project, environment, &environment_instances, serve).await
+        }
         Commands::Push(push) => {
             push::push(environment, &environment_instances, project.clone(), push).await
         }
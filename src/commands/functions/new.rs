@@ -296,7 +296,13 @@ pub async fn watch_for_file_changes(
             println!("{info}");
         }
     }
-    let watcher = FileWatcher::new(path.clone());
+    // Multi-file functions redeploy when any module or the manifest changes
+    let watcher = match (bundle::Bundle::collect(&path), path.parent()) {
+        (Ok(bundle), Some(root)) if !bundle.is_single_file() => {
+            FileWatcher::directory(root.to_path_buf(), bundle::is_bundle_input)
+        }
+        _ => FileWatcher::new(path.clone()),
+    };
     let environment_id = environment.node.id.clone();
     watcher
         .watch(move |token, event| {
@@ -335,9 +341,9 @@ async fn handle_function_change(
     let mut spinner = create_spinner("Updating function".into());
     let cmd = match common::get_start_cmd(&path) {
         Ok(cmd) => cmd,
-        Err(_) => {
-            println!("{}: Your function is too large", "ERROR".red());
-            return Err(anyhow::anyhow!("Function too big (max size of 96kb)"));
+        Err(e) => {
+            println!("{}: {e}", "ERROR".red());
+            return Err(e);
         }
     };
 
@@ -1,5 +1,7 @@
 use crate::controllers::project::ProjectEnvironmentInstances;
 
+use super::bundle::Bundle;
+
 use super::*;
 
 pub async fn pull(environment_instances: &ProjectEnvironmentInstances, args: Pull) -> Result<()> {
@@ -12,12 +14,28 @@ pub async fn pull(environment_instances: &ProjectEnvironmentInstances, args: Pul
         service.service_name.blue().bold()
     );
 
-    let decoded_content = common::extract_function_content(&service)?;
-    let current_content = std::fs::read_to_string(&path)?;
-    let diff_stats = common::calculate_diff(&current_content, &decoded_content);
+    let remote = common::extract_function_bundle(&service, &path)?;
+    // A local copy whose imports no longer resolve still gets overwritten
+    let local = Bundle::collect(&path).unwrap_or_else(|_| {
+        Bundle::single(
+            remote.entry.clone(),
+            std::fs::read(&path).unwrap_or_default(),
+        )
+    });
+
+    if local.hash() == remote.hash() {
+        println!("Function is already up to date");
+        return Ok(());
+    }
 
-    std::fs::write(&path, &decoded_content)?;
+    let diff_stats = common::calculate_bundle_diff(&local, &remote);
+    let written = remote.write_to(&path)?;
     println!("Function updated {diff_stats}");
+    if written.len() > 1 {
+        for file in written {
+            println!("  {}", file.display());
+        }
+    }
 
     Ok(())
 }
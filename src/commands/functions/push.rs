@@ -42,17 +42,22 @@ pub async fn push(
 
         let configs = Configs::new()?;
         let client = GQLClient::new_authorized(&configs)?;
-        let new_cmd = common::get_start_cmd(&path)?;
+        let (new_cmd, bundle) = common::get_start_cmd_with_bundle(&path)?;
+        let remote = common::extract_function_bundle(&service, &path)?;
 
-        let diff_stats = common::calculate_diff(
-            &common::extract_function_content(&service)?,
-            &String::from_utf8(std::fs::read(&path)?)?,
-        );
+        let diff_stats = common::calculate_bundle_diff(&remote, &bundle);
 
         update_function(&client, &configs, &environment.node.id, &id, new_cmd).await?;
         deploy_function(&client, &configs, &environment.node.id, &id).await?;
 
         println!("Function updated {diff_stats}");
+        if !bundle.is_single_file() {
+            println!(
+                "Bundled {} files (sha256 {})",
+                bundle.files.len(),
+                &bundle.hash()[..12]
+            );
+        }
     }
     Ok(())
 }
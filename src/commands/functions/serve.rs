@@ -0,0 +1,317 @@
+use std::{collections::BTreeMap, path::Path, process::ExitStatus, str::FromStr};
+
+use anyhow::bail;
+use chrono::Utc;
+use croner::Cron;
+use queries::project::{ProjectProject, ProjectProjectEnvironmentsEdges};
+use tokio_util::sync::CancellationToken;
+
+use super::*;
+use crate::{
+    controllers::{project::ProjectEnvironmentInstances, variables::get_service_variables},
+    util::{
+        host_env::{dropped_notice, strip_unsafe_host_vars},
+        watcher::FileWatcher,
+    },
+};
+
+/// Runtime used when the function's image doesn't name one.
+const DEFAULT_RUNTIME: &str = "bun";
+
+pub async fn serve(
+    project: &ProjectProject,
+    environment: &ProjectProjectEnvironmentsEdges,
+    environment_instances: &ProjectEnvironmentInstances,
+    args: Serve,
+) -> Result<()> {
+    let (id, path) = common::get_function_from_path(args.path)?;
+    let service = common::find_service(environment_instances, &id)
+        .ok_or_else(|| anyhow::anyhow!("Couldn't find service"))?;
+
+    let (runtime, version) = service
+        .source
+        .as_ref()
+        .and_then(|source| source.image.as_deref())
+        .and_then(list::parse_runtime_from_image)
+        .unwrap_or_else(|| (DEFAULT_RUNTIME.to_string(), String::new()));
+    let command = runtime_command(&runtime, &path)?;
+    check_local_runtime(&runtime, &version).await?;
+
+    let schedule = match &service.cron_schedule {
+        Some(cron) => Some(
+            Cron::from_str(cron)
+                .map_err(|e| anyhow::anyhow!("Invalid cron schedule '{cron}': {e}"))?,
+        ),
+        None => None,
+    };
+    if args.trigger_cron && schedule.is_none() {
+        bail!(
+            "{} is not a scheduled function; --trigger-cron only applies to functions with a cron schedule",
+            service.service_name
+        );
+    }
+
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let mut variables = get_service_variables(
+        &client,
+        &configs,
+        project.id.clone(),
+        environment.node.id.clone(),
+        service.service_id.clone(),
+    )
+    .await?;
+    let dropped = strip_unsafe_host_vars(&mut variables);
+    if let Some(notice) = dropped_notice(&dropped) {
+        eprintln!("{}", notice.yellow());
+    }
+
+    let http = common::has_domains(&service);
+    if http {
+        variables.insert("PORT".to_string(), args.port.to_string());
+    }
+
+    if args.trigger_cron {
+        println!(
+            "Running {} once as its cron would",
+            service.service_name.blue().bold()
+        );
+        let status = run_to_completion(&command, &variables, &path).await?;
+        if let Some(code) = status.code() {
+            std::process::exit(code);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "Serving function {} from {} with {} {}",
+        service.service_name.blue().bold(),
+        path.display().to_string().green(),
+        runtime,
+        if version.is_empty() {
+            String::new()
+        } else {
+            format!("(v{version} in production)")
+        }
+    );
+    if http {
+        println!(
+            "Listening on {}",
+            format!("http://localhost:{}", args.port).blue()
+        );
+    }
+    if let (Some(schedule), Some(cron)) = (&schedule, &service.cron_schedule) {
+        println!(
+            "Scheduled {} ({}). Use --trigger-cron to run it now.",
+            schedule.describe().blue(),
+            cron.blue()
+        );
+    }
+
+    let watcher = if args.no_watch {
+        None
+    } else {
+        Some(function_watcher(&path))
+    };
+
+    let Some(watcher) = watcher else {
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            let _ = tokio::signal::ctrl_c().await;
+            cancel.cancel();
+        });
+        return run(schedule, command, variables, path, token).await;
+    };
+
+    let reloads = std::sync::atomic::AtomicUsize::new(0);
+    // The watcher cancels the previous run's token before calling us again;
+    // wait for that run to kill its process so a fixed PORT is free before
+    // the replacement starts.
+    let running: std::sync::Arc<tokio::sync::Mutex<Option<tokio::task::JoinHandle<()>>>> =
+        Default::default();
+    watcher
+        .trigger_on_start()
+        .watch(move |token, _| {
+            if reloads.fetch_add(1, std::sync::atomic::Ordering::Relaxed) > 0 {
+                println!("{}", "Change detected, reloading function".yellow());
+            }
+            let (schedule, command, variables, path, running) = (
+                schedule.clone(),
+                command.clone(),
+                variables.clone(),
+                path.clone(),
+                running.clone(),
+            );
+            async move {
+                let mut running = running.lock().await;
+                if let Some(previous) = running.take() {
+                    let _ = previous.await;
+                }
+                *running = Some(tokio::spawn(async move {
+                    if let Err(e) = run(schedule, command, variables, path, token).await {
+                        eprintln!("{}: {e}", "ERROR".red());
+                    }
+                }));
+                Ok(())
+            }
+        })
+        .await
+}
+
+/// Runs the function until `token` is cancelled: once per cron tick for
+/// scheduled functions, otherwise as one long-running process.
+async fn run(
+    schedule: Option<Cron>,
+    command: Vec<String>,
+    variables: BTreeMap<String, String>,
+    path: PathBuf,
+    token: CancellationToken,
+) -> Result<()> {
+    match schedule {
+        Some(schedule) => loop {
+            let next = schedule.find_next_occurrence(&Utc::now(), false)?;
+            println!(
+                "Next run at {}",
+                next.format("%Y-%m-%d %H:%M:%S UTC").to_string().dimmed()
+            );
+            let wait = (next - Utc::now()).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = token.cancelled() => return Ok(()),
+            }
+            tokio::select! {
+                status = run_to_completion(&command, &variables, &path) => { status?; }
+                _ = token.cancelled() => return Ok(()),
+            }
+        },
+        None => {
+            let mut child = spawn(&command, &variables, &path)?;
+            tokio::select! {
+                status = child.wait() => {
+                    let status = status?;
+                    println!("{}", format!("Function exited ({status}); waiting for changes").dimmed());
+                }
+                _ = token.cancelled() => {
+                    child.kill().await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn run_to_completion(
+    command: &[String],
+    variables: &BTreeMap<String, String>,
+    path: &Path,
+) -> Result<ExitStatus> {
+    println!(
+        "{}",
+        format!("Running at {}", Utc::now().format("%H:%M:%S")).dimmed()
+    );
+    let status = spawn(command, variables, path)?.wait().await?;
+    if status.success() {
+        println!("{}", "Run finished".green());
+    } else {
+        println!("{}", format!("Run failed ({status})").red());
+    }
+    Ok(status)
+}
+
+fn spawn(
+    command: &[String],
+    variables: &BTreeMap<String, String>,
+    path: &Path,
+) -> Result<tokio::process::Child> {
+    let mut process = tokio::process::Command::new(&command[0]);
+    process
+        .args(&command[1..])
+        .envs(variables)
+        .kill_on_drop(true);
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        process.current_dir(dir);
+    }
+    process
+        .spawn()
+        .with_context(|| format!("Failed to start {}", command[0]))
+}
+
+/// Watches the function's entry, or its whole directory when it imports
+/// other local modules.
+fn function_watcher(path: &Path) -> FileWatcher {
+    match (bundle::Bundle::collect(path), path.parent()) {
+        (Ok(bundle), Some(root)) if !bundle.is_single_file() => {
+            FileWatcher::directory(root.to_path_buf(), bundle::is_bundle_input)
+        }
+        _ => FileWatcher::new(path.to_path_buf()),
+    }
+}
+
+/// The local command for a function runtime, run from the function's
+/// directory.
+fn runtime_command(runtime: &str, path: &Path) -> Result<Vec<String>> {
+    let file = path
+        .file_name()
+        .context("The function path has no file name")?
+        .to_string_lossy()
+        .into_owned();
+    Ok(match runtime {
+        "bun" => vec!["bun".into(), "run".into(), file],
+        "node" => vec!["node".into(), file],
+        "deno" => vec!["deno".into(), "run".into(), "-A".into(), file],
+        other => bail!("Running {other} functions locally isn't supported"),
+    })
+}
+
+/// Makes sure the runtime is installed and warns when its version differs
+/// from production's.
+async fn check_local_runtime(runtime: &str, version: &str) -> Result<()> {
+    let output = tokio::process::Command::new(runtime)
+        .arg("--version")
+        .output()
+        .await;
+    let Ok(output) = output else {
+        bail!("{runtime} is not installed. Install it to run this function locally.");
+    };
+    let local = String::from_utf8_lossy(&output.stdout);
+    let local = local
+        .split_whitespace()
+        .find_map(|word| {
+            let word = word.trim_start_matches('v');
+            word.starts_with(|c: char| c.is_ascii_digit())
+                .then_some(word)
+        })
+        .unwrap_or_default();
+    if !version.is_empty() && !same_minor_version(local, version) {
+        eprintln!(
+            "{}",
+            format!(
+                "Warning: local {runtime} is v{local}, but the function runs on v{version} in production"
+            )
+            .yellow()
+        );
+    }
+    Ok(())
+}
+
+fn same_minor_version(a: &str, b: &str) -> bool {
+    let minor = |v: &str| v.split('.').take(2).collect::<Vec<_>>().join(".");
+    minor(a) == minor(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_commands_and_versions() {
+        assert_eq!(
+            runtime_command("bun", Path::new("fns/hello.ts")).unwrap(),
+            vec!["bun", "run", "hello.ts"]
+        );
+        assert!(runtime_command("python", Path::new("main.py")).is_err());
+        assert!(same_minor_version("1.2.10", "1.2.3"));
+        assert!(!same_minor_version("1.1.0", "1.2"));
+    }
+}
@@ -0,0 +1,481 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use is_terminal::IsTerminal;
+use serde::Serialize;
+
+use crate::{
+    controllers::{
+        config::EnvironmentConfig,
+        develop::compose::ImportedComposeFile,
+        project::{EnvironmentContext, resolve_environment_context},
+        template_apply::staged_patch_is_nonempty,
+    },
+    util::{
+        progress::{create_spinner_if, fail_spinner, success_spinner},
+        prompt::prompt_confirm_with_default,
+    },
+};
+
+use super::*;
+
+/// Import an existing stack into a Railway environment
+#[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway import compose\n  railway import compose ./docker-compose.yml --dry-run\n  railway import compose ./stack/compose.yaml --environment staging --yes --json\n\nAutomation notes:\n  The preview shows the changes Railway converted the file into, which are exactly what gets committed.\n  The whole file is staged and committed as one environment patch, so it can be reviewed and reverted as a unit.\n  The environment must not have other staged changes; commit or discard them first."
+)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(Parser)]
+enum Commands {
+    /// Create services, volumes and variables from a docker-compose file
+    Compose(ComposeArgs),
+}
+
+#[derive(Parser)]
+struct ComposeArgs {
+    /// Path to the compose file
+    #[clap(default_value = "docker-compose.yml")]
+    file: PathBuf,
+
+    /// Project ID or name to import into (defaults to linked project)
+    #[clap(short, long)]
+    project: Option<String>,
+
+    /// Environment to import into (defaults to linked environment)
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Message for the environment patch
+    #[clap(short, long)]
+    message: Option<String>,
+
+    /// Show what Railway would create without changing anything
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Skip confirmation dialog
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+/// What a compose file will become on Railway.
+#[derive(Debug, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ImportPreview {
+    services: Vec<ServicePreview>,
+    volumes: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ServicePreview {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_command: Option<String>,
+    ports: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume: Option<VolumeMount>,
+    variables: Vec<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct VolumeMount {
+    name: String,
+    mount_path: String,
+}
+
+pub async fn command(args: Args) -> Result<()> {
+    match args.command {
+        Commands::Compose(args) => import_compose(args).await,
+    }
+}
+
+async fn import_compose(args: ComposeArgs) -> Result<()> {
+    let yaml = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let compose = ImportedComposeFile::from_yaml(&yaml)
+        .with_context(|| format!("Failed to parse {}", args.file.display()))?;
+    if compose.services.is_empty() {
+        bail!("{} doesn't define any services", args.file.display());
+    }
+    let file_name = args
+        .file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| args.file.display().to_string());
+
+    let ctx = resolve_environment_context(args.project, args.environment).await?;
+    // The server converts the file; preview its result rather than our own
+    // reading of the compose file, since that result is what gets committed.
+    let patch = convert_compose(&ctx, &yaml, &file_name).await?;
+    let preview = build_preview(&patch, &compose);
+
+    if args.dry_run {
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&preview)?);
+        } else {
+            print_preview(&preview, &file_name, &ctx, true);
+        }
+        return Ok(());
+    }
+
+    if !args.json {
+        print_preview(&preview, &file_name, &ctx, false);
+    }
+
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default(
+            &format!(
+                "Create {} in {}?",
+                pluralize(preview.services.len(), "service"),
+                ctx.environment_name
+            ),
+            false,
+        )?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    ensure_nothing_staged(&ctx).await?;
+
+    let mut spinner = create_spinner_if(!args.json, format!("Importing {file_name}..."));
+    let result = stage_and_commit(&ctx, patch, &file_name, args.message).await;
+    match (&result, spinner.as_mut()) {
+        (Ok(_), Some(spinner)) => success_spinner(
+            spinner,
+            format!(
+                "Imported {} into {}",
+                pluralize(preview.services.len(), "service"),
+                ctx.environment_name
+            ),
+        ),
+        (Err(_), Some(spinner)) => fail_spinner(spinner, format!("Failed to import {file_name}")),
+        _ => {}
+    }
+    result?;
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "projectId": ctx.project_id,
+                "environmentId": ctx.environment_id,
+                "services": preview.services,
+                "volumes": preview.volumes,
+                "warnings": preview.warnings,
+            }))?
+        );
+    }
+    Ok(())
+}
+
+/// The import must be the only thing in the committed patch.
+async fn ensure_nothing_staged(ctx: &EnvironmentContext) -> Result<()> {
+    let staged = post_graphql::<queries::EnvironmentStagedChanges, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        queries::environment_staged_changes::Variables {
+            environment_id: ctx.environment_id.clone(),
+        },
+    )
+    .await?
+    .environment_staged_changes;
+    if staged_patch_is_nonempty(&staged.patch) {
+        bail!(
+            "Environment {} already has staged changes. Commit or discard them before importing, so the import lands as its own patch.",
+            ctx.environment_name
+        );
+    }
+    Ok(())
+}
+
+/// The environment patch Railway converts the compose file into, without
+/// staging it.
+async fn convert_compose(
+    ctx: &EnvironmentContext,
+    yaml: &str,
+    file_name: &str,
+) -> Result<EnvironmentConfig> {
+    let import = post_graphql::<mutations::DockerComposeImport, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        mutations::docker_compose_import::Variables {
+            project_id: ctx.project_id.clone(),
+            environment_id: ctx.environment_id.clone(),
+            yaml: yaml.to_string(),
+            skip_staging_patch: Some(true),
+        },
+    )
+    .await?
+    .docker_compose_import;
+    if !import.errors.is_empty() {
+        bail!(
+            "Railway couldn't import {file_name}:\n  {}",
+            import.errors.join("\n  ")
+        );
+    }
+    import
+        .patch
+        .context("Railway returned no changes for the compose file")
+}
+
+async fn stage_and_commit(
+    ctx: &EnvironmentContext,
+    patch: EnvironmentConfig,
+    file_name: &str,
+    message: Option<String>,
+) -> Result<()> {
+    post_graphql::<mutations::EnvironmentStageChanges, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        mutations::environment_stage_changes::Variables {
+            environment_id: ctx.environment_id.clone(),
+            input: patch,
+            merge: Some(true),
+        },
+    )
+    .await
+    .context("Failed to stage changes")?;
+
+    post_graphql::<mutations::EnvironmentPatchCommitStaged, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        mutations::environment_patch_commit_staged::Variables {
+            environment_id: ctx.environment_id.clone(),
+            commit_message: Some(message.unwrap_or_else(|| format!("Import {file_name}"))),
+            skip_deploys: None,
+        },
+    )
+    .await
+    .context("Failed to commit staged changes")?;
+    Ok(())
+}
+
+/// What `patch` creates, plus warnings about parts of `compose` it leaves
+/// out. Services and volumes are keyed as in the patch.
+fn build_preview(patch: &EnvironmentConfig, compose: &ImportedComposeFile) -> ImportPreview {
+    let mut preview = ImportPreview {
+        volumes: patch.volumes.keys().cloned().collect(),
+        ..Default::default()
+    };
+
+    for (name, service) in &patch.services {
+        let image = service.source.as_ref().and_then(|s| s.image.clone());
+        if image.is_none() {
+            preview.warnings.push(format!(
+                "{name}: no image; the service is created empty, connect a repo or image afterwards"
+            ));
+        }
+        let mounts: BTreeMap<&str, &str> = service
+            .volume_mounts
+            .iter()
+            .filter_map(|(volume, mount)| Some((mount.mount_path.as_deref()?, volume.as_str())))
+            .collect();
+
+        if let Some(declared) = compose.services.get(name) {
+            for volume in &declared.volumes {
+                if !mounts.contains_key(mount_path(volume)) {
+                    preview.warnings.push(format!(
+                        "{name}: volume {volume} is not imported; copy the files into the image or a volume"
+                    ));
+                }
+            }
+
+            let interpolated = declared
+                .environment
+                .iter()
+                .filter(|(_, value)| value.contains("${") && !value.contains("${{"))
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>();
+            if !interpolated.is_empty() {
+                preview.warnings.push(format!(
+                    "{name}: {} use{} compose ${{VAR}} interpolation, which is imported literally",
+                    interpolated.join(", "),
+                    if interpolated.len() == 1 { "s" } else { "" }
+                ));
+            }
+        }
+
+        let mut ports = service
+            .get_ports()
+            .iter()
+            .map(|port| port.to_string())
+            .collect::<Vec<_>>();
+        ports.sort();
+        preview.services.push(ServicePreview {
+            name: name.clone(),
+            image,
+            start_command: service
+                .deploy
+                .as_ref()
+                .and_then(|d| d.start_command.clone()),
+            ports,
+            volume: mounts.iter().next().map(|(path, volume)| VolumeMount {
+                name: volume.to_string(),
+                mount_path: path.to_string(),
+            }),
+            variables: service.variables.keys().cloned().collect(),
+        });
+    }
+
+    preview
+}
+
+/// The container path of a short-syntax volume (`[source:]target[:mode]`).
+fn mount_path(volume: &str) -> &str {
+    let mut parts = volume.splitn(3, ':');
+    let first = parts.next().unwrap_or_default();
+    parts.next().unwrap_or(first)
+}
+
+fn print_preview(
+    preview: &ImportPreview,
+    file_name: &str,
+    ctx: &EnvironmentContext,
+    dry_run: bool,
+) {
+    println!(
+        "{} {} into {} ({})\n",
+        if dry_run { "Would import" } else { "Importing" },
+        file_name.bold(),
+        ctx.project.name.bold(),
+        ctx.environment_name.bold()
+    );
+
+    for service in &preview.services {
+        println!(
+            "  {} service {}  {}",
+            "+".green(),
+            service.name.bold(),
+            service.image.as_deref().unwrap_or("(no image)").dimmed()
+        );
+        if let Some(command) = &service.start_command {
+            println!("      start     {command}");
+        }
+        if !service.ports.is_empty() {
+            println!("      ports     {}", service.ports.join(", "));
+        }
+        if let Some(volume) = &service.volume {
+            println!("      volume    {} → {}", volume.name, volume.mount_path);
+        }
+        if !service.variables.is_empty() {
+            println!("      variables {}", service.variables.join(", "));
+        }
+    }
+    for volume in &preview.volumes {
+        println!("  {} volume  {}", "+".green(), volume.bold());
+    }
+    if !preview.warnings.is_empty() {
+        println!("\n{}", "Warnings:".yellow());
+        for warning in &preview.warnings {
+            println!("  {warning}");
+        }
+    }
+    println!();
+}
+
+fn pluralize(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews_the_converted_patch_and_what_it_leaves_out() {
+        let compose = ImportedComposeFile::from_yaml(
+            r#"
+services:
+  api:
+    build: .
+    command: npm start
+    ports: ["127.0.0.1:3000:3000/tcp", "3000"]
+    environment:
+      DATABASE_URL: postgres://postgres:${DB_PASSWORD}@db:5432/app
+      NODE_ENV: production
+    volumes:
+      - ./src:/app/src
+  db:
+    image: postgres:16
+    volumes:
+      - pgdata:/var/lib/postgresql/data
+      - /backups
+volumes:
+  pgdata:
+"#,
+        )
+        .unwrap();
+        // What Railway converted it into: the server, not the file, decides
+        // which mounts survive.
+        let patch: EnvironmentConfig = serde_json::from_value(serde_json::json!({
+            "services": {
+                "api": {
+                    "isCreated": true,
+                    "deploy": { "startCommand": "npm start" },
+                    "networking": { "serviceDomains": { "api": { "port": 3000 } } },
+                    "variables": {
+                        "DATABASE_URL": { "value": "postgres://postgres:${DB_PASSWORD}@db:5432/app" },
+                        "NODE_ENV": { "value": "production" },
+                    },
+                },
+                "db": {
+                    "isCreated": true,
+                    "source": { "image": "postgres:16" },
+                    "volumeMounts": { "pgdata": { "mountPath": "/var/lib/postgresql/data" } },
+                },
+            },
+            "volumes": { "pgdata": { "isCreated": true } },
+        }))
+        .unwrap();
+
+        let preview = build_preview(&patch, &compose);
+        assert_eq!(preview.services.len(), 2);
+
+        let api = &preview.services[0];
+        assert_eq!(api.name, "api");
+        assert_eq!(api.image, None);
+        assert_eq!(api.start_command.as_deref(), Some("npm start"));
+        assert_eq!(api.ports, vec!["3000"]);
+        assert_eq!(api.volume, None);
+        assert_eq!(api.variables, vec!["DATABASE_URL", "NODE_ENV"]);
+
+        let db = &preview.services[1];
+        assert_eq!(
+            db.volume,
+            Some(VolumeMount {
+                name: "pgdata".to_string(),
+                mount_path: "/var/lib/postgresql/data".to_string(),
+            })
+        );
+        assert_eq!(preview.volumes, vec!["pgdata"]);
+
+        assert_eq!(preview.warnings.len(), 4, "{:?}", preview.warnings);
+        assert!(preview.warnings[0].starts_with("api: no image"));
+        assert!(preview.warnings[1].contains("volume ./src:/app/src is not imported"));
+        assert!(preview.warnings[2].contains("DATABASE_URL uses compose ${VAR}"));
+        assert!(preview.warnings[3].contains("volume /backups is not imported"));
+    }
+}
@@ -181,6 +181,15 @@ pub async fn command(args: Args) -> Result<()> {
         println!("  {} Signed in as {}", "✓".green(), me.email);
     }
 
+    let profile = Configs::active_profile();
+    if profile != DEFAULT_PROFILE {
+        println!(
+            "  {} Saved to profile {}",
+            "✓".green(),
+            profile.cyan().bold()
+        );
+    }
+
     Ok(())
 }
 
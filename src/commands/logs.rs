@@ -11,11 +11,20 @@ use crate::{
             stream_build_logs, stream_deploy_logs, stream_dns_query_logs, stream_http_logs,
             stream_network_flow_logs,
         },
-        project::resolve_service_context,
+        log_export::{
+            EXPORT_STATE_FILE, ExportFetcher, ExportFormat, ExportLogType, ExportSinks,
+            ExportSource, ExportState, FileSink, OtlpSink, export_source,
+        },
+        project::{
+            EnvironmentContext, get_environment_instances, get_service_ids_in_env,
+            resolve_environment_context, resolve_service_context,
+        },
     },
+    errors::RailwayError,
     util::{
         logs::{
-            LogFormat, format_dns_query_log_header, format_network_flow_log_header,
+            LogFormat, ServiceLogLabel, TimestampMerger, format_dns_query_log_header,
+            format_network_flow_log_header, format_service_log_string, parse_log_timestamp,
             print_dns_query_log, print_http_log, print_log, print_network_flow_log,
         },
         time::parse_time,
@@ -23,8 +32,13 @@ use crate::{
 };
 use anyhow::{Context, bail};
 
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
+
 use super::{
-    queries::deployments::{DeploymentListInput, DeploymentStatus},
+    queries::deployments::{
+        DeploymentListInput, DeploymentStatus, DeploymentsDeploymentsEdgesNode,
+    },
     *,
 };
 
@@ -448,6 +462,11 @@ pub fn compose_http_filter(
   railway logs --json                                                # Get logs in JSON format
   railway logs --latest                                              # Stream logs from the latest deployment (even if failed/building)
 
+  Multiple services:
+  railway logs --service api --service worker                        # Stream both services' deploy logs, interleaved by time
+  railway logs --all --since 15m                                     # Last 15 minutes from every service in the environment
+  railway logs --all --json                                          # Each JSON line carries a \"service\" field
+
   HTTP logs (typed filters):
   railway logs --http --method GET --status 200                      # GET requests with 200 status
   railway logs --http --method POST --path /api/users                # POST requests to /api/users
@@ -474,12 +493,24 @@ pub fn compose_http_filter(
   railway logs --dns --rcode NXDOMAIN                                 # Names that don't exist
   railway logs --dns --zone internal                                  # Private network lookups
   railway logs --dns --domain example.com --qtype AAAA                # IPv6 lookups for a domain
-  railway logs --dns --qname backend.railway.internal --lines 50      # History for an exact name"
+  railway logs --dns --qname backend.railway.internal --lines 50      # History for an exact name
+
+  Export:
+  railway logs export --since 7d --out ./logs/                        # Write a week of deploy logs to rotated files
+  railway logs export --all --since 1d --type deploy,http --format gzip
+  railway logs export --since 1h --otlp-endpoint http://localhost:4318/v1/logs --no-files"
 )]
 pub struct Args {
-    /// Service to view logs from (defaults to linked service). Can be service name or service ID
+    #[clap(subcommand)]
+    command: Option<LogsCommand>,
+
+    /// Service to view logs from (defaults to linked service). Can be service name or service ID. Repeat to merge deploy logs from several services
     #[clap(short, long)]
-    service: Option<String>,
+    service: Vec<String>,
+
+    /// Merge deploy logs from every service in the environment
+    #[clap(long, conflicts_with = "service")]
+    all: bool,
 
     /// Environment to view logs from (defaults to linked environment). Can be environment name or environment ID
     #[clap(short, long)]
@@ -656,7 +687,99 @@ Examples:
     until: Option<String>,
 }
 
+#[derive(Parser)]
+enum LogsCommand {
+    /// Export a time window of logs to NDJSON files and/or an OTLP/HTTP endpoint
+    #[clap(after_help = "Examples:
+
+  railway logs export --since 7d --out ./logs/
+  railway logs export --service api --service worker --since 2024-01-15T00:00:00Z --until 2024-01-16T00:00:00Z
+  railway logs export --all --since 1d --type deploy,build,http --format gzip --max-file-size 50
+  railway logs export --since 6h --otlp-endpoint https://otel.example.com/v1/logs --otlp-header \"Authorization=Bearer $TOKEN\"
+
+Files are named <service>-<type>.<n>.ndjson and rotate once they reach --max-file-size.
+Progress is saved in the output directory; rerunning the same command after an
+interruption picks up where it stopped. Use --restart to export the window again.")]
+    Export(ExportArgs),
+}
+
+#[derive(Parser)]
+struct ExportArgs {
+    /// Service to export logs from (defaults to linked service). Repeat for several services
+    #[clap(short, long)]
+    service: Vec<String>,
+
+    /// Export logs from every service in the environment
+    #[clap(long, conflicts_with = "service")]
+    all: bool,
+
+    /// Environment to export logs from (defaults to linked environment)
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Project ID to use (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT_ID")]
+    project: Option<String>,
+
+    /// Start of the window. Accepts relative times (e.g., 2h, 1d, 1w) or ISO 8601 timestamps
+    #[clap(long, short = 'S', value_name = "TIME")]
+    since: String,
+
+    /// End of the window (defaults to now). Same formats as --since
+    #[clap(long, short = 'U', value_name = "TIME")]
+    until: Option<String>,
+
+    /// Log types to export, comma separated
+    #[clap(
+        long = "type",
+        value_enum,
+        value_delimiter = ',',
+        default_value = "deploy"
+    )]
+    types: Vec<ExportLogType>,
+
+    /// Filter logs using Railway's query syntax (see `railway logs --help`)
+    #[clap(long, short = 'f')]
+    filter: Option<String>,
+
+    /// Directory for exported files and the resume checkpoint
+    #[clap(long, short = 'o', default_value = "railway-logs")]
+    out: std::path::PathBuf,
+
+    /// File format
+    #[clap(long, value_enum, default_value = "ndjson")]
+    format: ExportFormat,
+
+    /// Start a new file once the current one reaches this many megabytes
+    #[clap(long, value_name = "MB", default_value_t = 100)]
+    max_file_size: u64,
+
+    /// Also send logs to this OTLP/HTTP logs endpoint (e.g. http://localhost:4318/v1/logs)
+    #[clap(long, value_name = "URL")]
+    otlp_endpoint: Option<String>,
+
+    /// Header to send with OTLP requests, as KEY=VALUE. Can be repeated
+    #[clap(long, value_name = "KEY=VALUE", requires = "otlp_endpoint")]
+    otlp_header: Vec<String>,
+
+    /// Only send to the OTLP endpoint; don't write NDJSON files
+    #[clap(long, requires = "otlp_endpoint")]
+    no_files: bool,
+
+    /// Ignore saved progress and export the whole window again
+    #[clap(long)]
+    restart: bool,
+
+    /// Print a JSON summary when done
+    #[clap(long)]
+    json: bool,
+}
+
 pub async fn command(args: Args) -> Result<()> {
+    if let Some(LogsCommand::Export(export_args)) = args.command {
+        return export_command(export_args).await;
+    }
+
     validate_filter_modes(&args)?;
 
     let configs = Configs::new()?;
@@ -694,7 +817,16 @@ pub async fn command(args: Args) -> Result<()> {
     // Stream only if no line limit or time filter is specified and running in a terminal
     let should_stream = args.lines.is_none() && !has_time_filter && std::io::stdout().is_terminal();
 
-    let ctx = resolve_service_context(args.project, args.service, args.environment).await?;
+    if args.all || args.service.len() > 1 {
+        return multi_service_command(args, start_date, end_date, should_stream).await;
+    }
+
+    let ctx = resolve_service_context(
+        args.project,
+        args.service.into_iter().next(),
+        args.environment,
+    )
+    .await?;
     let project_id = ctx.project_id;
     let environment_id = ctx.environment_id;
     let service = ctx.service_id;
@@ -789,34 +921,10 @@ pub async fn command(args: Args) -> Result<()> {
 
     // Fetch all deployments so we can find a sensible default deployment id if
     // none is provided
-    let vars = queries::deployments::Variables {
-        input: DeploymentListInput {
-            project_id: Some(project_id.clone()),
-            environment_id: Some(environment_id),
-            service_id: Some(service),
-            include_deleted: None,
-            status: None,
-        },
-        first: None,
-    };
-    let deployments = post_graphql::<queries::Deployments, _>(&client, &backboard, vars)
-        .await?
-        .deployments;
-    let mut all_deployments: Vec<_> = deployments
-        .edges
-        .into_iter()
-        .map(|deployment| deployment.node)
-        .collect();
-    all_deployments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-    let default_deployment = if args.latest {
-        all_deployments.first()
-    } else {
-        all_deployments
-            .iter()
-            .find(|d| d.status == DeploymentStatus::SUCCESS)
-            .or_else(|| all_deployments.first())
-    }
-    .context("No deployments found")?;
+    let all_deployments =
+        service_deployments(&client, &backboard, &project_id, &environment_id, &service).await?;
+    let default_deployment =
+        default_deployment(&all_deployments, args.latest).context("No deployments found")?;
 
     let deployment_id = if let Some(deployment_id) = args.deployment_id {
         // Use the provided deployment ID directly
@@ -898,13 +1006,526 @@ pub async fn command(args: Args) -> Result<()> {
     Ok(())
 }
 
+/// A service's deployments in an environment, newest first.
+async fn service_deployments(
+    client: &reqwest::Client,
+    backboard: &str,
+    project_id: &str,
+    environment_id: &str,
+    service_id: &str,
+) -> Result<Vec<DeploymentsDeploymentsEdgesNode>> {
+    let vars = queries::deployments::Variables {
+        input: DeploymentListInput {
+            project_id: Some(project_id.to_string()),
+            environment_id: Some(environment_id.to_string()),
+            service_id: Some(service_id.to_string()),
+            include_deleted: None,
+            status: None,
+        },
+        first: None,
+    };
+    let deployments = post_graphql::<queries::Deployments, _>(client, backboard, vars)
+        .await?
+        .deployments;
+    let mut all_deployments: Vec<_> = deployments
+        .edges
+        .into_iter()
+        .map(|deployment| deployment.node)
+        .collect();
+    all_deployments.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+    Ok(all_deployments)
+}
+
+/// The deployment `railway logs` shows when none is given: the latest
+/// successful one, or the latest of any status with `--latest` or when none
+/// has succeeded.
+fn default_deployment(
+    deployments: &[DeploymentsDeploymentsEdgesNode],
+    latest: bool,
+) -> Option<&DeploymentsDeploymentsEdgesNode> {
+    if latest {
+        deployments.first()
+    } else {
+        deployments
+            .iter()
+            .find(|d| d.status == DeploymentStatus::SUCCESS)
+            .or_else(|| deployments.first())
+    }
+}
+
+/// How long merged streams hold a line back so slower services can catch up
+/// before it is printed.
+const MERGED_STREAM_DELAY: Duration = Duration::from_millis(750);
+
+struct ServiceLogTarget {
+    label: ServiceLogLabel,
+    deployment_id: String,
+}
+
+fn validate_multi_service_args(args: &Args) -> Result<()> {
+    if args.http || args.network || args.dns || args.build {
+        bail!(
+            "--all and multiple --service flags only support deploy logs; drop --http, --network, --dns, or --build"
+        );
+    }
+    if args.deployment_id.is_some() {
+        bail!("A deployment ID cannot be combined with --all or multiple --service flags");
+    }
+    Ok(())
+}
+
+/// Services named by `--service` (in the order given, without duplicates),
+/// or every service in the environment for `--all`, as `(id, name)` pairs.
+async fn select_services(
+    ctx: &EnvironmentContext,
+    wanted: &[String],
+    all: bool,
+) -> Result<Vec<(String, String)>> {
+    let services: Vec<(String, String)> = if all {
+        let instances = get_environment_instances(
+            &ctx.client,
+            &ctx.configs,
+            &ctx.project_id,
+            &ctx.environment_id,
+        )
+        .await?;
+        let in_env = get_service_ids_in_env(&instances);
+        ctx.project
+            .services
+            .edges
+            .iter()
+            .filter(|s| in_env.contains(&s.node.id))
+            .map(|s| (s.node.id.clone(), s.node.name.clone()))
+            .collect()
+    } else {
+        let mut selected: Vec<(String, String)> = Vec::new();
+        for wanted in wanted {
+            let service = ctx
+                .project
+                .services
+                .edges
+                .iter()
+                .find(|s| s.node.name.eq_ignore_ascii_case(wanted) || s.node.id == *wanted)
+                .ok_or_else(|| RailwayError::ServiceNotFound(wanted.clone()))?;
+            if !selected.iter().any(|(id, _)| *id == service.node.id) {
+                selected.push((service.node.id.clone(), service.node.name.clone()));
+            }
+        }
+        selected
+    };
+
+    if services.is_empty() {
+        bail!("No services found in environment {}", ctx.environment_name);
+    }
+    Ok(services)
+}
+
+/// `railway logs --service a --service b` / `--all`: deploy logs from several
+/// services, interleaved by timestamp and prefixed per service.
+async fn multi_service_command(
+    args: Args,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    should_stream: bool,
+) -> Result<()> {
+    validate_multi_service_args(&args)?;
+
+    let ctx = resolve_environment_context(args.project.clone(), args.environment.clone()).await?;
+    let backboard = ctx.configs.get_backboard();
+
+    let services = select_services(&ctx, &args.service, args.all).await?;
+
+    let width = services
+        .iter()
+        .map(|(_, name)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    let lookups = services.iter().map(|(id, name)| {
+        let client = &ctx.client;
+        let backboard = &backboard;
+        let project_id = &ctx.project_id;
+        let environment_id = &ctx.environment_id;
+        async move {
+            let deployments =
+                service_deployments(client, backboard, project_id, environment_id, id).await?;
+            Ok::<_, anyhow::Error>(default_deployment(&deployments, args.latest).map(|d| {
+                ServiceLogTarget {
+                    label: ServiceLogLabel::new(name.clone(), width),
+                    deployment_id: d.id.clone(),
+                }
+            }))
+        }
+    });
+    let resolved = futures::future::try_join_all(lookups).await?;
+
+    let mut targets = Vec::new();
+    for ((_, name), target) in services.iter().zip(resolved) {
+        match target {
+            Some(target) => targets.push(target),
+            // Explicitly named services are expected to have logs; for --all,
+            // services that were never deployed are just skipped.
+            None if !args.all => bail!("No deployments found for service {name}"),
+            None => {}
+        }
+    }
+    if targets.is_empty() {
+        bail!("No deployments found");
+    }
+
+    if should_stream {
+        stream_merged_deploy_logs(targets, args.filter, args.json).await
+    } else {
+        fetch_merged_deploy_logs(
+            &ctx.client,
+            &backboard,
+            targets,
+            args.filter,
+            args.lines,
+            start_date,
+            end_date,
+            args.json,
+        )
+        .await
+    }
+}
+
+async fn stream_merged_deploy_logs(
+    targets: Vec<ServiceLogTarget>,
+    filter: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(String, String)>();
+
+    for target in targets {
+        let tx = tx.clone();
+        let filter = filter.clone();
+        tokio::spawn(async move {
+            let label = target.label;
+            let result = stream_deploy_logs(target.deployment_id, filter, |log| {
+                let line = format_service_log_string(&log, &label, json);
+                let _ = tx.send((log.timestamp, line));
+            })
+            .await;
+            if let Err(e) = result {
+                eprintln!(
+                    "{}",
+                    format!("Log stream for {} ended: {e}", label.name).yellow()
+                );
+            }
+        });
+    }
+    // Only the per-service tasks hold senders now, so the channel closes once
+    // every stream has ended.
+    drop(tx);
+
+    let mut merger = TimestampMerger::new(MERGED_STREAM_DELAY);
+    let mut tick = tokio::time::interval(Duration::from_millis(100));
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Some((timestamp, line)) => merger.push(&timestamp, line, Instant::now()),
+                None => break,
+            },
+            _ = tick.tick() => {
+                for line in merger.drain_ready(Instant::now()) {
+                    println!("{line}");
+                }
+            }
+        }
+    }
+    for line in merger.drain_all() {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn fetch_merged_deploy_logs(
+    client: &reqwest::Client,
+    backboard: &str,
+    targets: Vec<ServiceLogTarget>,
+    filter: Option<String>,
+    lines: Option<i64>,
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    json: bool,
+) -> Result<()> {
+    let fetches = targets.iter().map(|target| {
+        let filter = filter.clone();
+        async move {
+            let mut collected = Vec::new();
+            fetch_deploy_logs(
+                FetchLogsParams {
+                    client,
+                    backboard,
+                    deployment_id: target.deployment_id.clone(),
+                    limit: lines.or(Some(500)),
+                    filter,
+                    start_date,
+                    end_date,
+                },
+                |log| {
+                    collected.push((
+                        parse_log_timestamp(&log.timestamp),
+                        format_service_log_string(&log, &target.label, json),
+                    ))
+                },
+            )
+            .await?;
+            Ok::<_, anyhow::Error>(collected)
+        }
+    });
+
+    let mut merged: Vec<_> = futures::future::try_join_all(fetches)
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+    // Stable, so each service's own order survives identical timestamps.
+    merged.sort_by_key(|(timestamp, _)| *timestamp);
+
+    // Each service was asked for `lines`; the merged view honours the same
+    // limit overall, keeping the most recent.
+    if let Some(limit) = lines {
+        let limit = limit.max(0) as usize;
+        if merged.len() > limit {
+            merged.drain(..merged.len() - limit);
+        }
+    }
+
+    for (_, line) in merged {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+/// Deployments whose logs can fall inside `[since, until)`: those created in
+/// the window, plus the last one created before it, which was still serving
+/// when the window opened. `deployments` is newest first.
+fn deployments_in_window(
+    deployments: &[DeploymentsDeploymentsEdgesNode],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Vec<String> {
+    let mut ids: Vec<String> = deployments
+        .iter()
+        .filter(|d| d.created_at >= since && d.created_at < until)
+        .map(|d| d.id.clone())
+        .collect();
+    if let Some(before) = deployments.iter().find(|d| d.created_at < since) {
+        ids.push(before.id.clone());
+    }
+    ids
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportSummary {
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    records: u64,
+    files: Vec<std::path::PathBuf>,
+}
+
+/// `railway logs export`: page through `[since, until)` for each selected
+/// service and log type and write the lines to files and/or OTLP.
+async fn export_command(args: ExportArgs) -> Result<()> {
+    let since = parse_time(&args.since)?;
+    let until = args
+        .until
+        .as_deref()
+        .map(parse_time)
+        .transpose()?
+        .unwrap_or_else(Utc::now);
+    if since >= until {
+        bail!("--since time must be before --until time");
+    }
+
+    std::fs::create_dir_all(&args.out)
+        .with_context(|| format!("Failed to create {}", args.out.display()))?;
+    let state_path = args.out.join(EXPORT_STATE_FILE);
+    let mut state = match ExportState::load(&state_path).filter(|_| !args.restart) {
+        Some(saved) => {
+            if saved.filter != args.filter {
+                bail!(
+                    "{} holds an export with a different --filter. Use --restart or a different --out directory",
+                    args.out.display()
+                );
+            }
+            if !saved.requested_with(&args.since, args.until.as_deref()) {
+                bail!(
+                    "{} holds an export of a different window ({} to {}). Use --restart or a different --out directory",
+                    args.out.display(),
+                    saved.since.to_rfc3339(),
+                    saved.until.to_rfc3339()
+                );
+            }
+            if !args.json {
+                println!(
+                    "Resuming export of {} to {}",
+                    saved.since.to_rfc3339(),
+                    saved.until.to_rfc3339()
+                );
+            }
+            saved
+        }
+        None => ExportState::new(
+            since,
+            until,
+            args.filter.clone(),
+            args.since.clone(),
+            args.until.clone(),
+        ),
+    };
+    state.save(&state_path)?;
+
+    let ctx = resolve_environment_context(args.project.clone(), args.environment.clone()).await?;
+    let backboard = ctx.configs.get_backboard();
+
+    let services = if args.all || !args.service.is_empty() {
+        select_services(&ctx, &args.service, args.all).await?
+    } else {
+        let linked = ctx
+            .linked_service
+            .clone()
+            .ok_or(RailwayError::NoServiceLinked)?;
+        select_services(&ctx, &[linked], false).await?
+    };
+
+    let mut types = args.types.clone();
+    types.sort();
+    types.dedup();
+
+    let mut sources = Vec::new();
+    for (service_id, service_name) in &services {
+        let deployments = if types.iter().any(|t| t.is_per_deployment()) {
+            let all = service_deployments(
+                &ctx.client,
+                &backboard,
+                &ctx.project_id,
+                &ctx.environment_id,
+                service_id,
+            )
+            .await?;
+            deployments_in_window(&all, state.since, state.until)
+        } else {
+            Vec::new()
+        };
+        for log_type in &types {
+            if log_type.is_per_deployment() && deployments.is_empty() {
+                continue;
+            }
+            sources.push(ExportSource {
+                service_id: service_id.clone(),
+                service_name: service_name.clone(),
+                log_type: *log_type,
+                deployment_ids: deployments.clone(),
+            });
+        }
+    }
+    if sources.is_empty() {
+        bail!("No deployments found in the export window");
+    }
+
+    let mut sinks = ExportSinks {
+        files: if args.no_files {
+            None
+        } else {
+            Some(FileSink::new(
+                args.out.clone(),
+                args.format,
+                args.max_file_size.saturating_mul(1024 * 1024),
+            )?)
+        },
+        otlp: args
+            .otlp_endpoint
+            .clone()
+            .map(|endpoint| OtlpSink::new(endpoint, &args.otlp_header))
+            .transpose()?,
+    };
+    let fetcher = ExportFetcher {
+        client: &ctx.client,
+        backboard: &backboard,
+        environment_id: &ctx.environment_id,
+        filter: state.filter.clone(),
+    };
+
+    let mut total = 0u64;
+    for source in &sources {
+        let spinner = crate::util::progress::create_spinner_if(
+            !args.json,
+            format!("Exporting {} {} logs", source.service_name, source.log_type),
+        );
+        let result = export_source(&fetcher, source, &mut state, &state_path, &mut sinks).await;
+        match result {
+            Ok(count) => {
+                total += count;
+                if let Some(mut spinner) = spinner {
+                    crate::util::progress::success_spinner(
+                        &mut spinner,
+                        format!(
+                            "Exported {count} {} {} log lines",
+                            source.service_name, source.log_type
+                        ),
+                    );
+                }
+            }
+            Err(e) => {
+                if let Some(mut spinner) = spinner {
+                    crate::util::progress::fail_spinner(
+                        &mut spinner,
+                        format!(
+                            "Failed exporting {} {} logs",
+                            source.service_name, source.log_type
+                        ),
+                    );
+                }
+                // Close what was written so far; the checkpoint only covers
+                // lines that are already on disk.
+                sinks.finish()?;
+                return Err(e);
+            }
+        }
+    }
+    let files = sinks.finish()?;
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&ExportSummary {
+                since: state.since,
+                until: state.until,
+                records: total,
+                files,
+            })?
+        );
+    } else {
+        println!(
+            "Exported {} log lines{}",
+            total.to_string().bold(),
+            if args.no_files {
+                String::new()
+            } else {
+                format!(" to {}", args.out.display().to_string().cyan())
+            }
+        );
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn base_args() -> Args {
         Args {
-            service: None,
+            command: None,
+            service: Vec::new(),
+            all: false,
             environment: None,
             project: None,
             deployment: false,
@@ -1137,6 +1758,71 @@ mod tests {
         assert!(validate_filter_modes(&args).is_ok());
     }
 
+    #[test]
+    fn export_subcommand_parses() {
+        let args = Args::parse_from([
+            "logs",
+            "export",
+            "--service",
+            "api",
+            "--since",
+            "7d",
+            "--type",
+            "deploy,http",
+            "--format",
+            "gzip",
+            "--otlp-endpoint",
+            "http://localhost:4318/v1/logs",
+            "--otlp-header",
+            "Authorization=Bearer x",
+        ]);
+        let Some(LogsCommand::Export(export)) = args.command else {
+            panic!("expected export subcommand");
+        };
+        assert_eq!(export.service, vec!["api".to_string()]);
+        assert_eq!(
+            export.types,
+            vec![ExportLogType::Deploy, ExportLogType::Http]
+        );
+        assert_eq!(export.format, ExportFormat::Gzip);
+        assert_eq!(export.out, std::path::PathBuf::from("railway-logs"));
+        assert_eq!(export.max_file_size, 100);
+
+        // A bare positional is still a deployment ID, not a subcommand
+        let args = Args::parse_from(["logs", "7422c95b-c604-46bc-9de4-b7a43e1fd53d"]);
+        assert!(args.command.is_none());
+        assert!(args.deployment_id.is_some());
+
+        assert!(Args::try_parse_from(["logs", "export"]).is_err());
+        assert!(Args::try_parse_from(["logs", "export", "--since", "1h", "--no-files"]).is_err());
+    }
+
+    #[test]
+    fn multiple_services_and_all_parse() {
+        let args = Args::parse_from(["logs", "--service", "api", "--service", "worker"]);
+        assert_eq!(args.service, vec!["api", "worker"]);
+
+        let args = Args::parse_from(["logs", "--all", "--json"]);
+        assert!(args.all);
+
+        assert!(Args::try_parse_from(["logs", "--all", "--service", "api"]).is_err());
+    }
+
+    #[test]
+    fn multi_service_logs_only_support_deploy_logs() {
+        let mut args = base_args();
+        args.all = true;
+        assert!(validate_multi_service_args(&args).is_ok());
+
+        args.http = true;
+        assert!(validate_multi_service_args(&args).is_err());
+
+        let mut args = base_args();
+        args.service = vec!["api".to_string(), "worker".to_string()];
+        args.deployment_id = Some("deployment-id".to_string());
+        assert!(validate_multi_service_args(&args).is_err());
+    }
+
     #[test]
     fn status_requires_http_or_network_mode() {
         let mut args = base_args();
@@ -1041,7 +1041,7 @@ impl RailwayMcp {
 
         let vars = mutations::variable_collection_upsert::Variables {
             project_id: ctx.project_id,
-            service_id: ctx.service_id,
+            service_id: Some(ctx.service_id),
             environment_id: ctx.environment_id,
             variables: params.variables,
             skip_deploys: params.skip_deploys,
@@ -1906,9 +1906,7 @@ impl RailwayMcp {
             })?,
         };
 
-        let body = create_deploy_tarball(&path, &path, false, |_, _| {}).map_err(|e| {
-            McpError::internal_error(format!("Failed to create deploy tarball: {e}"), None)
-        })?;
+        let body = create_deploy_tarball(&path, &path, false, |_, _| {});
 
         let hostname = self.configs.get_host();
         let response = upload_deploy_tarball(
@@ -246,7 +246,7 @@ impl RailwayMcp {
 
         let vars = mutations::variable_collection_upsert::Variables {
             project_id: ctx.project_id,
-            service_id: ctx.service_id,
+            service_id: Some(ctx.service_id),
             environment_id: ctx.environment_id,
             variables,
             skip_deploys: None,
@@ -10,6 +10,7 @@ pub fn get_dynamic_args(cmd: clap::Command) -> clap::Command {
 
 pub mod add;
 pub mod api;
+pub mod audit;
 pub mod autoupdate;
 pub mod bucket;
 pub mod cdn;
@@ -25,9 +26,11 @@ pub mod dev;
 pub mod docs;
 pub mod domain;
 pub mod down;
+pub mod edge;
 pub mod environment;
 pub mod flag;
 pub mod functions;
+pub mod import;
 pub mod init;
 pub mod link;
 pub mod list;
@@ -36,11 +39,13 @@ pub mod logout;
 pub mod logs;
 pub mod mcp;
 pub mod metrics;
+pub mod notifications;
 pub mod open;
 pub mod outbound_networking;
 mod output;
 pub mod postgres;
 pub mod private_network;
+pub mod profile;
 pub mod project;
 pub mod redeploy;
 pub mod restart;
@@ -57,6 +62,7 @@ pub mod status;
 pub mod tcp_proxy;
 pub mod telemetry_cmd;
 pub mod templates;
+pub mod token;
 pub mod unlink;
 pub mod up;
 pub mod upgrade;
@@ -0,0 +1,715 @@
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use is_terminal::IsTerminal;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::{
+    controllers::project::{get_project, resolve_project_id_or_name},
+    table::ListTable,
+    util::{progress::create_spinner_if, prompt::prompt_confirm_with_default},
+    workspace::{pick_workspace, workspaces},
+};
+
+use super::*;
+
+/// Manage alerting: notification rules, webhook tests and recent deliveries
+#[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway notify rules list --project\n  railway notify rules add --project api --on deploy-failed --on crashed --to slack:https://hooks.slack.com/services/T000/B000/XXXX\n  railway notify rules add --workspace acme --on deploy-failed --to email --severity critical\n  railway notify rules rm <rule-id> --yes\n  railway notify test https://example.com/railway-webhook\n  railway notify inbox --unread --mark-read\n\nEvents:\n  deploy-failed, crashed, or any raw event type (e.g. Deployment.failed).\n\nChannels:\n  email, slack:<webhook-url>, webhook:<url> (a bare URL is treated as a webhook).\n\nScope:\n  Rules cover a workspace or a project. Railway's API can't create a rule for a single environment, so `rules add` has no --environment; rules created elsewhere with one are listed with their environment."
+)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(Parser)]
+enum Commands {
+    /// List, add and remove notification rules
+    #[clap(subcommand, visible_alias = "rule")]
+    Rules(RulesCommands),
+
+    /// Send a sample payload to a webhook URL
+    Test(TestArgs),
+
+    /// List recent notification deliveries
+    Inbox(InboxArgs),
+}
+
+#[derive(Parser)]
+enum RulesCommands {
+    /// List notification rules
+    #[clap(visible_alias = "ls")]
+    List(ListArgs),
+
+    /// Add a notification rule
+    #[clap(visible_alias = "create")]
+    Add(AddArgs),
+
+    /// Remove a notification rule by ID
+    #[clap(visible_alias = "rm", visible_alias = "delete")]
+    Remove(RemoveArgs),
+}
+
+#[derive(Parser)]
+struct ScopeArgs {
+    /// Workspace ID or name (defaults to your only workspace, or prompts)
+    #[clap(short, long, conflicts_with = "project")]
+    workspace: Option<String>,
+
+    /// Scope to a project ID or name, or the linked project
+    #[clap(long, num_args = 0..=1, value_name = "PROJECT")]
+    project: Option<Option<String>>,
+}
+
+#[derive(Parser)]
+struct ListArgs {
+    #[clap(flatten)]
+    scope: ScopeArgs,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct AddArgs {
+    #[clap(flatten)]
+    scope: ScopeArgs,
+
+    /// Event to alert on: deploy-failed, crashed, or a raw event type (can be repeated)
+    #[clap(long = "on", value_name = "EVENT", required = true)]
+    events: Vec<String>,
+
+    /// Where to send alerts: email, slack:<url> or webhook:<url> (can be repeated)
+    #[clap(long = "to", value_name = "CHANNEL", required = true)]
+    channels: Vec<String>,
+
+    /// Only alert for these severities (can be repeated)
+    #[clap(long = "severity", value_enum)]
+    severities: Vec<Severity>,
+
+    /// Also alert for ephemeral (PR) environments
+    #[clap(long)]
+    ephemeral_environments: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct RemoveArgs {
+    /// ID of the rule to remove (see `railway notify rules list`)
+    id: String,
+
+    /// Skip confirmation dialog
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct TestArgs {
+    /// Webhook URL to send the sample payload to
+    url: String,
+
+    /// JSON payload to send instead of the sample deploy-failed event
+    #[clap(long)]
+    payload: Option<String>,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct InboxArgs {
+    #[clap(flatten)]
+    scope: ScopeArgs,
+
+    /// Only show unread deliveries
+    #[clap(long)]
+    unread: bool,
+
+    /// Maximum number of deliveries to show
+    #[clap(short = 'n', long, default_value_t = 20)]
+    limit: i64,
+
+    /// Mark the listed deliveries as read
+    #[clap(long)]
+    mark_read: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Severity {
+    Info,
+    Notice,
+    Warning,
+    Critical,
+}
+
+impl From<Severity> for mutations::notification_rule_create::NotificationSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Info => Self::INFO,
+            Severity::Notice => Self::NOTICE,
+            Severity::Warning => Self::WARNING,
+            Severity::Critical => Self::CRITICAL,
+        }
+    }
+}
+
+/// Friendly names for the event types people alert on most.
+const EVENT_ALIASES: &[(&str, &str)] = &[
+    ("deploy-failed", "Deployment.failed"),
+    ("crashed", "Deployment.crashed"),
+];
+
+struct Scope {
+    workspace_id: String,
+    project_id: Option<String>,
+    label: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleOutput {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment_id: Option<String>,
+    event_types: Vec<String>,
+    severities: Vec<String>,
+    channels: Vec<Value>,
+    ephemeral_environments: bool,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeliveryOutput {
+    id: String,
+    channel: String,
+    status: String,
+    created_at: DateTime<Utc>,
+    read: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_type: Option<String>,
+    severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_id: Option<String>,
+    payload: Value,
+}
+
+pub async fn command(args: Args) -> Result<()> {
+    match args.command {
+        Commands::Rules(RulesCommands::List(args)) => list_rules(args).await,
+        Commands::Rules(RulesCommands::Add(args)) => add_rule(args).await,
+        Commands::Rules(RulesCommands::Remove(args)) => remove_rule(args).await,
+        Commands::Test(args) => test_webhook(args).await,
+        Commands::Inbox(args) => inbox(args).await,
+    }
+}
+
+async fn list_rules(args: ListArgs) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let scope = resolve_scope(&client, &configs, args.scope).await?;
+
+    let rules = post_graphql::<queries::NotificationRules, _>(
+        &client,
+        configs.get_backboard(),
+        queries::notification_rules::Variables {
+            workspace_id: scope.workspace_id.clone(),
+            project_id: scope.project_id.clone(),
+        },
+    )
+    .await?
+    .notification_rules
+    .into_iter()
+    .map(|rule| RuleOutput {
+        id: rule.id,
+        project_id: rule.project_id,
+        environment_id: rule.environment_id,
+        event_types: rule.event_types,
+        severities: rule.severities.iter().map(|s| format!("{s:?}")).collect(),
+        channels: rule.channels.into_iter().map(|c| c.config).collect(),
+        ephemeral_environments: rule.ephemeral_environments.unwrap_or(false),
+        created_at: rule.created_at,
+    })
+    .collect::<Vec<_>>();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+        return Ok(());
+    }
+    if rules.is_empty() {
+        println!("No notification rules for {}", scope.label);
+        return Ok(());
+    }
+
+    let rows = rules
+        .iter()
+        .map(|rule| {
+            vec![
+                rule.id.clone(),
+                rule.event_types
+                    .iter()
+                    .map(|t| event_label(t))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                rule.channels
+                    .iter()
+                    .map(describe_channel)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                if rule.severities.is_empty() {
+                    "any".to_string()
+                } else {
+                    rule.severities.join(", ").to_lowercase()
+                },
+                match (&rule.project_id, &rule.environment_id) {
+                    (_, Some(environment)) => format!("environment {environment}"),
+                    (Some(project), None) => format!("project {project}"),
+                    (None, None) => "workspace".to_string(),
+                },
+            ]
+        })
+        .collect();
+    ListTable::new(
+        format!("Notification rules for {}", scope.label),
+        &["ID", "Events", "Channels", "Severity", "Scope"],
+        rows,
+    )
+    .print();
+    Ok(())
+}
+
+async fn add_rule(args: AddArgs) -> Result<()> {
+    let event_types = args
+        .events
+        .iter()
+        .map(|event| parse_event(event))
+        .collect::<Result<Vec<_>>>()?;
+    let channel_configs = args
+        .channels
+        .iter()
+        .map(|channel| parse_channel(channel))
+        .collect::<Result<Vec<_>>>()?;
+
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let scope = resolve_scope(&client, &configs, args.scope).await?;
+
+    let spinner = create_spinner_if(!args.json, "Creating notification rule...".into());
+    let rule = post_graphql::<mutations::NotificationRuleCreate, _>(
+        &client,
+        configs.get_backboard(),
+        mutations::notification_rule_create::Variables {
+            input: mutations::notification_rule_create::CreateNotificationRuleInput {
+                workspace_id: scope.workspace_id.clone(),
+                project_id: scope.project_id.clone(),
+                event_types: event_types.clone(),
+                channel_configs: channel_configs.clone(),
+                severities: (!args.severities.is_empty())
+                    .then(|| args.severities.iter().map(|s| (*s).into()).collect()),
+                ephemeral_environments: args.ephemeral_environments.then_some(true),
+            },
+        },
+    )
+    .await?
+    .notification_rule_create;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "id": rule.id,
+                "eventTypes": rule.event_types,
+                "channels": channel_configs,
+                "workspaceId": scope.workspace_id,
+                "projectId": scope.project_id,
+            }))?
+        );
+        return Ok(());
+    }
+    println!(
+        "Added rule {} for {}: {} → {}",
+        rule.id.green(),
+        scope.label,
+        event_types
+            .iter()
+            .map(|t| event_label(t))
+            .collect::<Vec<_>>()
+            .join(", ")
+            .bold(),
+        channel_configs
+            .iter()
+            .map(describe_channel)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Ok(())
+}
+
+async fn remove_rule(args: RemoveArgs) -> Result<()> {
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default(&format!("Remove notification rule {}?", args.id), false)?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    post_graphql::<mutations::NotificationRuleDelete, _>(
+        &client,
+        configs.get_backboard(),
+        mutations::notification_rule_delete::Variables {
+            id: args.id.clone(),
+        },
+    )
+    .await?;
+
+    if args.json {
+        println!("{}", json!({ "id": args.id }));
+    } else {
+        println!("Removed notification rule {}", args.id.green());
+    }
+    Ok(())
+}
+
+async fn test_webhook(args: TestArgs) -> Result<()> {
+    if !is_http_url(&args.url) {
+        bail!("Webhook URL must start with http:// or https://");
+    }
+    let payload = match args.payload {
+        Some(payload) => {
+            serde_json::from_str::<Value>(&payload).context("--payload must be valid JSON")?;
+            payload
+        }
+        None => sample_payload().to_string(),
+    };
+
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let spinner = create_spinner_if(
+        !args.json,
+        format!("Sending test payload to {}...", args.url),
+    );
+    let status = post_graphql::<mutations::WebhookTest, _>(
+        &client,
+        configs.get_backboard(),
+        mutations::webhook_test::Variables {
+            url: args.url.clone(),
+            payload,
+        },
+    )
+    .await?
+    .webhook_test;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+
+    let ok = (200..300).contains(&status);
+    if args.json {
+        println!("{}", json!({ "url": args.url, "status": status, "ok": ok }));
+    } else if ok {
+        println!("Webhook responded with {}", status.to_string().green());
+    } else {
+        println!("Webhook responded with {}", status.to_string().red());
+    }
+    if !ok {
+        bail!("Webhook test failed with HTTP status {status}");
+    }
+    Ok(())
+}
+
+async fn inbox(args: InboxArgs) -> Result<()> {
+    if args.limit < 1 {
+        bail!("--limit must be at least 1");
+    }
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let scope = resolve_scope(&client, &configs, args.scope).await?;
+
+    let deliveries = post_graphql::<queries::NotificationDeliveries, _>(
+        &client,
+        configs.get_backboard(),
+        queries::notification_deliveries::Variables {
+            filter: Some(
+                queries::notification_deliveries::NotificationDeliveryFilterInput {
+                    workspace_id: Some(scope.workspace_id.clone()),
+                    project_id: scope.project_id.clone(),
+                    environment_id: None,
+                    only_unread: args.unread.then_some(true),
+                    status: None,
+                    type_: None,
+                },
+            ),
+            first: Some(args.limit),
+            after: None,
+        },
+    )
+    .await?
+    .notification_deliveries
+    .edges
+    .into_iter()
+    .map(|edge| {
+        let delivery = edge.node;
+        let instance = delivery.notification_instance;
+        DeliveryOutput {
+            id: delivery.id,
+            channel: format!("{:?}", delivery.type_).to_lowercase(),
+            status: format!("{:?}", delivery.status).to_lowercase(),
+            created_at: delivery.created_at,
+            read: delivery.read_at.is_some(),
+            event_type: instance.event_type,
+            severity: format!("{:?}", instance.severity).to_lowercase(),
+            project_id: instance.project_id,
+            service_id: instance.service_id,
+            payload: instance.payload,
+        }
+    })
+    .collect::<Vec<_>>();
+
+    if args.mark_read {
+        let unread = deliveries
+            .iter()
+            .filter(|d| !d.read)
+            .map(|d| d.id.clone())
+            .collect::<Vec<_>>();
+        if !unread.is_empty() {
+            post_graphql::<mutations::NotificationDeliveriesMarkAsRead, _>(
+                &client,
+                configs.get_backboard(),
+                mutations::notification_deliveries_mark_as_read::Variables {
+                    delivery_ids: unread,
+                },
+            )
+            .await?;
+        }
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&deliveries)?);
+        return Ok(());
+    }
+    if deliveries.is_empty() {
+        println!("No notifications for {}", scope.label);
+        return Ok(());
+    }
+
+    let rows = deliveries
+        .iter()
+        .map(|delivery| {
+            let mark = if delivery.read { " " } else { "●" };
+            vec![
+                format!(
+                    "{mark} {}",
+                    DateTime::<Local>::from(delivery.created_at).format("%Y-%m-%d %H:%M")
+                ),
+                delivery
+                    .event_type
+                    .as_deref()
+                    .map(event_label)
+                    .unwrap_or_else(|| "-".to_string()),
+                delivery.severity.clone(),
+                delivery.channel.clone(),
+                delivery.status.clone(),
+            ]
+        })
+        .collect();
+    ListTable::new(
+        format!("Notifications for {}", scope.label),
+        &["Time", "Event", "Severity", "Channel", "Status"],
+        rows,
+    )
+    .print();
+    Ok(())
+}
+
+/// Resolves `--project [PROJECT]` (and its workspace) or `--workspace`.
+async fn resolve_scope(
+    client: &reqwest::Client,
+    configs: &Configs,
+    args: ScopeArgs,
+) -> Result<Scope> {
+    let Some(project) = args.project else {
+        let workspace = pick_workspace(workspaces().await?, args.workspace)?;
+        return Ok(Scope {
+            workspace_id: workspace.id().to_string(),
+            project_id: None,
+            label: format!("workspace {}", workspace.name()),
+        });
+    };
+
+    let project_id = match project {
+        Some(project) => resolve_project_id_or_name(client, configs, &project).await?,
+        None => configs.get_linked_project().await?.project,
+    };
+    let project = get_project(client, configs, project_id).await?;
+    let workspace_id = project
+        .workspace_id
+        .context("Project is not in a workspace")?;
+    Ok(Scope {
+        workspace_id,
+        project_id: Some(project.id),
+        label: format!("project {}", project.name),
+    })
+}
+
+/// Maps `--on` values to event types, accepting aliases or raw types.
+fn parse_event(event: &str) -> Result<String> {
+    let event = event.trim();
+    if let Some((_, event_type)) = EVENT_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(event))
+    {
+        return Ok(event_type.to_string());
+    }
+    if event.contains('.') {
+        return Ok(event.to_string());
+    }
+    let aliases = EVENT_ALIASES
+        .iter()
+        .map(|(alias, _)| *alias)
+        .collect::<Vec<_>>()
+        .join(", ");
+    bail!(
+        "Unknown event '{event}'. Use one of {aliases}, or a raw event type like Deployment.failed"
+    )
+}
+
+fn event_label(event_type: &str) -> String {
+    EVENT_ALIASES
+        .iter()
+        .find(|(_, t)| *t == event_type)
+        .map(|(alias, _)| alias.to_string())
+        .unwrap_or_else(|| event_type.to_string())
+}
+
+/// Parses a `--to` value into a channel config.
+fn parse_channel(channel: &str) -> Result<Value> {
+    let channel = channel.trim();
+    if channel.eq_ignore_ascii_case("email") {
+        return Ok(json!({ "type": "email" }));
+    }
+    if is_http_url(channel) {
+        return Ok(json!({ "type": "webhook", "url": channel }));
+    }
+    match channel.split_once(':') {
+        Some((kind @ ("slack" | "webhook"), url)) if is_http_url(url) => {
+            Ok(json!({ "type": kind, "url": url }))
+        }
+        Some(("slack" | "webhook", _)) => {
+            bail!(
+                "Channel '{channel}' needs an http(s) URL, e.g. slack:https://hooks.slack.com/..."
+            )
+        }
+        _ => bail!("Unknown channel '{channel}'. Use email, slack:<url> or webhook:<url>"),
+    }
+}
+
+fn describe_channel(config: &Value) -> String {
+    let kind = config
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    match config.get("url").and_then(Value::as_str) {
+        Some(url) => format!("{kind} {}", redact_url(url)),
+        None => kind.to_string(),
+    }
+}
+
+/// Keeps the host of a webhook URL; the path usually embeds a secret.
+fn redact_url(url: &str) -> String {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host = rest.split('/').next().unwrap_or(rest);
+            if rest.len() > host.len() {
+                format!("{scheme}://{host}/…")
+            } else {
+                url.to_string()
+            }
+        }
+        None => url.to_string(),
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+fn sample_payload() -> Value {
+    json!({
+        "type": "Deployment.failed",
+        "severity": "CRITICAL",
+        "timestamp": Utc::now().to_rfc3339(),
+        "details": {
+            "status": "FAILED",
+            "source": "railway notify test",
+        },
+        "resource": {
+            "project": { "id": "00000000-0000-0000-0000-000000000000", "name": "example" },
+            "service": { "id": "00000000-0000-0000-0000-000000000000", "name": "web" },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_event_aliases_and_raw_types() {
+        assert_eq!(parse_event("deploy-failed").unwrap(), "Deployment.failed");
+        assert_eq!(parse_event("Crashed").unwrap(), "Deployment.crashed");
+        assert_eq!(parse_event("Volume.alert").unwrap(), "Volume.alert");
+        assert!(parse_event("explode").is_err());
+        assert_eq!(event_label("Deployment.crashed"), "crashed");
+        assert_eq!(event_label("Volume.alert"), "Volume.alert");
+    }
+
+    #[test]
+    fn parses_channels() {
+        assert_eq!(parse_channel("email").unwrap(), json!({ "type": "email" }));
+        assert_eq!(
+            parse_channel("slack:https://hooks.slack.com/services/T/B/X").unwrap(),
+            json!({ "type": "slack", "url": "https://hooks.slack.com/services/T/B/X" })
+        );
+        assert_eq!(
+            parse_channel("https://example.com/hook").unwrap(),
+            json!({ "type": "webhook", "url": "https://example.com/hook" })
+        );
+        assert!(parse_channel("slack:not-a-url").is_err());
+        assert!(parse_channel("pager").is_err());
+
+        assert_eq!(
+            describe_channel(
+                &parse_channel("slack:https://hooks.slack.com/services/T/B/X").unwrap()
+            ),
+            "slack https://hooks.slack.com/…"
+        );
+    }
+}
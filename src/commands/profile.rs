@@ -0,0 +1,202 @@
+use is_terminal::IsTerminal;
+use serde::Serialize;
+
+use crate::util::prompt::prompt_confirm_with_default;
+
+use super::*;
+
+/// Manage named login profiles (e.g. a personal and a work account)
+#[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway profile add work\n  railway --profile work login\n  railway profile use work\n  RAILWAY_PROFILE=personal railway whoami\n\nEach profile keeps its own login and linked projects. The active profile is picked by --profile, then RAILWAY_PROFILE, then `railway profile use`."
+)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(Parser)]
+enum Commands {
+    /// List profiles and show which one is active
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Output in JSON format
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Create a new, logged-out profile
+    Add {
+        /// Profile name (letters, numbers, '-' and '_')
+        name: String,
+
+        /// Make the new profile the active one
+        #[clap(long = "use")]
+        use_profile: bool,
+    },
+
+    /// Set the profile used when --profile and RAILWAY_PROFILE are unset
+    #[clap(visible_alias = "switch")]
+    Use {
+        /// Profile name, or "default"
+        name: String,
+    },
+
+    /// Delete a profile along with its login and linked projects
+    #[clap(visible_aliases = ["rm", "delete"])]
+    Remove {
+        /// Profile name
+        name: String,
+
+        /// Skip confirmation dialog
+        #[clap(short = 'y', long = "yes")]
+        yes: bool,
+    },
+
+    /// Print the active profile
+    Current,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileJson {
+    name: String,
+    active: bool,
+    logged_in: bool,
+    linked_projects: usize,
+}
+
+pub async fn command(args: Args) -> Result<()> {
+    match args.command {
+        Commands::List { json } => list(json),
+        Commands::Add { name, use_profile } => add(&name, use_profile),
+        Commands::Use { name } => use_profile(&name),
+        Commands::Remove { name, yes } => remove(&name, yes),
+        Commands::Current => {
+            println!("{}", Configs::active_profile());
+            Ok(())
+        }
+    }
+}
+
+/// Env tokens apply to every profile alike, so only the stored login says
+/// anything about a particular profile.
+fn has_stored_login(configs: &Configs) -> bool {
+    let user = &configs.root_config.user;
+    user.access_token.is_some() || user.token.as_deref().is_some_and(|t| !t.is_empty())
+}
+
+fn list(json: bool) -> Result<()> {
+    let active = Configs::active_profile();
+    let profiles = Configs::list_profiles()?
+        .into_iter()
+        .map(|name| {
+            let configs = Configs::for_profile(&name)?;
+            Ok(ProfileJson {
+                active: name == active,
+                logged_in: has_stored_login(&configs),
+                linked_projects: configs.root_config.projects.len(),
+                name,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&profiles)?);
+        return Ok(());
+    }
+
+    for profile in profiles {
+        let marker = if profile.active { "*" } else { " " };
+        let name = if profile.active {
+            profile.name.green().bold()
+        } else {
+            profile.name.normal()
+        };
+        let status = if profile.logged_in {
+            "logged in".to_string()
+        } else {
+            "logged out".dimmed().to_string()
+        };
+        println!(
+            "{marker} {name} {}",
+            format!("({status}, {} linked)", profile.linked_projects).dimmed()
+        );
+    }
+
+    if let Ok(from_env) = std::env::var(crate::consts::RAILWAY_PROFILE_ENV) {
+        if !from_env.is_empty() {
+            println!(
+                "\n{}",
+                format!("Active profile set by RAILWAY_PROFILE={from_env}").dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn add(name: &str, use_after: bool) -> Result<()> {
+    Configs::create_profile(name)?;
+    println!("Created profile {}", name.cyan().bold());
+
+    if use_after {
+        Configs::set_stored_active_profile(name)?;
+        println!("Switched to profile {}", name.cyan().bold());
+        println!("Run {} to sign in.", "railway login".bold());
+    } else {
+        println!(
+            "Run {} to sign in.",
+            format!("railway --profile {name} login").bold()
+        );
+    }
+    Ok(())
+}
+
+fn use_profile(name: &str) -> Result<()> {
+    Configs::set_stored_active_profile(name)?;
+    println!("Switched to profile {}", name.cyan().bold());
+
+    if !has_stored_login(&Configs::for_profile(name)?) {
+        println!(
+            "{}",
+            format!("Profile {name} is not logged in. Run `railway login`.").yellow()
+        );
+    }
+    Ok(())
+}
+
+fn remove(name: &str, yes: bool) -> Result<()> {
+    if !Configs::profile_exists(name)? {
+        bail!(crate::errors::RailwayError::ProfileNotFound(
+            name.to_string()
+        ));
+    }
+
+    if !yes {
+        if !std::io::stdout().is_terminal() {
+            bail!(
+                "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+            );
+        }
+        let confirmed = prompt_confirm_with_default(
+            &format!(
+                "Remove profile \"{}\"? Its login and linked projects will be deleted.",
+                name.red()
+            ),
+            false,
+        )?;
+        if !confirmed {
+            println!("Removal cancelled.");
+            return Ok(());
+        }
+    }
+
+    Configs::remove_profile(name)?;
+    if Configs::stored_active_profile().as_deref() == Some(name) {
+        Configs::set_stored_active_profile(DEFAULT_PROFILE)?;
+        println!("Switched back to the {DEFAULT_PROFILE} profile");
+    }
+    println!("Removed profile {}", name.cyan().bold());
+    Ok(())
+}
@@ -0,0 +1,605 @@
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use is_terminal::IsTerminal;
+use serde::Serialize;
+
+use crate::{
+    controllers::project::resolve_project_id_or_name, table::ListTable,
+    util::prompt::prompt_confirm_with_default,
+};
+
+use super::*;
+
+#[derive(Parser)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct MembersArgs {
+    #[clap(subcommand)]
+    command: Option<MembersCommands>,
+
+    #[clap(flatten)]
+    list: ProjectArgs,
+}
+
+#[derive(Parser)]
+enum MembersCommands {
+    /// List members and pending invitations (default)
+    #[clap(visible_alias = "ls")]
+    List(ProjectArgs),
+
+    /// Give a workspace member access to the project
+    Add(AddArgs),
+
+    /// Remove a member, or cancel a pending invitation
+    #[clap(visible_alias = "rm")]
+    Remove(RemoveArgs),
+
+    /// Change a member's role
+    Role(RoleArgs),
+}
+
+#[derive(Parser)]
+struct ProjectArgs {
+    /// Project ID or name (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT")]
+    project: Option<String>,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct AddArgs {
+    /// Email or user ID of a member of the project's workspace
+    user: String,
+
+    /// Role to grant
+    #[clap(long, value_enum, default_value = "member")]
+    role: Role,
+
+    #[clap(flatten)]
+    target: ProjectArgs,
+}
+
+#[derive(Parser)]
+struct RemoveArgs {
+    /// Email or user ID of the member (or email of the invitation) to remove
+    user: String,
+
+    /// Skip confirmation dialog
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    #[clap(flatten)]
+    target: ProjectArgs,
+}
+
+#[derive(Parser)]
+struct RoleArgs {
+    /// Email or user ID of the member
+    user: String,
+
+    /// New role
+    #[clap(value_enum)]
+    role: Role,
+
+    #[clap(flatten)]
+    target: ProjectArgs,
+}
+
+#[derive(Parser)]
+pub struct InviteArgs {
+    /// Email address to invite
+    email: String,
+
+    /// Role the invitee gets on accepting
+    #[clap(long, value_enum, default_value = "member")]
+    role: Role,
+
+    /// Resend the pending invitation for this email instead of creating one
+    #[clap(long, conflicts_with = "role")]
+    resend: bool,
+
+    #[clap(flatten)]
+    target: ProjectArgs,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Role {
+    Admin,
+    Member,
+    Viewer,
+}
+
+macro_rules! project_role {
+    ($module:ident, $role:expr) => {{
+        use mutations::$module::ProjectRole;
+        match $role {
+            Role::Admin => ProjectRole::ADMIN,
+            Role::Member => ProjectRole::MEMBER,
+            Role::Viewer => ProjectRole::VIEWER,
+        }
+    }};
+}
+
+/// How a role returned by the API is shown, in text and JSON alike.
+macro_rules! role_name {
+    ($($module:ident)::+, $role:expr) => {{
+        use $($module)::+::ProjectRole;
+        match $role {
+            ProjectRole::ADMIN => "admin".to_string(),
+            ProjectRole::MEMBER => "member".to_string(),
+            ProjectRole::VIEWER => "viewer".to_string(),
+            ProjectRole::Other(other) => other.to_lowercase(),
+        }
+    }};
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MemberOutput {
+    id: String,
+    email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    role: String,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+struct ProjectMembers {
+    project_id: String,
+    project_name: String,
+    workspace_id: Option<String>,
+    members: Vec<queries::project_members::ProjectMembersProjectMembers>,
+    invitations: Vec<queries::project_members::ProjectMembersProjectInvitations>,
+}
+
+pub async fn members_command(args: MembersArgs) -> Result<()> {
+    match args.command {
+        None => list_members(args.list).await,
+        Some(MembersCommands::List(args)) => list_members(args).await,
+        Some(MembersCommands::Add(args)) => add_member(args).await,
+        Some(MembersCommands::Remove(args)) => remove_member(args).await,
+        Some(MembersCommands::Role(args)) => update_role(args).await,
+    }
+}
+
+async fn fetch_members(
+    client: &reqwest::Client,
+    configs: &Configs,
+    project: Option<String>,
+) -> Result<ProjectMembers> {
+    let project_id = match project {
+        Some(project) => resolve_project_id_or_name(client, configs, &project).await?,
+        None => configs.get_linked_project().await?.project,
+    };
+    let data = post_graphql::<queries::ProjectMembers, _>(
+        client,
+        configs.get_backboard(),
+        queries::project_members::Variables {
+            project_id: project_id.clone(),
+        },
+    )
+    .await?;
+    Ok(ProjectMembers {
+        project_id,
+        project_name: data.project.name,
+        workspace_id: data.project.workspace_id,
+        members: data.project_members,
+        invitations: data.project_invitations,
+    })
+}
+
+impl ProjectMembers {
+    /// The member whose user ID or email (in any case) is `user`.
+    fn member(
+        &self,
+        user: &str,
+    ) -> Option<&queries::project_members::ProjectMembersProjectMembers> {
+        self.members
+            .iter()
+            .find(|m| matches_user(&m.id, &m.email, user))
+    }
+
+    /// The pending invitation whose ID or email is `user`.
+    fn invitation(
+        &self,
+        user: &str,
+    ) -> Option<&queries::project_members::ProjectMembersProjectInvitations> {
+        self.invitations
+            .iter()
+            .find(|i| matches_user(&i.id, &i.email, user))
+    }
+}
+
+fn matches_user(id: &str, email: &str, user: &str) -> bool {
+    id == user || email.eq_ignore_ascii_case(user)
+}
+
+async fn list_members(args: ProjectArgs) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let project = fetch_members(&client, &configs, args.project).await?;
+
+    let mut output: Vec<MemberOutput> = project
+        .members
+        .into_iter()
+        .map(|member| MemberOutput {
+            id: member.id,
+            email: member.email,
+            name: member.name,
+            role: role_name!(queries::project_members, member.role),
+            status: "active",
+            expires_at: None,
+        })
+        .collect();
+    output.extend(
+        project
+            .invitations
+            .into_iter()
+            .map(|invitation| MemberOutput {
+                id: invitation.id,
+                email: invitation.email,
+                name: None,
+                // The API doesn't say which role an invitation grants.
+                role: "invited".to_string(),
+                status: if invitation.is_expired {
+                    "expired"
+                } else {
+                    "invited"
+                },
+                expires_at: Some(invitation.expires_at),
+            }),
+    );
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    if output.is_empty() {
+        println!("No members found");
+        return Ok(());
+    }
+
+    let rows = output
+        .into_iter()
+        .map(|member| {
+            let status = match member.expires_at {
+                Some(at) => format!(
+                    "{} until {}",
+                    member.status,
+                    DateTime::<Local>::from(at).format("%Y-%m-%d")
+                ),
+                None => member.status.to_string(),
+            };
+            vec![
+                member.email,
+                member.name.unwrap_or_default(),
+                member.role,
+                status,
+                member.id,
+            ]
+        })
+        .collect();
+    ListTable::new(
+        format!("Members of {}", project.project_name),
+        &["Email", "Name", "Role", "Status", "ID"],
+        rows,
+    )
+    .print();
+    Ok(())
+}
+
+async fn add_member(args: AddArgs) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let project = fetch_members(&client, &configs, args.target.project).await?;
+
+    if project.member(&args.user).is_some() {
+        bail!(
+            "{} is already a member of {}. Use `railway project members role` to change their role.",
+            args.user,
+            project.project_name
+        );
+    }
+    let workspace_id = project
+        .workspace_id
+        .context("This project doesn't belong to a workspace; invite people with `railway project invite` instead")?;
+    let workspace = post_graphql::<queries::WorkspaceMembers, _>(
+        &client,
+        configs.get_backboard(),
+        queries::workspace_members::Variables { workspace_id },
+    )
+    .await?
+    .workspace;
+    let user = workspace
+        .members
+        .into_iter()
+        .find(|m| matches_user(&m.id, &m.email, &args.user))
+        .with_context(|| {
+            format!(
+                "{} is not a member of this project's workspace. Invite them with `railway project invite {}`.",
+                args.user, args.user
+            )
+        })?;
+
+    let member = post_graphql::<mutations::ProjectMemberAdd, _>(
+        &client,
+        configs.get_backboard(),
+        mutations::project_member_add::Variables {
+            input: mutations::project_member_add::ProjectMemberAddInput {
+                project_id: project.project_id,
+                user_id: user.id,
+                role: project_role!(project_member_add, args.role),
+            },
+        },
+    )
+    .await?
+    .project_member_add;
+
+    let role = role_name!(mutations::project_member_add, member.role);
+    if args.target.json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": member.id, "email": member.email, "role": role })
+        );
+    } else {
+        println!(
+            "Added {} to {} as {}",
+            member.email.green(),
+            project.project_name.bold(),
+            role
+        );
+    }
+    Ok(())
+}
+
+async fn remove_member(args: RemoveArgs) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let project = fetch_members(&client, &configs, args.target.project).await?;
+
+    let member = project.member(&args.user);
+    let invitation = project.invitation(&args.user);
+    let prompt = match (member, invitation) {
+        (Some(member), _) => format!(
+            "Remove {} from project {}?",
+            member.email, project.project_name
+        ),
+        (None, Some(invitation)) => format!(
+            "Cancel the invitation for {} to project {}?",
+            invitation.email, project.project_name
+        ),
+        (None, None) => bail!(
+            "{} is not a member of, or invited to, project {}",
+            args.user,
+            project.project_name
+        ),
+    };
+
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default(&prompt, false)?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    let (id, email, status) = if let Some(member) = member {
+        post_graphql::<mutations::ProjectMemberRemove, _>(
+            &client,
+            configs.get_backboard(),
+            mutations::project_member_remove::Variables {
+                input: mutations::project_member_remove::ProjectMemberRemoveInput {
+                    project_id: project.project_id.clone(),
+                    user_id: member.id.clone(),
+                },
+            },
+        )
+        .await?;
+        (member.id.clone(), member.email.clone(), "removed")
+    } else {
+        let invitation = invitation.expect("checked above");
+        post_graphql::<mutations::ProjectInvitationDelete, _>(
+            &client,
+            configs.get_backboard(),
+            mutations::project_invitation_delete::Variables {
+                id: invitation.id.clone(),
+            },
+        )
+        .await?;
+        (invitation.id.clone(), invitation.email.clone(), "uninvited")
+    };
+
+    if args.target.json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": id, "email": email, "status": status })
+        );
+    } else if status == "removed" {
+        println!(
+            "Removed {} from {}",
+            email.green(),
+            project.project_name.bold()
+        );
+    } else {
+        println!("Cancelled the invitation for {}", email.green());
+    }
+    Ok(())
+}
+
+async fn update_role(args: RoleArgs) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let project = fetch_members(&client, &configs, args.target.project).await?;
+    let member = project.member(&args.user).with_context(|| {
+        format!(
+            "{} is not a member of project {}",
+            args.user, project.project_name
+        )
+    })?;
+
+    let member = post_graphql::<mutations::ProjectMemberUpdate, _>(
+        &client,
+        configs.get_backboard(),
+        mutations::project_member_update::Variables {
+            input: mutations::project_member_update::ProjectMemberUpdateInput {
+                project_id: project.project_id.clone(),
+                user_id: member.id.clone(),
+                role: project_role!(project_member_update, args.role),
+            },
+        },
+    )
+    .await?
+    .project_member_update;
+
+    let role = role_name!(mutations::project_member_update, member.role);
+    if args.target.json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": member.id, "email": member.email, "role": role })
+        );
+    } else {
+        println!(
+            "{} is now {} on {}",
+            member.email.green(),
+            role,
+            project.project_name.bold()
+        );
+    }
+    Ok(())
+}
+
+pub async fn invite_command(args: InviteArgs) -> Result<()> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let project = fetch_members(&client, &configs, args.target.project).await?;
+
+    if project
+        .members
+        .iter()
+        .any(|m| m.email.eq_ignore_ascii_case(&args.email))
+    {
+        bail!(
+            "{} is already a member of {}",
+            args.email,
+            project.project_name
+        );
+    }
+
+    let (id, email, expires_at) = if args.resend {
+        let invitation = project
+            .invitations
+            .iter()
+            .find(|i| i.email.eq_ignore_ascii_case(&args.email))
+            .with_context(|| format!("No pending invitation for {}", args.email))?;
+        let resent = post_graphql::<mutations::ProjectInvitationResend, _>(
+            &client,
+            configs.get_backboard(),
+            mutations::project_invitation_resend::Variables {
+                id: invitation.id.clone(),
+            },
+        )
+        .await?
+        .project_invitation_resend;
+        (resent.id, resent.email, resent.expires_at)
+    } else {
+        let created = post_graphql::<mutations::ProjectInvitationCreate, _>(
+            &client,
+            configs.get_backboard(),
+            mutations::project_invitation_create::Variables {
+                id: project.project_id.clone(),
+                input: mutations::project_invitation_create::ProjectInvitee {
+                    email: args.email.clone(),
+                    role: project_role!(project_invitation_create, args.role),
+                },
+            },
+        )
+        .await?
+        .project_invitation_create;
+        (created.id, created.email, created.expires_at)
+    };
+
+    if args.target.json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": id, "email": email, "expiresAt": expires_at })
+        );
+    } else {
+        println!(
+            "{} {} to {} (expires {})",
+            if args.resend {
+                "Re-sent the invitation for"
+            } else {
+                "Invited"
+            },
+            email.green(),
+            project.project_name.bold(),
+            DateTime::<Local>::from(expires_at).format("%Y-%m-%d")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use queries::project_members::{
+        ProjectMembersProjectInvitations, ProjectMembersProjectMembers, ProjectRole,
+    };
+
+    fn project() -> ProjectMembers {
+        ProjectMembers {
+            project_id: "p1".into(),
+            project_name: "api".into(),
+            workspace_id: None,
+            members: vec![ProjectMembersProjectMembers {
+                id: "u1".into(),
+                email: "Alice@Example.com".into(),
+                name: Some("Alice".into()),
+                role: ProjectRole::ADMIN,
+            }],
+            invitations: vec![ProjectMembersProjectInvitations {
+                id: "inv1".into(),
+                email: "bob@example.com".into(),
+                expires_at: Utc::now(),
+                is_expired: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn members_resolve_by_email_or_id() {
+        let project = project();
+        assert_eq!(project.member("u1").unwrap().id, "u1");
+        assert_eq!(project.member("alice@example.com").unwrap().id, "u1");
+        assert!(project.member("Alice").is_none());
+        assert!(project.member("bob@example.com").is_none());
+
+        assert_eq!(project.invitation("BOB@example.com").unwrap().id, "inv1");
+        assert_eq!(project.invitation("inv1").unwrap().id, "inv1");
+        assert!(project.invitation("u1").is_none());
+    }
+
+    #[test]
+    fn roles_render_by_name() {
+        assert_eq!(
+            role_name!(queries::project_members, ProjectRole::VIEWER),
+            "viewer"
+        );
+        assert_eq!(
+            role_name!(
+                queries::project_members,
+                ProjectRole::Other("BILLING".into())
+            ),
+            "billing"
+        );
+    }
+}
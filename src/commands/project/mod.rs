@@ -1,7 +1,12 @@
 use super::*;
 
+mod members;
+
 /// Manage projects
 #[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway project members --json\n  railway project members add teammate@example.com --role viewer\n  railway project members role teammate@example.com admin\n  railway project members remove teammate@example.com --yes\n  railway project invite new-hire@example.com --role member\n  railway project invite new-hire@example.com --resend"
+)]
 pub struct Args {
     #[clap(subcommand)]
     command: Commands,
@@ -19,6 +24,13 @@ enum Commands {
     /// Delete a project
     #[clap(visible_alias = "rm", visible_alias = "remove")]
     Delete(crate::commands::delete::Args),
+
+    /// List and manage who has access to a project
+    #[clap(visible_alias = "member")]
+    Members(members::MembersArgs),
+
+    /// Invite someone to a project by email
+    Invite(members::InviteArgs),
 }
 
 pub async fn command(args: Args) -> Result<()> {
@@ -26,5 +38,7 @@ pub async fn command(args: Args) -> Result<()> {
         Commands::List(list_args) => crate::commands::list::command(list_args).await,
         Commands::Link(link_args) => crate::commands::link::command(link_args).await,
         Commands::Delete(delete_args) => crate::commands::delete::command(delete_args).await,
+        Commands::Members(members_args) => members::members_command(members_args).await,
+        Commands::Invite(invite_args) => members::invite_command(invite_args).await,
     }
 }
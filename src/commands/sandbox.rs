@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -11,9 +11,13 @@ use crate::commands::ssh::{
     DurableResume, PortForward, ensure_ssh_key, run_native_ssh, run_native_ssh_forward, tel,
 };
 use crate::config::{Configs, StoredSandbox, StoredSandboxTemplate};
+use crate::controllers::deployment_timeline::format_duration;
 use crate::controllers::environment::get_matched_environment;
 use crate::controllers::project::get_project;
 use crate::controllers::sandbox_exec::{self, ExecOutcome};
+use crate::controllers::sandbox_sync::{self, Location, Snapshot, SyncPlan};
+use crate::controllers::sandboxfile::Recipe;
+use crate::controllers::upload::deploy_change_filter;
 use crate::controllers::variables::Variable;
 use crate::gql::{mutations, queries};
 use crate::util::progress::{create_shimmer_spinner, fail_spinner};
@@ -21,11 +25,12 @@ use crate::util::prompt::{
     prompt_confirm_with_default_with_cancel, prompt_options, prompt_options_skippable,
 };
 use crate::util::shell::shell_join;
+use crate::util::watcher::FileWatcher;
 
 /// Manage ephemeral sandboxes
 #[derive(Parser)]
 #[clap(
-    after_help = "Examples:\n\n  railway sandbox create            # create + remember it as active\n  railway sandbox create --variable FOO=bar,DB_URL=postgres.DATABASE_URL\n  railway sandbox create --env-file .env\n  railway sandbox template build --name dev -c 'npm i -g pnpm' --wait\n  railway sandbox create --template dev   # boot from the pre-built snapshot\n  railway sandbox checkpoint create my-setup       # capture the active sandbox's disk\n  railway sandbox create --checkpoint my-setup     # boot a new sandbox from it\n  railway sandbox checkpoint list   # list named checkpoints in the environment\n  railway sandbox list              # list sandboxes in the environment\n  railway sandbox ssh               # connect to the active (last) sandbox\n  railway sandbox ssh --id <id>     # connect to a specific sandbox\n  railway sandbox exec --id <id> -- ls -la\n  railway sandbox exec --detach -- npm run build   # leave it running, prints a session name\n  railway sandbox exec --session <name>            # reattach to a detached/disconnected command\n  railway sandbox forward 3000      # localhost:3000 → port 3000 in the active sandbox\n  railway sandbox forward 8080:3000 # localhost:8080 → port 3000 (explicit local port)\n  railway sandbox forward 3000 5432 # several ports over one connection\n  railway sandbox fork              # fork the active sandbox; the fork becomes active\n  railway sandbox fork <id> --variable FOO=bar\n  railway sandbox destroy --id <id>\n\nNote: requires the PROJECT_SANDBOXES feature to be enabled."
+    after_help = "Examples:\n\n  railway sandbox create            # create + remember it as active\n  railway sandbox create --variable FOO=bar,DB_URL=postgres.DATABASE_URL\n  railway sandbox create --env-file .env\n  railway sandbox template build --name dev -c 'npm i -g pnpm' --wait\n  railway sandbox template build --name dev -f Sandboxfile --wait\n  railway sandbox create --template dev   # boot from the pre-built snapshot\n  railway sandbox checkpoint create my-setup       # capture the active sandbox's disk\n  railway sandbox create --checkpoint my-setup     # boot a new sandbox from it\n  railway sandbox checkpoint list   # list named checkpoints in the environment\n  railway sandbox list              # list sandboxes in the environment\n  railway sandbox ssh               # connect to the active (last) sandbox\n  railway sandbox ssh --id <id>     # connect to a specific sandbox\n  railway sandbox exec --id <id> -- ls -la\n  railway sandbox exec --detach -- npm run build   # leave it running, prints a session name\n  railway sandbox exec --session <name>            # reattach to a detached/disconnected command\n  railway sandbox create --checkpoint ci-base --label ci   # repeat for each shard\n  railway sandbox exec --label ci -- 'npm test -- --shard=$((SANDBOX_INDEX+1))/$SANDBOX_COUNT'\n  railway sandbox exec --ids a,b,c -- uptime      # run in several sandboxes at once\n  railway sandbox forward 3000      # localhost:3000 → port 3000 in the active sandbox\n  railway sandbox forward 8080:3000 # localhost:8080 → port 3000 (explicit local port)\n  railway sandbox forward 3000 5432 # several ports over one connection\n  railway sandbox sync ./ :/workspace          # push the working tree (honors .gitignore)\n  railway sandbox sync ./ :/workspace --watch  # keep pushing changes until Ctrl+C\n  railway sandbox cp ./seed.sql :/tmp/seed.sql\n  railway sandbox cp :/workspace/out.log ./\n  railway sandbox fork              # fork the active sandbox; the fork becomes active\n  railway sandbox fork <id> --variable FOO=bar\n  railway sandbox destroy --id <id>\n\nNote: requires the PROJECT_SANDBOXES feature to be enabled."
 )]
 pub struct Args {
     #[clap(subcommand)]
@@ -71,6 +76,14 @@ enum Commands {
     #[clap(visible_alias = "port-forward", visible_alias = "fwd")]
     Forward(ForwardArgs),
 
+    /// Push a local directory into a sandbox, optionally re-pushing changes
+    /// as they happen (defaults to the active sandbox)
+    Sync(SyncArgs),
+
+    /// Copy a single file into or out of a sandbox (defaults to the active
+    /// sandbox)
+    Cp(CpArgs),
+
     /// Destroy a sandbox (defaults to the active sandbox)
     #[clap(visible_alias = "rm", visible_alias = "delete")]
     Destroy(DestroyArgs),
@@ -110,6 +123,11 @@ struct CreateArgs {
     #[clap(long)]
     private_network: bool,
 
+    /// Label the sandbox locally (repeatable), to run commands across every
+    /// sandbox with that label via `railway sandbox exec --label`
+    #[clap(long = "label", value_name = "LABEL")]
+    labels: Vec<String>,
+
     /// Output the created sandbox as JSON
     #[clap(long)]
     json: bool,
@@ -123,8 +141,8 @@ struct TemplateArgs {
 
 #[derive(Parser)]
 enum TemplateCommands {
-    /// Build a template from shell instructions. Templates are
-    /// content-addressed and cached server-side, so re-running the same
+    /// Build a template from shell instructions or a Sandboxfile. Templates
+    /// are content-addressed and cached server-side, so re-running the same
     /// build is an instant cache hit
     #[clap(visible_alias = "create", visible_alias = "new")]
     Build(TemplateBuildArgs),
@@ -145,16 +163,22 @@ struct TemplateBuildArgs {
         short = 'c',
         long = "command",
         value_name = "SHELL_COMMAND",
-        required = true
+        required_unless_present = "file"
     )]
     commands: Vec<String>,
 
+    /// Build from a Sandboxfile (FROM, ENV, RUN, COPY and IDLE_TIMEOUT lines)
+    /// instead of `-c` flags. COPY sources resolve relative to the file
+    #[clap(short = 'f', long, value_name = "PATH", conflicts_with = "commands")]
+    file: Option<std::path::PathBuf>,
+
     /// Local name for the template, usable with `railway sandbox create
     /// --template <name>`
     #[clap(long)]
     name: Option<String>,
 
-    /// Base image digest to build on (defaults to the standard sandbox image)
+    /// Base image digest to build on (defaults to the Sandboxfile's FROM, or
+    /// the standard sandbox image)
     #[clap(long, value_name = "DIGEST")]
     base_image_digest: Option<String>,
 
@@ -290,6 +314,11 @@ struct ForkArgs {
     #[clap(long)]
     private_network: bool,
 
+    /// Label the sandbox locally (repeatable), to run commands across every
+    /// sandbox with that label via `railway sandbox exec --label`
+    #[clap(long = "label", value_name = "LABEL")]
+    labels: Vec<String>,
+
     /// Output the created sandbox as JSON
     #[clap(long)]
     json: bool,
@@ -346,6 +375,23 @@ struct ExecArgs {
     #[clap(long = "id", value_name = "ID")]
     id: Option<String>,
 
+    /// Run in every running sandbox in the environment at once
+    #[clap(long, conflicts_with_all = ["id", "label", "ids", "session", "detach"])]
+    all: bool,
+
+    /// Run in every sandbox with this label at once (see `create --label`)
+    #[clap(long, value_name = "LABEL", conflicts_with_all = ["id", "ids", "session", "detach"])]
+    label: Option<String>,
+
+    /// Run in each of these sandboxes at once
+    #[clap(
+        long,
+        value_name = "ID,...",
+        value_delimiter = ',',
+        conflicts_with_all = ["id", "session", "detach"]
+    )]
+    ids: Vec<String>,
+
     /// Client-side deadline in seconds; on expiry the command is terminated
     /// and the CLI exits 124
     #[clap(long)]
@@ -367,7 +413,8 @@ struct ExecArgs {
 
     /// Command to run (everything after `--`; optional with --session).
     /// A single argument runs as a shell command; multiple arguments run
-    /// as argv with each argument quoted intact
+    /// as argv with each argument quoted intact. With --all, --label or
+    /// --ids each run sees SANDBOX_INDEX (0-based) and SANDBOX_COUNT
     #[clap(trailing_var_arg = true)]
     command: Vec<String>,
 }
@@ -395,6 +442,50 @@ struct ForwardArgs {
     strict: bool,
 }
 
+/// `railway sandbox sync ./ :/workspace`. The sandbox side is written with a
+/// leading `:`, like `scp`.
+#[derive(Parser)]
+struct SyncArgs {
+    /// Local directory to push
+    #[clap(value_name = "LOCAL_DIR")]
+    source: Location,
+
+    /// Directory in the sandbox, with a leading `:` (created if missing)
+    #[clap(value_name = ":SANDBOX_DIR")]
+    dest: Location,
+
+    /// Sandbox ID to sync into (defaults to the active sandbox)
+    #[clap(long = "id", value_name = "ID")]
+    id: Option<String>,
+
+    /// After the initial push, keep watching and push changes (and deletions)
+    /// until Ctrl+C
+    #[clap(long)]
+    watch: bool,
+
+    /// Don't skip files ignored by .gitignore (.railwayignore still applies)
+    #[clap(long)]
+    no_gitignore: bool,
+}
+
+/// `railway sandbox cp ./a.txt :/workspace/a.txt` uploads;
+/// `railway sandbox cp :/workspace/a.txt ./` downloads.
+#[derive(Parser)]
+struct CpArgs {
+    /// File to copy: a local path, or a sandbox path with a leading `:`
+    #[clap(value_name = "SOURCE")]
+    source: Location,
+
+    /// Where to copy it. A destination ending in `/` (or an existing local
+    /// directory) keeps the source file name
+    #[clap(value_name = "DEST")]
+    dest: Location,
+
+    /// Sandbox ID to copy to or from (defaults to the active sandbox)
+    #[clap(long = "id", value_name = "ID")]
+    id: Option<String>,
+}
+
 /// Destroy has no trailing command, so a positional id is unambiguous; `--id`
 /// is also accepted. Omitted → the active sandbox.
 #[derive(Parser)]
@@ -438,6 +529,8 @@ pub async fn command(args: Args) -> Result<()> {
         Commands::Ssh(sub) => ssh(&mut configs, &client, project, environment, sub).await,
         Commands::Exec(sub) => exec(&mut configs, &client, project, environment, sub).await,
         Commands::Forward(sub) => forward(&mut configs, &client, project, environment, sub).await,
+        Commands::Sync(sub) => sync(&mut configs, &client, project, environment, sub).await,
+        Commands::Cp(sub) => cp(&mut configs, &client, project, environment, sub).await,
         Commands::Destroy(sub) => destroy(&mut configs, &client, project, environment, sub).await,
     }
 }
@@ -841,6 +934,7 @@ pub(crate) async fn create_and_store(
             environment_id,
             project_id: Some(project_id),
             created_at: Some(sandbox.created_at.to_rfc3339()),
+            labels: None,
         },
         true,
     );
@@ -875,6 +969,8 @@ async fn create(
     // content-addressed, so sandboxCreate needs the full recipe (not just the
     // id), resolved from the local store. Both ride the same input — the
     // server treats `name` and `instructions` as mutually exclusive.
+    let mut template_variables = None;
+    let mut template_idle_timeout = None;
     let template = if let Some(name) = &args.checkpoint {
         Some(mutations::sandbox_create::SandboxTemplateInput {
             instructions: None,
@@ -890,30 +986,45 @@ async fn create(
                     "Unknown template `{handle}` for this environment. Build it first:\n  railway sandbox template build --name {handle} -c '<command>' --wait"
                 )
             })?;
+        template_variables = stored.variables.clone();
+        template_idle_timeout = stored.idle_timeout_minutes;
         Some(mutations::sandbox_create::SandboxTemplateInput {
             instructions: Some(stored.instructions),
             base_image_digest: stored.base_image_digest,
             name: None,
-            variables: None,
+            variables: stored.variables,
         })
     } else {
         None
     };
 
+    // A Sandboxfile's ENV also applies inside the sandbox, under any
+    // `--variable`/`--env-file` values
+    let variables = match (
+        template_variables,
+        variables_to_input(&args.env_files, &args.variables)?,
+    ) {
+        (Some(mut base), Some(overrides)) => {
+            base.extend(overrides);
+            Some(base)
+        }
+        (base, overrides) => overrides.or(base),
+    };
+
     let input = mutations::sandbox_create::SandboxCreateInput {
         environment_id: environment_id.clone(),
-        idle_timeout_minutes: args.idle_timeout_minutes,
+        idle_timeout_minutes: args.idle_timeout_minutes.or(template_idle_timeout),
         template,
         source_sandbox_id: None,
         network_isolation: args
             .private_network
             .then_some(mutations::sandbox_create::SandboxNetworkIsolation::PRIVATE),
-        variables: variables_to_input(&args.env_files, &args.variables)?,
+        variables,
         // Server-side default (SANDBOX_DEFAULT_REGION); the CLI exposes no
         // region flag yet.
         region: None,
     };
-    create_and_store(
+    let id = create_and_store(
         configs,
         client,
         project_id,
@@ -926,8 +1037,12 @@ async fn create(
         },
         false,
     )
-    .await
-    .map(|_| ())
+    .await?;
+    if !args.labels.is_empty() {
+        configs.set_sandbox_labels(&id, args.labels);
+        configs.write()?;
+    }
+    Ok(())
 }
 
 async fn template(
@@ -957,6 +1072,28 @@ async fn template_build(
     environment: Option<String>,
     args: TemplateBuildArgs,
 ) -> Result<()> {
+    let recipe = match &args.file {
+        Some(path) => {
+            let recipe = Recipe::from_file(path)?;
+            let root = path.parent().unwrap_or(std::path::Path::new("."));
+            let instructions = recipe.instructions(root)?;
+            TemplateRecipe {
+                labels: instructions.iter().map(|i| i.label.clone()).collect(),
+                instructions: instructions.into_iter().map(|i| i.command).collect(),
+                base_image_digest: args.base_image_digest.or(recipe.base_image_digest),
+                variables: (!recipe.env.is_empty()).then_some(recipe.env),
+                idle_timeout_minutes: recipe.idle_timeout_minutes,
+            }
+        }
+        None => TemplateRecipe {
+            labels: args.commands.iter().map(|c| format!("RUN {c}")).collect(),
+            instructions: args.commands,
+            base_image_digest: args.base_image_digest,
+            variables: None,
+            idle_timeout_minutes: None,
+        },
+    };
+
     let (_, environment_id) =
         resolve_project_and_env(configs, client, project, environment).await?;
 
@@ -966,10 +1103,10 @@ async fn template_build(
         mutations::sandbox_template_build::Variables {
             environment_id: environment_id.clone(),
             input: mutations::sandbox_template_build::SandboxTemplateInput {
-                instructions: Some(args.commands.clone()),
-                base_image_digest: args.base_image_digest.clone(),
+                instructions: Some(recipe.instructions.clone()),
+                base_image_digest: recipe.base_image_digest.clone(),
                 name: None,
-                variables: None,
+                variables: recipe.variables.clone(),
             },
         },
     )
@@ -982,8 +1119,10 @@ async fn template_build(
         id: built.id.clone(),
         name: args.name.clone(),
         environment_id: environment_id.clone(),
-        instructions: args.commands,
-        base_image_digest: args.base_image_digest,
+        instructions: recipe.instructions.clone(),
+        base_image_digest: recipe.base_image_digest.clone(),
+        variables: recipe.variables.clone(),
+        idle_timeout_minutes: recipe.idle_timeout_minutes,
         created_at: Some(chrono::Utc::now().to_rfc3339()),
     });
     configs.write()?;
@@ -992,6 +1131,19 @@ async fn template_build(
         built.status,
         mutations::sandbox_template_build::SandboxTemplateBuildStatus::READY
     );
+    // Templates are hashed as a whole: either the server already has this
+    // exact recipe and every step is cached, or it builds all of them.
+    if !args.json {
+        for label in &recipe.labels {
+            let (mark, state) = if already_ready {
+                ("✓", "cached")
+            } else {
+                ("•", "build ")
+            };
+            println!("  {mark} {state}  {}", truncate_label(label));
+        }
+        println!();
+    }
     let status = if args.wait && !already_ready {
         wait_for_template(client, configs, &environment_id, &built.id).await?
     } else {
@@ -1000,11 +1152,17 @@ async fn template_build(
 
     let handle = args.name.unwrap_or_else(|| built.id.clone());
     if args.json {
+        let steps: Vec<_> = recipe
+            .labels
+            .iter()
+            .map(|label| serde_json::json!({ "step": label, "cached": already_ready }))
+            .collect();
         let out = serde_json::json!({
             "id": built.id,
             "status": status,
             "environmentId": environment_id,
             "name": handle,
+            "steps": steps,
         });
         println!("{}", serde_json::to_string_pretty(&out)?);
         return Ok(());
@@ -1024,6 +1182,25 @@ async fn template_build(
     Ok(())
 }
 
+/// A template build request: instructions plus how to show each one.
+struct TemplateRecipe {
+    labels: Vec<String>,
+    instructions: Vec<String>,
+    base_image_digest: Option<String>,
+    variables: Option<BTreeMap<String, String>>,
+    idle_timeout_minutes: Option<i64>,
+}
+
+/// Keeps step lines to one terminal row; COPY steps and long RUN chains
+/// would otherwise wrap.
+fn truncate_label(label: &str) -> String {
+    const MAX: usize = 100;
+    if label.chars().count() <= MAX {
+        return label.to_string();
+    }
+    format!("{}…", label.chars().take(MAX - 1).collect::<String>())
+}
+
 async fn template_status(
     configs: &mut Configs,
     client: &reqwest::Client,
@@ -1387,7 +1564,7 @@ async fn fork(
         // region flag yet.
         region: None,
     };
-    create_and_store(
+    let id = create_and_store(
         configs,
         client,
         project_id,
@@ -1400,8 +1577,12 @@ async fn fork(
         },
         true,
     )
-    .await
-    .map(|_| ())
+    .await?;
+    if !args.labels.is_empty() {
+        configs.set_sandbox_labels(&id, args.labels);
+        configs.write()?;
+    }
+    Ok(())
 }
 
 async fn list(
@@ -1414,17 +1595,7 @@ async fn list(
     let (project_id, environment_id) =
         resolve_project_and_env(configs, client, project, environment).await?;
 
-    let res = post_graphql::<queries::Sandboxes, _>(
-        client,
-        configs.get_backboard(),
-        queries::sandboxes::Variables {
-            environment_id: environment_id.clone(),
-            first: Some(100),
-            after: None,
-        },
-    )
-    .await?;
-    let mut nodes: Vec<_> = res.sandboxes.edges.into_iter().map(|e| e.node).collect();
+    let mut nodes = fetch_sandboxes(client, configs, &environment_id).await?;
 
     // Tombstones quickly outnumber live sandboxes; hide them unless --all.
     let hidden = if args.all {
@@ -1444,6 +1615,7 @@ async fn list(
                 environment_id: environment_id.clone(),
                 project_id: Some(project_id.clone()),
                 created_at: Some(node.created_at.to_rfc3339()),
+                labels: None,
             },
             false,
         );
@@ -1468,7 +1640,7 @@ async fn list(
 
     let active = configs.get_active_sandbox().map(|s| s.id);
     println!(
-        "{:<38}  {:<10}  {:<10}  {:<16}",
+        "{:<38}  {:<10}  {:<10}  {:<16}  LABELS",
         "ID", "STATUS", "REGION", "CREATED"
     );
     for node in nodes {
@@ -1477,8 +1649,13 @@ async fn list(
         } else {
             " "
         };
+        let labels = configs
+            .get_sandbox(&node.id)
+            .and_then(|s| s.labels)
+            .unwrap_or_default()
+            .join(",");
         println!(
-            "{marker} {:<38}  {:<10}  {:<10}  {:<16}",
+            "{marker} {:<38}  {:<10}  {:<10}  {:<16}  {labels}",
             node.id,
             format!("{:?}", node.status),
             node.region,
@@ -1500,6 +1677,10 @@ async fn exec(
 ) -> Result<()> {
     use colored::Colorize;
 
+    if args.all || args.label.is_some() || !args.ids.is_empty() {
+        return exec_many(configs, client, project, environment, args).await;
+    }
+
     // clap can't express "required unless --session" for a trailing vararg.
     if args.command.is_empty() && args.session.is_none() {
         bail!("a command is required (or pass --session <name> to reattach)");
@@ -1573,7 +1754,9 @@ async fn exec(
             .timeout
             .map(|secs| Duration::from_secs(secs.max(0) as u64)),
         detach: args.detach,
-        stdin_is_tty: std::io::stdin().is_terminal(),
+        // Piped stdin is forwarded; a TTY is never read.
+        forward_stdin: !std::io::stdin().is_terminal(),
+        output_prefix: None,
     };
 
     let outcome = tel::track_for(
@@ -1635,6 +1818,280 @@ async fn exec(
     }
 }
 
+/// Every sandbox in the environment, following pagination.
+async fn fetch_sandboxes(
+    client: &reqwest::Client,
+    configs: &Configs,
+    environment_id: &str,
+) -> Result<Vec<queries::sandboxes::SandboxesSandboxesEdgesNode>> {
+    let mut nodes = Vec::new();
+    let mut after = None;
+    loop {
+        let page = post_graphql::<queries::Sandboxes, _>(
+            client,
+            configs.get_backboard(),
+            queries::sandboxes::Variables {
+                environment_id: environment_id.to_string(),
+                first: Some(100),
+                after: after.take(),
+            },
+        )
+        .await?
+        .sandboxes;
+        nodes.extend(page.edges.into_iter().map(|e| e.node));
+        match page.page_info.end_cursor {
+            Some(cursor) if page.page_info.has_next_page => after = Some(cursor),
+            _ => return Ok(nodes),
+        }
+    }
+}
+
+/// Sandboxes a fan-out exec runs in, as `(sandbox_id, environment_id)` in
+/// `SANDBOX_INDEX` order.
+async fn resolve_fan_out_targets(
+    configs: &mut Configs,
+    client: &reqwest::Client,
+    args: &ExecArgs,
+    project: Option<String>,
+    environment: Option<String>,
+) -> Result<Vec<(String, String)>> {
+    if args.all {
+        let (_, environment_id) =
+            resolve_project_and_env(configs, client, project, environment).await?;
+        let mut nodes: Vec<_> = fetch_sandboxes(client, configs, &environment_id)
+            .await?
+            .into_iter()
+            .filter(|n| matches!(n.status, queries::sandboxes::SandboxStatus::RUNNING))
+            .collect();
+        if nodes.is_empty() {
+            bail!("No running sandboxes in this environment.");
+        }
+        nodes.sort_by_key(|n| n.created_at);
+        return Ok(nodes
+            .into_iter()
+            .map(|n| (n.id, environment_id.clone()))
+            .collect());
+    }
+
+    if let Some(label) = &args.label {
+        // Labels are local, so only narrow by environment when asked to.
+        let environment_id = if project.is_some() || environment.is_some() {
+            Some(
+                resolve_project_and_env(configs, client, project, environment)
+                    .await?
+                    .1,
+            )
+        } else {
+            None
+        };
+        let labelled: Vec<_> = configs
+            .sandboxes_with_label(label)
+            .into_iter()
+            .filter(|s| {
+                environment_id
+                    .as_ref()
+                    .is_none_or(|e| *e == s.environment_id)
+            })
+            .map(|s| (s.id, s.environment_id))
+            .collect();
+        if labelled.is_empty() {
+            bail!(
+                "No sandboxes are labelled {label}. Label new ones with `railway sandbox create --label {label}`."
+            );
+        }
+
+        // The local record outlives the sandboxes; only target running ones.
+        let mut running = HashSet::new();
+        for environment_id in labelled.iter().map(|(_, e)| e).collect::<BTreeSet<_>>() {
+            running.extend(
+                fetch_sandboxes(client, configs, environment_id)
+                    .await?
+                    .into_iter()
+                    .filter(|n| matches!(n.status, queries::sandboxes::SandboxStatus::RUNNING))
+                    .map(|n| n.id),
+            );
+        }
+        let (targets, stopped): (Vec<_>, Vec<_>) = labelled
+            .into_iter()
+            .partition(|(id, _)| running.contains(id));
+        if targets.is_empty() {
+            bail!("None of the sandboxes labelled {label} are running.");
+        }
+        if !stopped.is_empty() {
+            eprintln!(
+                "Skipping {} sandbox{} labelled {label} that {} not running",
+                stopped.len(),
+                if stopped.len() == 1 { "" } else { "es" },
+                if stopped.len() == 1 { "is" } else { "are" }
+            );
+        }
+        return Ok(targets);
+    }
+
+    let mut targets: Vec<(String, String)> = Vec::with_capacity(args.ids.len());
+    for id in &args.ids {
+        if targets.iter().any(|(existing, _)| existing == id) {
+            continue;
+        }
+        targets.push(
+            resolve_target(
+                configs,
+                client,
+                Some(id.clone()),
+                project.clone(),
+                environment.clone(),
+            )
+            .await?,
+        );
+    }
+    Ok(targets)
+}
+
+/// `exec --all | --label | --ids`: run one command in many sandboxes at once,
+/// prefixing each line with the sandbox it came from, then summarize.
+async fn exec_many(
+    configs: &mut Configs,
+    client: &reqwest::Client,
+    project: Option<String>,
+    environment: Option<String>,
+    args: ExecArgs,
+) -> Result<()> {
+    use colored::Colorize;
+
+    if args.command.is_empty() {
+        bail!("a command is required");
+    }
+
+    let targets = tel::track_for(
+        "sandbox",
+        "exec_many_resolve_targets",
+        resolve_fan_out_targets(configs, client, &args, project, environment).await,
+    )
+    .await?;
+
+    let command = match args.command.as_slice() {
+        [cmd] => cmd.clone(),
+        argv => shell_join(argv),
+    };
+    let count = targets.len();
+    let backboard = configs.get_backboard();
+    let timeout = args
+        .timeout
+        .map(|secs| Duration::from_secs(secs.max(0) as u64));
+
+    eprintln!(
+        "{}",
+        format!("Running in {count} sandboxes: {command}").dimmed()
+    );
+
+    let runs = targets
+        .iter()
+        .enumerate()
+        .map(|(index, (sandbox_id, environment_id))| {
+            let options = sandbox_exec::ExecOptions {
+                command: Some(shard_command(&command, index, count)),
+                session: None,
+                resume_from_last_read: false,
+                timeout,
+                detach: false,
+                // Stdin can't be split across sandboxes; every run gets EOF.
+                forward_stdin: false,
+                output_prefix: Some(format!(
+                    "{} ",
+                    format!("[{index} {}]", short_id(sandbox_id)).cyan()
+                )),
+            };
+            let backboard = backboard.clone();
+            async move {
+                let started = std::time::Instant::now();
+                let outcome =
+                    exec_one(client, backboard, environment_id, sandbox_id, options).await;
+                (outcome, started.elapsed())
+            }
+        });
+    let results = futures::future::join_all(runs).await;
+
+    eprintln!();
+    eprintln!(
+        "{:<5}  {:<38}  {:<14}  DURATION",
+        "INDEX", "SANDBOX", "EXIT"
+    );
+    let mut failed = Vec::new();
+    for (index, ((sandbox_id, _), (outcome, elapsed))) in targets.iter().zip(results).enumerate() {
+        let (exit, code) = match outcome {
+            Ok(ExecOutcome::Exited { code, .. }) => (code.to_string(), code),
+            Ok(ExecOutcome::TimedOut { .. }) => {
+                ("timed out".to_string(), sandbox_exec::TIMEOUT_EXIT_CODE)
+            }
+            Ok(ExecOutcome::Detached { .. } | ExecOutcome::Disconnected { .. }) => {
+                ("disconnected".to_string(), 1)
+            }
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    format!("[{index} {}] {e}", short_id(sandbox_id)).red()
+                );
+                ("error".to_string(), 1)
+            }
+        };
+        let line = format!(
+            "{index:<5}  {sandbox_id:<38}  {exit:<14}  {}",
+            format_duration(elapsed.as_millis() as i64)
+        );
+        if code == 0 {
+            eprintln!("{line}");
+        } else {
+            eprintln!("{}", line.red());
+            failed.push(code);
+        }
+    }
+
+    match failed.first() {
+        None => Ok(()),
+        Some(&code) => {
+            tel::report_failure_for(
+                "sandbox",
+                "exec_many_exit_nonzero",
+                &format!("{} of {count} failed", failed.len()),
+            )
+            .await;
+            std::process::exit(code);
+        }
+    }
+}
+
+/// Authorize, connect and run one fan-out command, keeping that sandbox
+/// alive while it runs.
+async fn exec_one(
+    client: &reqwest::Client,
+    backboard: String,
+    environment_id: &str,
+    sandbox_id: &str,
+    options: sandbox_exec::ExecOptions,
+) -> Result<ExecOutcome> {
+    let jwt = mint_shell_token(client, backboard.clone(), environment_id, sandbox_id).await?;
+    let ws = sandbox_exec::connect(&jwt).await?;
+    let heartbeat = spawn_heartbeat(
+        client.clone(),
+        backboard,
+        environment_id.to_string(),
+        sandbox_id.to_string(),
+    );
+    let outcome = sandbox_exec::run(ws, options).await;
+    heartbeat.abort();
+    outcome
+}
+
+/// Prefix a fan-out command with its shard, so test runners can pick their
+/// slice from `SANDBOX_INDEX` (0-based) and `SANDBOX_COUNT`.
+fn shard_command(command: &str, index: usize, count: usize) -> String {
+    format!("export SANDBOX_INDEX={index} SANDBOX_COUNT={count}; {command}")
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
 fn reattach_hint(sandbox_id: &str, session_name: &str) -> String {
     format!("Reattach with: railway sandbox exec --id {sandbox_id} --session {session_name}")
 }
@@ -1663,6 +2120,255 @@ async fn mint_shell_token(
     Ok(res.generate_shell_token)
 }
 
+/// Where `sandbox sync` pushes to, with what it needs to authorize each push.
+struct SyncTarget {
+    client: reqwest::Client,
+    backboard: String,
+    environment_id: String,
+    sandbox_id: String,
+    root: std::path::PathBuf,
+    dest: String,
+    no_gitignore: bool,
+}
+
+impl SyncTarget {
+    /// Apply `plan` in one round trip: unpack the changed files, then delete
+    /// the removed ones.
+    async fn push(&self, plan: &SyncPlan) -> Result<()> {
+        let mut commands = Vec::new();
+        let mut stdin = Vec::new();
+        if !plan.upload.is_empty() {
+            let root = self.root.clone();
+            let upload = plan.upload.clone();
+            stdin = tokio::task::spawn_blocking(move || sandbox_sync::archive(&root, &upload))
+                .await??;
+            commands.push(sandbox_sync::extract_command(&self.dest));
+        }
+        if !plan.remove.is_empty() {
+            commands.push(sandbox_sync::remove_command(&self.dest, &plan.remove));
+        }
+        let jwt = mint_shell_token(
+            &self.client,
+            self.backboard.clone(),
+            &self.environment_id,
+            &self.sandbox_id,
+        )
+        .await?;
+        sandbox_sync::run(&jwt, &commands.join(" && "), &stdin).await?;
+        Ok(())
+    }
+
+    /// Snapshot the tree and push whatever changed since `synced`, which is
+    /// only advanced once the push lands so a failed one is retried.
+    async fn sync_changes(&self, synced: &mut Snapshot) -> Result<Option<SyncPlan>> {
+        let (root, no_gitignore) = (self.root.clone(), self.no_gitignore);
+        let current =
+            tokio::task::spawn_blocking(move || sandbox_sync::snapshot(&root, no_gitignore))
+                .await??;
+        let plan = SyncPlan::between(synced, &current);
+        if plan.is_empty() {
+            return Ok(None);
+        }
+        self.push(&plan).await?;
+        *synced = current;
+        Ok(Some(plan))
+    }
+}
+
+async fn sync(
+    configs: &mut Configs,
+    client: &reqwest::Client,
+    project: Option<String>,
+    environment: Option<String>,
+    args: SyncArgs,
+) -> Result<()> {
+    use colored::Colorize;
+
+    let (Location::Local(root), Location::Sandbox(dest)) = (args.source, args.dest) else {
+        bail!(
+            "sync pushes a local directory into a sandbox, e.g. `railway sandbox sync ./ :/workspace`. Use `railway sandbox cp` to copy files out."
+        );
+    };
+    if !root.is_dir() {
+        bail!("{} is not a directory", root.display());
+    }
+    let root = root.canonicalize()?;
+
+    let (sandbox_id, environment_id) = tel::track_for(
+        "sandbox",
+        "sync_resolve_target",
+        resolve_target(configs, client, args.id.clone(), project, environment).await,
+    )
+    .await?;
+
+    configs.set_active_sandbox(&sandbox_id);
+    configs.write()?;
+
+    let target = SyncTarget {
+        client: client.clone(),
+        backboard: configs.get_backboard(),
+        environment_id,
+        sandbox_id,
+        root,
+        dest,
+        no_gitignore: args.no_gitignore,
+    };
+
+    let mut spinner = create_shimmer_spinner("Syncing");
+    let mut synced = Snapshot::new();
+    match tel::track_for(
+        "sandbox",
+        "sync_push",
+        target.sync_changes(&mut synced).await,
+    )
+    .await
+    {
+        Ok(_) => spinner.finish_and_clear(),
+        Err(e) => {
+            fail_spinner(&mut spinner, "Failed to sync".to_string());
+            return Err(e);
+        }
+    }
+    println!(
+        "Synced {} files to {} in sandbox {}",
+        synced.len(),
+        target.dest.cyan(),
+        target.sandbox_id.cyan()
+    );
+
+    if !args.watch {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Watching {} for changes (Ctrl+C to stop)",
+            target.root.display()
+        )
+        .dimmed()
+    );
+    // Keep the sandbox alive against the idle reaper while we watch.
+    let heartbeat = spawn_heartbeat(
+        target.client.clone(),
+        target.backboard.clone(),
+        target.environment_id.clone(),
+        target.sandbox_id.clone(),
+    );
+
+    let watcher = FileWatcher::directory(
+        target.root.clone(),
+        deploy_change_filter(&target.root, target.no_gitignore),
+    );
+    let target = std::sync::Arc::new(target);
+    let synced = std::sync::Arc::new(tokio::sync::Mutex::new(synced));
+    let result = watcher
+        .watch(move |_, _| {
+            let (target, synced) = (target.clone(), synced.clone());
+            async move {
+                let mut synced = synced.lock().await;
+                match target.sync_changes(&mut synced).await {
+                    Ok(Some(plan)) => println!(
+                        "Synced {} changed, {} removed",
+                        plan.upload.len(),
+                        plan.remove.len()
+                    ),
+                    Ok(None) => {}
+                    Err(e) => eprintln!("{}: {e}", "Sync failed".red()),
+                }
+                Ok(())
+            }
+        })
+        .await;
+    heartbeat.abort();
+    result
+}
+
+async fn cp(
+    configs: &mut Configs,
+    client: &reqwest::Client,
+    project: Option<String>,
+    environment: Option<String>,
+    args: CpArgs,
+) -> Result<()> {
+    if matches!(
+        (&args.source, &args.dest),
+        (Location::Local(_), Location::Local(_)) | (Location::Sandbox(_), Location::Sandbox(_))
+    ) {
+        bail!(
+            "Exactly one side of cp must be a sandbox path, written with a leading ':' (e.g. :/workspace/app.py)"
+        );
+    }
+    if let Location::Local(source) = &args.source {
+        if !source.is_file() {
+            bail!(
+                "{} is not a file. Use `railway sandbox sync` for directories.",
+                source.display()
+            );
+        }
+    }
+
+    let (sandbox_id, environment_id) = tel::track_for(
+        "sandbox",
+        "cp_resolve_target",
+        resolve_target(configs, client, args.id.clone(), project, environment).await,
+    )
+    .await?;
+
+    configs.set_active_sandbox(&sandbox_id);
+    configs.write()?;
+
+    let jwt = tel::track_for(
+        "sandbox",
+        "cp_mint_token",
+        mint_shell_token(
+            client,
+            configs.get_backboard(),
+            &environment_id,
+            &sandbox_id,
+        )
+        .await,
+    )
+    .await?;
+
+    match (args.source, args.dest) {
+        (Location::Local(source), Location::Sandbox(dest)) => {
+            let dest = if dest.ends_with('/') {
+                let name = source
+                    .file_name()
+                    .ok_or_else(|| anyhow!("{} has no file name", source.display()))?;
+                format!("{dest}{}", name.to_string_lossy())
+            } else {
+                dest
+            };
+            let (dir, name) = sandbox_sync::split_remote(&dest)?;
+            let archive = sandbox_sync::archive_file(&source, &name)?;
+            tel::track_for(
+                "sandbox",
+                "cp_upload",
+                sandbox_sync::run(&jwt, &sandbox_sync::extract_command(&dir), &archive).await,
+            )
+            .await?;
+            println!("Copied {} to :{dest}", source.display());
+        }
+        (Location::Sandbox(source), Location::Local(dest)) => {
+            let (_, name) = sandbox_sync::split_remote(&source)?;
+            let captured = tel::track_for(
+                "sandbox",
+                "cp_download",
+                sandbox_sync::run(&jwt, &sandbox_sync::read_command(&source), &[]).await,
+            )
+            .await?;
+            let dest = if dest.is_dir() { dest.join(name) } else { dest };
+            std::fs::write(&dest, &captured.stdout)
+                .map_err(|e| anyhow!("Failed to write {}: {e}", dest.display()))?;
+            println!("Copied :{source} to {}", dest.display());
+        }
+        _ => unreachable!("checked above"),
+    }
+    Ok(())
+}
+
 async fn destroy(
     configs: &mut Configs,
     client: &reqwest::Client,
@@ -2252,6 +2958,49 @@ mod tests {
         assert_eq!(exec.command, vec!["sleep", "300"]);
     }
 
+    #[test]
+    fn sync_and_cp_take_a_sandbox_side() {
+        let args = parse_exec(&["sync", "./", ":/workspace", "--watch"]).unwrap();
+        let Commands::Sync(sync) = args.command else {
+            panic!("expected sync subcommand");
+        };
+        assert_eq!(sync.dest, Location::Sandbox("/workspace".into()));
+        assert!(sync.watch);
+        assert!(parse_exec(&["cp", ":/workspace/out.log", "."]).is_ok());
+        assert!(parse_exec(&["cp", ":", "."]).is_err());
+    }
+
+    #[test]
+    fn exec_fan_out_selectors_parse() {
+        let args = parse_exec(&["exec", "--ids", "a,b,c", "--", "uptime"]).unwrap();
+        let Commands::Exec(exec) = args.command else {
+            panic!("expected exec subcommand");
+        };
+        assert_eq!(exec.ids, vec!["a", "b", "c"]);
+        assert!(parse_exec(&["exec", "--label", "ci", "--", "ls"]).is_ok());
+        assert!(parse_exec(&["exec", "--all", "--label", "ci", "--", "ls"]).is_err());
+        assert!(parse_exec(&["exec", "--all", "--detach", "--", "ls"]).is_err());
+        assert!(parse_exec(&["create", "--label", "ci", "--label", "gpu"]).is_ok());
+    }
+
+    #[test]
+    fn shard_command_exports_index_and_count() {
+        assert_eq!(
+            shard_command("npm test", 1, 4),
+            "export SANDBOX_INDEX=1 SANDBOX_COUNT=4; npm test"
+        );
+        assert_eq!(short_id("3f2a9c1b-77aa"), "3f2a9c1b");
+        assert_eq!(short_id("abc"), "abc");
+    }
+
+    #[test]
+    fn template_build_takes_commands_or_a_sandboxfile() {
+        assert!(parse_exec(&["template", "build", "-f", "Sandboxfile"]).is_ok());
+        assert!(parse_exec(&["template", "build", "-c", "true"]).is_ok());
+        assert!(parse_exec(&["template", "build"]).is_err());
+        assert!(parse_exec(&["template", "build", "-f", "Sandboxfile", "-c", "true"]).is_err());
+    }
+
     #[test]
     fn manually_wrapped_pairs_split_and_pass_verbatim() {
         // Users may pre-wrap references themselves; comma-splitting still
@@ -0,0 +1,477 @@
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+use is_terminal::IsTerminal;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    controllers::{
+        config::{EnvironmentConfig, ServiceInstance, environment::fetch_environment_config},
+        environment::get_matched_environment,
+        project::{ServiceContext, resolve_service_context},
+        template_apply::stage_and_commit_patch,
+    },
+    util::{progress::create_spinner_if, prompt::prompt_confirm_with_default},
+};
+
+use super::*;
+
+#[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway service duplicate worker --name worker-experiment\n  railway service duplicate worker --name worker --environment staging --with-variables\n  railway service duplicate api --name api-copy --with-variables --with-volumes=empty --deploy --yes --json\n\nAutomation notes:\n  Source, build and deploy settings are always copied; domains and TCP proxies are not.\n  Volumes are never copied with their data: --with-volumes=empty creates fresh volumes at the same mount paths.\n  The copy is committed without deploying unless --deploy is passed."
+)]
+pub(super) struct DuplicateArgs {
+    /// Service name or ID to duplicate (defaults to linked service)
+    service: Option<String>,
+
+    /// Name for the new service
+    #[clap(short, long)]
+    name: String,
+
+    /// Environment to create the copy in (defaults to the source environment)
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Environment the source service is read from (defaults to linked environment)
+    #[clap(long, value_name = "ENVIRONMENT")]
+    from_environment: Option<String>,
+
+    /// Project ID to use (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT_ID")]
+    project: Option<String>,
+
+    /// Copy the service's variables
+    #[clap(long)]
+    with_variables: bool,
+
+    /// Recreate the service's volumes empty, or leave them out
+    #[clap(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        default_value_t = VolumeMode::None,
+        default_missing_value = "empty"
+    )]
+    with_volumes: VolumeMode,
+
+    /// Deploy the new service once it's created
+    #[clap(long)]
+    deploy: bool,
+
+    /// Skip confirmation dialog
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum VolumeMode {
+    /// Create a new, empty volume at each mount path
+    Empty,
+    /// Don't create volumes
+    None,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DuplicateOutput {
+    id: String,
+    name: String,
+    source_service_id: String,
+    environment_id: String,
+    volume_ids: Vec<String>,
+    deployed: bool,
+    diff: Vec<String>,
+}
+
+pub(super) async fn duplicate_command(args: DuplicateArgs) -> Result<()> {
+    let source = resolve_service_context(
+        args.project.clone(),
+        args.service.clone(),
+        args.from_environment.clone(),
+    )
+    .await?;
+    let target = match &args.environment {
+        Some(environment) => get_matched_environment(&source.project, environment.clone())?,
+        None => get_matched_environment(&source.project, source.environment_id.clone())?,
+    };
+
+    let config = fetch_environment_config(
+        &source.client,
+        &source.configs,
+        &source.environment_id,
+        args.with_variables,
+    )
+    .await?
+    .config;
+    let instance = config.services.get(&source.service_id).with_context(|| {
+        format!(
+            "Service {} has no configuration in {}",
+            source.service_name, source.environment_name
+        )
+    })?;
+    let with_volumes = args.with_volumes == VolumeMode::Empty;
+    let copy = duplicate_instance(instance, args.with_variables, with_volumes);
+    let mount_paths = if with_volumes {
+        instance
+            .volume_mounts
+            .values()
+            .filter_map(|mount| mount.mount_path.clone())
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    let diff = diff_lines(instance, &copy, with_volumes);
+
+    if !args.json {
+        println!(
+            "Duplicating {} ({}) as {} in {}\n",
+            source.service_name.bold(),
+            source.environment_name,
+            args.name.bold(),
+            target.name.bold()
+        );
+        for line in &diff {
+            match line.chars().next() {
+                Some('+') => println!("  {}", line.green()),
+                Some('-') => println!("  {}", line.dimmed()),
+                _ => println!("  {line}"),
+            }
+        }
+        println!();
+    }
+
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default(
+            &format!("Create service {} in {}?", args.name, target.name),
+            false,
+        )?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    let spinner = create_spinner_if(!args.json, format!("Creating {}...", args.name));
+    let created = post_graphql::<mutations::ServiceCreate, _>(
+        &source.client,
+        source.configs.get_backboard(),
+        mutations::service_create::Variables {
+            name: Some(args.name.clone()),
+            project_id: source.project_id.clone(),
+            environment_id: target.id.clone(),
+            source: None,
+            variables: None,
+            branch: None,
+        },
+    )
+    .await
+    .context("Failed to create service")?
+    .service_create;
+
+    let ServiceContext {
+        client,
+        configs,
+        project,
+        project_id,
+        service_id: source_service_id,
+        service_name: source_service_name,
+        ..
+    } = source;
+    let ctx = ServiceContext {
+        client,
+        configs,
+        project,
+        project_id,
+        environment_id: target.id.clone(),
+        environment_name: target.name.clone(),
+        service_id: created.id.clone(),
+        service_name: created.name.clone(),
+    };
+
+    let mut volume_ids = Vec::new();
+    let configured = configure_copy(&ctx, &mount_paths, copy, args.deploy, &mut volume_ids).await;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+    let deployed = match configured {
+        Ok(deployed) => deployed,
+        Err(e) => return Err(remove_partial_copy(&ctx, &volume_ids, e).await),
+    };
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&DuplicateOutput {
+                id: ctx.service_id,
+                name: ctx.service_name,
+                source_service_id,
+                environment_id: ctx.environment_id,
+                volume_ids,
+                deployed,
+                diff,
+            })?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Created {} from {} in {}",
+        ctx.service_name.green(),
+        source_service_name,
+        ctx.environment_name
+    );
+    if !deployed {
+        println!(
+            "{}",
+            "Not deployed yet. Pass --deploy next time, or deploy it from the dashboard.".dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Give the freshly created service its volumes and the copied config,
+/// recording created volumes in `volume_ids` so a failure can remove them.
+async fn configure_copy(
+    ctx: &ServiceContext,
+    mount_paths: &[String],
+    copy: ServiceInstance,
+    deploy: bool,
+    volume_ids: &mut Vec<String>,
+) -> Result<bool> {
+    for mount_path in mount_paths {
+        let volume = post_graphql::<mutations::VolumeCreate, _>(
+            &ctx.client,
+            ctx.configs.get_backboard(),
+            mutations::volume_create::Variables {
+                project_id: ctx.project_id.clone(),
+                environment_id: ctx.environment_id.clone(),
+                service_id: ctx.service_id.clone(),
+                mount_path: mount_path.clone(),
+            },
+        )
+        .await
+        .with_context(|| format!("Failed to create volume at {mount_path}"))?;
+        volume_ids.push(volume.volume_create.id);
+    }
+
+    let patch = EnvironmentConfig {
+        services: BTreeMap::from([(ctx.service_id.clone(), copy)]),
+        ..EnvironmentConfig::default()
+    };
+    stage_and_commit_patch(ctx, patch, deploy).await
+}
+
+/// Delete a copy that failed halfway, so it doesn't linger as an empty
+/// service. If that fails too, the error names the service to clean up.
+async fn remove_partial_copy(
+    ctx: &ServiceContext,
+    volume_ids: &[String],
+    error: anyhow::Error,
+) -> anyhow::Error {
+    let mut cleanup = Ok(());
+    for id in volume_ids {
+        if let Err(e) = post_graphql::<mutations::VolumeDelete, _>(
+            &ctx.client,
+            ctx.configs.get_backboard(),
+            mutations::volume_delete::Variables { id: id.clone() },
+        )
+        .await
+        {
+            cleanup = Err(e);
+        }
+    }
+    if cleanup.is_ok() {
+        cleanup = post_graphql::<mutations::ServiceDelete, _>(
+            &ctx.client,
+            ctx.configs.get_backboard(),
+            mutations::service_delete::Variables {
+                service_id: ctx.service_id.clone(),
+                environment_id: ctx.environment_id.clone(),
+            },
+        )
+        .await
+        .map(|_| ());
+    }
+    match cleanup {
+        Ok(()) => error.context(format!(
+            "Failed to configure service {}; it was removed again",
+            ctx.service_name
+        )),
+        Err(_) => error.context(format!(
+            "Failed to configure service {}, which was created but is left unconfigured (ID {}). Remove it with `railway service delete --service {} --environment {}`",
+            ctx.service_name, ctx.service_id, ctx.service_id, ctx.environment_name
+        )),
+    }
+}
+
+/// The parts of a service instance that make sense on a new service: source,
+/// build and deploy config, and optionally variables. Domains, proxies and
+/// cluster wiring are tied to the original service and are left out.
+fn duplicate_instance(
+    source: &ServiceInstance,
+    with_variables: bool,
+    with_volumes: bool,
+) -> ServiceInstance {
+    let mut deploy = source.deploy.clone();
+    if !with_volumes && let Some(deploy) = deploy.as_mut() {
+        deploy.required_mount_path = None;
+    }
+    ServiceInstance {
+        source: source.source.clone(),
+        build: source.build.clone(),
+        deploy,
+        config_file: source.config_file.clone(),
+        variables: if with_variables {
+            source.variables.clone()
+        } else {
+            BTreeMap::new()
+        },
+        ..ServiceInstance::default()
+    }
+}
+
+/// `+` lines for settings the copy gets, `-` lines for settings of the source
+/// that are not copied. Variable values are masked.
+fn diff_lines(source: &ServiceInstance, copy: &ServiceInstance, with_volumes: bool) -> Vec<String> {
+    let copied = flatten_instance(copy);
+    let mut lines = copied
+        .iter()
+        .map(|(path, value)| format!("+ {path} = {value}"))
+        .collect::<Vec<_>>();
+
+    for mount in source.volume_mounts.values() {
+        let path = mount.mount_path.as_deref().unwrap_or("(no mount path)");
+        if with_volumes {
+            lines.push(format!("+ volume {path} (empty)"));
+        } else {
+            lines.push(format!("- volume {path} (pass --with-volumes=empty)"));
+        }
+    }
+
+    let mut skipped = BTreeMap::new();
+    for (path, _) in flatten_instance(source) {
+        if copied.contains_key(&path) {
+            continue;
+        }
+        let section = path.split('.').next().unwrap_or(&path).to_string();
+        let reason = match section.as_str() {
+            "variables" => "pass --with-variables",
+            "networking" => "domains and proxies stay with the original",
+            "volumeMounts" => continue,
+            "deploy" => "only applies with a volume",
+            _ => "not copied",
+        };
+        skipped.entry(section).or_insert(reason);
+    }
+    for (section, reason) in skipped {
+        lines.push(format!("- {section} ({reason})"));
+    }
+    lines
+}
+
+fn flatten_instance(instance: &ServiceInstance) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    if let Ok(value) = serde_json::to_value(instance) {
+        flatten(&value, String::new(), &mut out);
+    }
+    out
+}
+
+fn flatten(value: &Value, path: String, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(_) if path.starts_with("variables.") && path.matches('.').count() == 1 => {
+            out.insert(path, "••••••".to_string());
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten(value, child, out);
+            }
+        }
+        Value::Null => {}
+        Value::String(s) => {
+            out.insert(path, s.clone());
+        }
+        other => {
+            out.insert(path, other.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn instance() -> ServiceInstance {
+        serde_json::from_value(json!({
+            "source": { "repo": "acme/worker", "branch": "main" },
+            "build": { "builder": "RAILPACK" },
+            "deploy": {
+                "startCommand": "node worker.js",
+                "requiredMountPath": "/data",
+                "numReplicas": 2
+            },
+            "variables": { "QUEUE": { "value": "jobs" } },
+            "networking": { "serviceDomains": { "worker.up.railway.app": { "port": 8080 } } },
+            "volumeMounts": { "vol-1": { "mountPath": "/data" } },
+            "parentServiceId": "svc-0"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn duplicate_copies_config_but_not_identity() {
+        let copy = duplicate_instance(&instance(), false, false);
+        assert_eq!(copy.source.unwrap().repo.as_deref(), Some("acme/worker"));
+        assert_eq!(
+            copy.deploy.as_ref().unwrap().start_command.as_deref(),
+            Some("node worker.js")
+        );
+        assert_eq!(copy.deploy.unwrap().required_mount_path, None);
+        assert!(copy.variables.is_empty());
+        assert!(copy.networking.is_none());
+        assert!(copy.volume_mounts.is_empty());
+        assert!(copy.parent_service_id.is_none());
+
+        let copy = duplicate_instance(&instance(), true, true);
+        assert!(copy.variables.contains_key("QUEUE"));
+        assert_eq!(
+            copy.deploy.unwrap().required_mount_path.as_deref(),
+            Some("/data")
+        );
+    }
+
+    #[test]
+    fn diff_shows_copied_and_skipped_settings() {
+        let source = instance();
+        let copy = duplicate_instance(&source, true, false);
+        let diff = diff_lines(&source, &copy, false);
+
+        assert!(diff.contains(&"+ source.repo = acme/worker".to_string()));
+        assert!(diff.contains(&"+ deploy.numReplicas = 2".to_string()));
+        assert!(diff.contains(&"+ variables.QUEUE = ••••••".to_string()));
+        assert!(!diff.iter().any(|line| line.contains("jobs")));
+        assert!(diff.contains(&"- volume /data (pass --with-volumes=empty)".to_string()));
+        assert!(
+            diff.contains(&"- networking (domains and proxies stay with the original)".to_string())
+        );
+        assert!(diff.contains(&"- deploy (only applies with a volume)".to_string()));
+        assert!(diff.contains(&"- parentServiceId (not copied)".to_string()));
+    }
+}
@@ -26,6 +26,8 @@ use crate::{
 
 use super::*;
 
+mod duplicate;
+
 pub fn get_dynamic_args(cmd: clap::Command) -> clap::Command {
     cmd.mut_subcommand(
         "scale",
@@ -36,7 +38,7 @@ pub fn get_dynamic_args(cmd: clap::Command) -> clap::Command {
 /// Manage services
 #[derive(Parser)]
 #[clap(
-    after_help = "Examples:\n\n  railway service list --json\n  railway service delete --service api --environment production --yes --json\n  railway service link api\n  railway service source connect --repo owner/repo --branch main --service api\n  railway service source disconnect --service api\n  railway service files list /app --json\n  railway service files browse /app\n  railway service files download /app/data.db ./data.db --json\n  railway service files upload ./seed.db /app/seed.db --json\n  railway service files delete /app/data.db --yes --json\n  railway service files rename /app/data.db /app/data-old.db --json\n\nAutomation notes:\n  Destructive non-interactive runs must pass exact selectors and --yes.\n  Prefer service IDs from `railway service list --json` when names may collide."
+    after_help = "Examples:\n\n  railway service list --json\n  railway service delete --service api --environment production --yes --json\n  railway service link api\n  railway service duplicate worker --name worker-experiment --with-variables\n  railway service source connect --repo owner/repo --branch main --service api\n  railway service source disconnect --service api\n  railway service files list /app --json\n  railway service files browse /app\n  railway service files download /app/data.db ./data.db --json\n  railway service files upload ./seed.db /app/seed.db --json\n  railway service files delete /app/data.db --yes --json\n  railway service files rename /app/data.db /app/data-old.db --json\n\nAutomation notes:\n  Destructive non-interactive runs must pass exact selectors and --yes.\n  Prefer service IDs from `railway service list --json` when names may collide."
 )]
 pub struct Args {
     #[clap(subcommand)]
@@ -80,6 +82,10 @@ enum Commands {
     /// Manage files in a service filesystem
     #[clap(visible_alias = "file")]
     Files(FilesArgs),
+
+    /// Create a copy of a service in this or another environment
+    #[clap(visible_alias = "clone")]
+    Duplicate(duplicate::DuplicateArgs),
 }
 
 #[derive(Parser)]
@@ -293,6 +299,9 @@ pub async fn command(args: Args) -> Result<()> {
         }
         Some(Commands::Scale(scale_args)) => crate::commands::scale::command(scale_args).await,
         Some(Commands::Files(files_args)) => files_command(files_args).await,
+        Some(Commands::Duplicate(duplicate_args)) => {
+            duplicate::duplicate_command(duplicate_args).await
+        }
         None => unreachable!(),
     }
 }
@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, Utc};
+use is_terminal::IsTerminal;
+use serde::Serialize;
+
+use crate::{
+    controllers::project::resolve_environment_context,
+    gql::queries::{
+        api_tokens::ApiTokensApiTokensEdgesNode,
+        project_tokens::ProjectTokensProjectTokensEdgesNode,
+    },
+    table::ListTable,
+    util::{
+        progress::create_spinner_if,
+        prompt::{prompt_confirm_with_default, prompt_text},
+        two_factor::validate_two_factor_if_enabled,
+    },
+    workspace::{pick_workspace, workspaces},
+};
+
+use super::*;
+
+/// Create, list and revoke project and account API tokens
+#[derive(Parser)]
+#[clap(
+    after_help = "Examples:\n\n  railway token create --project --environment production --name ci --json\n  railway token create --name laptop --workspace my-team\n  railway token list --project\n  railway token list --json\n  railway token revoke <token-id> --project --yes\n\nAutomation notes:\n  Project tokens are set as RAILWAY_TOKEN; account and workspace tokens as RAILWAY_API_TOKEN.\n  The secret is printed once, on create. List output only shows a masked form."
+)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(Parser)]
+enum Commands {
+    /// Create a token and print its secret once
+    #[clap(visible_alias = "new")]
+    Create(CreateArgs),
+
+    /// List tokens without their secrets
+    #[clap(visible_alias = "ls")]
+    List(ListArgs),
+
+    /// Revoke a token by ID
+    #[clap(visible_alias = "delete", visible_alias = "rm")]
+    Revoke(RevokeArgs),
+}
+
+#[derive(Parser)]
+struct CreateArgs {
+    /// Token name, e.g. the CI system it's for
+    #[clap(short, long)]
+    name: Option<String>,
+
+    /// Create a project token (for RAILWAY_TOKEN) instead of an account token.
+    /// Takes a project ID or name, or uses the linked project.
+    #[clap(long, num_args = 0..=1, value_name = "PROJECT")]
+    project: Option<Option<String>>,
+
+    /// Environment a project token is scoped to (defaults to linked environment)
+    #[clap(short, long, requires = "project")]
+    environment: Option<String>,
+
+    /// Workspace an account token is scoped to (defaults to your personal account)
+    #[clap(short, long, conflicts_with = "project")]
+    workspace: Option<String>,
+
+    /// 2FA code for verification (required if 2FA is enabled in non-interactive mode)
+    #[clap(long = "2fa-code")]
+    two_factor_code: Option<String>,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct ListArgs {
+    /// List project tokens for a project ID or name, or the linked project
+    #[clap(long, num_args = 0..=1, value_name = "PROJECT")]
+    project: Option<Option<String>>,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct RevokeArgs {
+    /// ID of the token to revoke (see `railway token list`)
+    id: String,
+
+    /// The token is a project token of this project ID or name, or the linked project
+    #[clap(long, num_args = 0..=1, value_name = "PROJECT")]
+    project: Option<Option<String>>,
+
+    /// Skip confirmation dialog
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenOutput {
+    id: String,
+    name: String,
+    kind: &'static str,
+    display_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+pub async fn command(args: Args) -> Result<()> {
+    match args.command {
+        Commands::Create(args) => create_token(args).await,
+        Commands::List(args) => list_tokens(args).await,
+        Commands::Revoke(args) => revoke_token(args).await,
+    }
+}
+
+async fn create_token(args: CreateArgs) -> Result<()> {
+    let is_terminal = std::io::stdout().is_terminal();
+    let name = match args.name {
+        Some(name) => name,
+        None if is_terminal => prompt_text("Token name")?,
+        None => bail!("--name is required in non-interactive mode"),
+    };
+    if name.trim().is_empty() {
+        bail!("Token name cannot be empty");
+    }
+
+    let (scope, env_var, token) = match args.project {
+        Some(project) => {
+            let ctx = resolve_environment_context(project, args.environment).await?;
+            validate_two_factor_if_enabled(
+                &ctx.client,
+                &ctx.configs,
+                is_terminal,
+                args.two_factor_code,
+            )
+            .await?;
+            let spinner = create_spinner_if(!args.json, "Creating project token...".into());
+            let token = post_graphql::<mutations::ProjectTokenCreate, _>(
+                &ctx.client,
+                ctx.configs.get_backboard(),
+                mutations::project_token_create::Variables {
+                    input: mutations::project_token_create::ProjectTokenCreateInput {
+                        project_id: ctx.project_id.clone(),
+                        environment_id: ctx.environment_id.clone(),
+                        name: name.clone(),
+                    },
+                },
+            )
+            .await?
+            .project_token_create;
+            if let Some(spinner) = spinner {
+                spinner.finish_and_clear();
+            }
+            let scope = format!(
+                "project {} ({})",
+                ctx.project.name.bold(),
+                ctx.environment_name
+            );
+            (scope, "RAILWAY_TOKEN", token)
+        }
+        None => {
+            let configs = Configs::new()?;
+            let client = GQLClient::new_authorized(&configs)?;
+            let workspace = match args.workspace {
+                Some(workspace) => Some(pick_workspace(workspaces().await?, Some(workspace))?),
+                None => None,
+            };
+            validate_two_factor_if_enabled(&client, &configs, is_terminal, args.two_factor_code)
+                .await?;
+            let spinner = create_spinner_if(!args.json, "Creating API token...".into());
+            let token = post_graphql::<mutations::ApiTokenCreate, _>(
+                &client,
+                configs.get_backboard(),
+                mutations::api_token_create::Variables {
+                    input: mutations::api_token_create::ApiTokenCreateInput {
+                        name: name.clone(),
+                        workspace_id: workspace.as_ref().map(|w| w.id().to_string()),
+                    },
+                },
+            )
+            .await?
+            .api_token_create;
+            if let Some(spinner) = spinner {
+                spinner.finish_and_clear();
+            }
+            let scope = match &workspace {
+                Some(workspace) => format!("workspace {}", workspace.name().bold()),
+                None => "your account".to_string(),
+            };
+            (scope, "RAILWAY_API_TOKEN", token)
+        }
+    };
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "name": name,
+                "token": token,
+                "envVar": env_var,
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!("Created token {} for {}", name.green(), scope);
+    println!();
+    println!("  {token}");
+    println!();
+    println!(
+        "{}",
+        format!("Set it as {env_var}. It won't be shown again.").yellow()
+    );
+    Ok(())
+}
+
+async fn list_tokens(args: ListArgs) -> Result<()> {
+    let tokens = match args.project {
+        Some(project) => {
+            let (client, configs, project_id) = project_scope(project).await?;
+            post_graphql::<queries::ProjectTokens, _>(
+                &client,
+                configs.get_backboard(),
+                queries::project_tokens::Variables { project_id },
+            )
+            .await?
+            .project_tokens
+            .edges
+            .into_iter()
+            .map(|edge| project_token_output(edge.node))
+            .collect::<Vec<_>>()
+        }
+        None => {
+            let configs = Configs::new()?;
+            let client = GQLClient::new_authorized(&configs)?;
+            let tokens = post_graphql::<queries::ApiTokens, _>(
+                &client,
+                configs.get_backboard(),
+                queries::api_tokens::Variables {},
+            )
+            .await?
+            .api_tokens
+            .edges;
+            // Names only make the list readable; fall back to IDs without them.
+            let workspace_names: HashMap<String, String> = workspaces()
+                .await
+                .map(|workspaces| {
+                    workspaces
+                        .iter()
+                        .map(|w| (w.id().to_string(), w.name().to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            tokens
+                .into_iter()
+                .map(|edge| api_token_output(edge.node, &workspace_names))
+                .collect::<Vec<_>>()
+        }
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&tokens)?);
+        return Ok(());
+    }
+    if tokens.is_empty() {
+        println!("No tokens found");
+        return Ok(());
+    }
+
+    ListTable::new(
+        "Tokens".to_string(),
+        &["ID", "Name", "Scope", "Token", "Created", "Expires"],
+        tokens.iter().map(token_row).collect(),
+    )
+    .print();
+    Ok(())
+}
+
+fn project_token_output(node: ProjectTokensProjectTokensEdgesNode) -> TokenOutput {
+    TokenOutput {
+        id: node.id,
+        name: node.name,
+        kind: "project",
+        display_token: node.display_token,
+        project: Some(node.project.name),
+        environment: Some(node.environment.name),
+        workspace_id: None,
+        workspace: None,
+        created_at: Some(node.created_at),
+        expires_at: None,
+    }
+}
+
+fn api_token_output(
+    node: ApiTokensApiTokensEdgesNode,
+    workspace_names: &HashMap<String, String>,
+) -> TokenOutput {
+    TokenOutput {
+        id: node.id,
+        name: node.name,
+        kind: "api",
+        display_token: node.display_token,
+        project: None,
+        environment: None,
+        workspace: node
+            .workspace_id
+            .as_ref()
+            .and_then(|id| workspace_names.get(id).cloned()),
+        workspace_id: node.workspace_id,
+        created_at: None,
+        expires_at: node.expires_at,
+    }
+}
+
+/// What a token can act on, e.g. `environment production of project api`.
+fn token_scope(token: &TokenOutput) -> String {
+    match (&token.environment, &token.workspace_id) {
+        (Some(environment), _) => match &token.project {
+            Some(project) => format!("environment {environment} of project {project}"),
+            None => format!("environment {environment}"),
+        },
+        (None, Some(id)) => format!("workspace {}", token.workspace.as_deref().unwrap_or(id)),
+        (None, None) => "personal account".to_string(),
+    }
+}
+
+/// Project tokens report when they were created and never expire; account
+/// tokens report only their expiry.
+fn token_dates(token: &TokenOutput) -> (String, String) {
+    let date = |at: DateTime<Utc>| DateTime::<Local>::from(at).format("%Y-%m-%d").to_string();
+    (
+        token
+            .created_at
+            .map(date)
+            .unwrap_or_else(|| "-".to_string()),
+        token
+            .expires_at
+            .map(date)
+            .unwrap_or_else(|| "never".to_string()),
+    )
+}
+
+fn token_row(token: &TokenOutput) -> Vec<String> {
+    let (created, expires) = token_dates(token);
+    vec![
+        token.id.clone(),
+        token.name.clone(),
+        token_scope(token),
+        token.display_token.clone(),
+        created,
+        expires,
+    ]
+}
+
+async fn revoke_token(args: RevokeArgs) -> Result<()> {
+    let project = args.project.is_some();
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default(
+            &format!(
+                "Revoke token {}? Anything using it will stop working.",
+                args.id
+            ),
+            false,
+        )?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    if let Some(project) = args.project {
+        let (client, configs, _) = project_scope(project).await?;
+        post_graphql::<mutations::ProjectTokenDelete, _>(
+            &client,
+            configs.get_backboard(),
+            mutations::project_token_delete::Variables {
+                id: args.id.clone(),
+            },
+        )
+        .await?;
+    } else {
+        let configs = Configs::new()?;
+        let client = GQLClient::new_authorized(&configs)?;
+        post_graphql::<mutations::ApiTokenDelete, _>(
+            &client,
+            configs.get_backboard(),
+            mutations::api_token_delete::Variables {
+                id: args.id.clone(),
+            },
+        )
+        .await?;
+    }
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": args.id, "kind": if project { "project" } else { "api" } })
+        );
+    } else {
+        println!("Revoked token {}", args.id.green());
+    }
+    Ok(())
+}
+
+/// The client, configs and project ID for `--project [PROJECT]`.
+async fn project_scope(project: Option<String>) -> Result<(reqwest::Client, Configs, String)> {
+    let configs = Configs::new()?;
+    let client = GQLClient::new_authorized(&configs)?;
+    let project_id = match project {
+        Some(project) => {
+            crate::controllers::project::resolve_project_id_or_name(&client, &configs, &project)
+                .await?
+        }
+        None => configs.get_linked_project().await?.project,
+    };
+    Ok((client, configs, project_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::gql::queries::project_tokens::{
+        ProjectTokensProjectTokensEdgesNodeEnvironment, ProjectTokensProjectTokensEdgesNodeProject,
+    };
+
+    fn api_token(workspace_id: Option<&str>) -> ApiTokensApiTokensEdgesNode {
+        ApiTokensApiTokensEdgesNode {
+            id: "tok-1".into(),
+            name: "laptop".into(),
+            display_token: "rw_****abcd".into(),
+            workspace_id: workspace_id.map(str::to_string),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn project_tokens_are_scoped_to_project_and_environment() {
+        let token = project_token_output(ProjectTokensProjectTokensEdgesNode {
+            id: "tok-2".into(),
+            name: "ci".into(),
+            display_token: "****wxyz".into(),
+            created_at: Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap(),
+            environment: ProjectTokensProjectTokensEdgesNodeEnvironment {
+                id: "env-1".into(),
+                name: "production".into(),
+            },
+            project: ProjectTokensProjectTokensEdgesNodeProject { name: "api".into() },
+        });
+        assert_eq!(token.kind, "project");
+        assert_eq!(token_scope(&token), "environment production of project api");
+        let (created, expires) = token_dates(&token);
+        assert!(created.starts_with("2026-03-0"));
+        assert_eq!(expires, "never");
+    }
+
+    #[test]
+    fn api_tokens_name_their_workspace() {
+        let names = HashMap::from([("ws-1".to_string(), "Acme".to_string())]);
+        let token = api_token_output(api_token(Some("ws-1")), &names);
+        assert_eq!(token_scope(&token), "workspace Acme");
+        assert_eq!(token.workspace_id.as_deref(), Some("ws-1"));
+
+        // An unknown workspace still shows something identifiable.
+        let token = api_token_output(api_token(Some("ws-2")), &names);
+        assert_eq!(token_scope(&token), "workspace ws-2");
+
+        let token = api_token_output(api_token(None), &names);
+        assert_eq!(token_scope(&token), "personal account");
+        assert_eq!(token_dates(&token), ("-".to_string(), "never".to_string()));
+        assert_eq!(token_row(&token).len(), 6);
+    }
+}
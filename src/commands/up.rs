@@ -1,10 +1,15 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::PathBuf,
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
+};
 
 use anyhow::{Context, Result, bail};
 use colored::Colorize;
 use futures::StreamExt;
-use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use is_terminal::IsTerminal;
+use tokio_util::sync::CancellationToken;
 
 use crate::{
     consts::TICK_STRING,
@@ -13,7 +18,10 @@ use crate::{
         environment::get_matched_environment,
         project::get_project,
         service::get_or_prompt_service,
-        upload::{create_deploy_tarball, upload_deploy_tarball},
+        upload::{
+            UpResponse, create_deploy_tarball, deploy_change_filter, deploy_file_report,
+            upload_deploy_tarball,
+        },
     },
     subscription::subscribe_graphql,
     subscriptions::deployment::DeploymentStatus,
@@ -21,6 +29,7 @@ use crate::{
         detect::detect_services,
         git::{detect_current_branch, detect_github_remote},
         logs::{LogFormat, print_log},
+        watcher::FileWatcher,
     },
     workspace::{pick_workspace, workspaces},
 };
@@ -36,7 +45,7 @@ use super::*;
 /// skip the surrounding prompts in scripted or agent-driven contexts.
 #[derive(Parser)]
 #[clap(
-    after_help = "Examples:\n\n  railway up --service api --environment production\n  railway up ./apps/api --path-as-root --service api\n  railway up --detach --json --message \"deploy api\"\n\nAutomation notes:\n  `railway up --detach --json` starts an upload and deployment, but it does not wait for the deployment to become healthy.\n  Poll with `railway deployment list --json` and inspect logs with `railway logs --json --lines 100`.\n  To switch a locally uploaded service to GitHub autodeploys, run `railway service source connect --repo owner/repo --branch main --service api`."
+    after_help = "Examples:\n\n  railway up --service api --environment production\n  railway up ./apps/api --path-as-root --service api\n  railway up --detach --json --message \"deploy api\"\n  railway up --dry-run\n  railway up --watch --service api --environment pr-42\n\nAutomation notes:\n  `railway up --detach --json` starts an upload and deployment, but it does not wait for the deployment to become healthy.\n  Poll with `railway deployment list --json` and inspect logs with `railway logs --json --lines 100`.\n  To switch a locally uploaded service to GitHub autodeploys, run `railway service source connect --repo owner/repo --branch main --service api`."
 )]
 pub struct Args {
     path: Option<PathBuf>,
@@ -105,6 +114,17 @@ pub struct Args {
     #[clap(short, long)]
     /// Message to attach to the deployment
     message: Option<String>,
+
+    #[clap(long)]
+    /// List the files that would be uploaded, and the ignore rule that
+    /// excludes each file left out, without uploading anything
+    dry_run: bool,
+
+    #[clap(long, conflicts_with_all = ["detach", "ci", "dry_run", "new"])]
+    /// Keep running and redeploy whenever files that would be uploaded
+    /// change. A change that lands while the previous deploy is still
+    /// building cancels that deploy.
+    watch: bool,
 }
 
 pub async fn command(args: Args) -> Result<()> {
@@ -112,6 +132,16 @@ pub async fn command(args: Args) -> Result<()> {
 
     let mut configs = Configs::new()?;
 
+    if args.dry_run {
+        let linked_project_path = configs
+            .get_linked_project()
+            .await
+            .ok()
+            .map(|lp| lp.project_path);
+        let deploy_paths = get_deploy_paths(&args, linked_project_path)?;
+        return print_dry_run(&deploy_paths, args.no_gitignore, args.json);
+    }
+
     // If the user isn't signed in, intercept early: show a clack-style
     // picker (Create New Account / Log In and Deploy), chain into the
     // login flow, then reload configs and continue with `up`. This
@@ -198,112 +228,38 @@ pub async fn command(args: Args) -> Result<()> {
         })?;
     let environment_id = get_matched_environment(&project, environment)?.id;
 
-    let service = get_or_prompt_service(linked_project, project, args.service).await?;
-
-    let is_tty = std::io::stdout().is_terminal() && !args.json;
-
-    let spinner = if is_tty {
-        let spinner = ProgressBar::new_spinner()
-            .with_style(
-                ProgressStyle::default_spinner()
-                    .tick_chars(TICK_STRING)
-                    .template("{spinner:.green} {msg:.cyan.bold}")?,
-            )
-            .with_message("Indexing");
-        spinner.enable_steady_tick(Duration::from_millis(100));
-        Some(spinner)
-    } else if !args.json {
-        println!("Indexing...");
-        None
-    } else {
-        None
-    };
+    let service = get_or_prompt_service(linked_project, project, args.service.clone()).await?;
 
-    let mut progress_bar: Option<ProgressBar> = None;
-    let body = create_deploy_tarball(
-        &deploy_paths.project_path,
-        &deploy_paths.archive_prefix_path,
-        args.no_gitignore,
-        |current, total| {
-            if current == 0 {
-                // Indexing complete
-                if let Some(s) = &spinner {
-                    s.finish_with_message("Indexed");
-                }
-                if is_tty {
-                    let pg = ProgressBar::new(total as u64)
-                        .with_style(
-                            ProgressStyle::default_bar()
-                                .template(
-                                    "{spinner:.green} {msg:.cyan.bold} [{bar:20}] {percent}% ",
-                                )
-                                .unwrap()
-                                .progress_chars("=> ")
-                                .tick_chars(TICK_STRING),
-                        )
-                        .with_message("Compressing")
-                        .with_finish(ProgressFinish::WithMessage("Compressed".into()));
-                    pg.enable_steady_tick(Duration::from_millis(100));
-                    progress_bar = Some(pg);
-                }
-            } else if let Some(pg) = &progress_bar {
-                pg.inc(1);
-            }
-        },
-    )?;
-
-    // Ensure progress bar finishes if no entries were processed
-    drop(progress_bar);
-
-    if args.verbose {
-        println!("railway up");
-        println!("service: {}", service.as_deref().unwrap_or_default());
-        println!("environment: {environment_id}");
-        println!("bytes: {}", body.len());
+    if args.watch {
+        let Some(service) = service else {
+            bail!(
+                "--watch needs a service to redeploy. Pass --service or run `railway service` to link one"
+            );
+        };
+        return watch_and_deploy(WatchDeploy {
+            backboard: configs.get_backboard(),
+            hostname: hostname.to_string(),
+            client,
+            project_id,
+            environment_id,
+            service,
+            deploy_paths,
+            in_flight: tokio::sync::Mutex::new(None),
+            args,
+        })
+        .await;
     }
 
-    let spinner = if std::io::stdout().is_terminal() && !args.json {
-        let spinner = ProgressBar::new_spinner()
-            .with_style(
-                ProgressStyle::default_spinner()
-                    .tick_chars(TICK_STRING)
-                    .template("{spinner:.green} {msg:.cyan.bold}")?,
-            )
-            .with_message("Uploading");
-        spinner.enable_steady_tick(Duration::from_millis(100));
-        Some(spinner)
-    } else if !args.json {
-        println!("Uploading...");
-        None
-    } else {
-        None
-    };
-
-    let up_result = upload_deploy_tarball(
+    let body = upload_from_dir(
+        &args,
         &client,
         hostname,
         &project_id,
         &environment_id,
         service.as_deref(),
-        args.message.as_deref(),
-        body,
+        &deploy_paths,
     )
-    .await;
-
-    let body = match up_result {
-        Err(e) => {
-            if let Some(spinner) = spinner {
-                spinner.finish_with_message("Failed");
-            }
-            return Err(e);
-        }
-        Ok(body) => {
-            if let Some(spinner) = spinner {
-                spinner.finish_with_message("Uploaded");
-            }
-            body
-        }
-    };
+    .await?;
 
     let deployment_id = body.deployment_id;
 
@@ -472,6 +428,337 @@ pub async fn command(args: Args) -> Result<()> {
     Ok(())
 }
 
+/// Index, compress and upload a deploy directory, showing progress. Returns
+/// once the upload is accepted; the build runs on Railway from there.
+async fn upload_from_dir(
+    args: &Args,
+    client: &reqwest::Client,
+    hostname: &str,
+    project_id: &str,
+    environment_id: &str,
+    service: Option<&str>,
+    deploy_paths: &DeployPaths,
+) -> Result<UpResponse> {
+    let is_tty = std::io::stdout().is_terminal() && !args.json;
+
+    let spinner = if is_tty {
+        let spinner = ProgressBar::new_spinner()
+            .with_style(
+                ProgressStyle::default_spinner()
+                    .tick_chars(TICK_STRING)
+                    .template("{spinner:.green} {msg:.cyan.bold}")?,
+            )
+            .with_message("Indexing");
+        spinner.enable_steady_tick(Duration::from_millis(100));
+        Some(spinner)
+    } else if !args.json {
+        println!("Indexing...");
+        None
+    } else {
+        None
+    };
+
+    // Compression and upload run together, so one bar covers both. It stays
+    // hidden until indexing finishes and the file count is known.
+    let upload_bar = if is_tty {
+        Some(
+            ProgressBar::with_draw_target(None, ProgressDrawTarget::hidden()).with_style(
+                ProgressStyle::default_bar()
+                    .template("{spinner:.green} {msg:.cyan.bold} [{bar:20}] {percent}% ")?
+                    .progress_chars("=> ")
+                    .tick_chars(TICK_STRING),
+            ),
+        )
+    } else {
+        None
+    };
+    let on_progress = {
+        let spinner = spinner.clone();
+        let upload_bar = upload_bar.clone();
+        move |current: usize, total: usize| {
+            if current == 0 {
+                // Indexing complete
+                if let Some(s) = &spinner {
+                    s.finish_with_message("Indexed");
+                }
+                if let Some(pg) = &upload_bar {
+                    pg.set_length(total as u64);
+                    pg.set_message("Uploading");
+                    pg.set_draw_target(ProgressDrawTarget::stderr());
+                    pg.enable_steady_tick(Duration::from_millis(100));
+                }
+            } else if let Some(pg) = &upload_bar {
+                pg.inc(1);
+            }
+        }
+    };
+
+    let archive = create_deploy_tarball(
+        &deploy_paths.project_path,
+        &deploy_paths.archive_prefix_path,
+        args.no_gitignore,
+        on_progress,
+    );
+    let bytes_written = archive.bytes_written();
+
+    if !is_tty && !args.json {
+        println!("Uploading...");
+    }
+
+    let up_result = upload_deploy_tarball(
+        client,
+        hostname,
+        project_id,
+        environment_id,
+        service,
+        args.message.as_deref(),
+        archive,
+    )
+    .await;
+
+    let body = match up_result {
+        Err(e) => {
+            if let Some(s) = &spinner {
+                if !s.is_finished() {
+                    s.finish_with_message("Failed");
+                }
+            }
+            if let Some(pg) = &upload_bar {
+                pg.abandon_with_message("Failed");
+            }
+            return Err(e);
+        }
+        Ok(body) => {
+            if let Some(pg) = &upload_bar {
+                pg.finish_with_message("Uploaded");
+            }
+            body
+        }
+    };
+
+    if args.verbose {
+        println!("railway up");
+        println!("service: {}", service.unwrap_or_default());
+        println!("environment: {environment_id}");
+        println!("bytes: {}", bytes_written.load(Ordering::Relaxed));
+    }
+
+    Ok(body)
+}
+
+/// How long `railway up --watch` follows one deploy's logs and status before
+/// giving up on it and just watching for changes.
+const WATCHED_DEPLOY_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// State shared by the redeploys of `railway up --watch`.
+struct WatchDeploy {
+    args: Args,
+    client: reqwest::Client,
+    backboard: String,
+    hostname: String,
+    project_id: String,
+    environment_id: String,
+    service: String,
+    deploy_paths: DeployPaths,
+    /// The latest deployment, so the next change can cancel it if it's still
+    /// in progress.
+    in_flight: tokio::sync::Mutex<Option<String>>,
+}
+
+/// `railway up --watch`: deploy now, then again whenever a file that would be
+/// uploaded changes. Build logs for the current deploy stream until a newer
+/// change supersedes it.
+async fn watch_and_deploy(watch: WatchDeploy) -> Result<()> {
+    let root = std::path::absolute(&watch.deploy_paths.project_path)
+        .context("Failed to resolve the directory to watch")?;
+    let filter = deploy_change_filter(&root, watch.args.no_gitignore);
+    let watch = Arc::new(watch);
+
+    FileWatcher::directory(root, filter)
+        .trigger_on_start()
+        .watch(move |token, _event| {
+            let watch = watch.clone();
+            async move {
+                // Deploy in the background so the watcher stays responsive:
+                // Ctrl-C or a newer change cancels an upload in progress.
+                tokio::spawn(async move {
+                    tokio::select! {
+                        _ = token.cancelled() => {}
+                        result = redeploy(&watch, token.clone()) => {
+                            // Keep watching after a failed deploy; the next save may fix it.
+                            if let Err(e) = result {
+                                eprintln!("{}: {e:#}", "Deploy failed".red().bold());
+                            }
+                        }
+                    }
+                });
+                Ok(())
+            }
+        })
+        .await
+}
+
+async fn redeploy(watch: &WatchDeploy, token: CancellationToken) -> Result<()> {
+    let previous = watch.in_flight.lock().await.take();
+    if let Some(previous) = previous {
+        if !watch.args.json {
+            println!("\n{}", "Change detected, redeploying".cyan().bold());
+        }
+        cancel_if_in_progress(watch, &previous).await;
+    }
+
+    let response = upload_from_dir(
+        &watch.args,
+        &watch.client,
+        &watch.hostname,
+        &watch.project_id,
+        &watch.environment_id,
+        Some(&watch.service),
+        &watch.deploy_paths,
+    )
+    .await?;
+    *watch.in_flight.lock().await = Some(response.deployment_id.clone());
+
+    if watch.args.json {
+        println!(
+            "{}",
+            serde_json::json!({"deploymentId": response.deployment_id, "logsUrl": response.logs_url})
+        );
+    } else {
+        println!("  {}: {}", "Build Logs".green().bold(), response.logs_url);
+    }
+
+    let client = watch.client.clone();
+    let backboard = watch.backboard.clone();
+    let json_mode = watch.args.json;
+    let deployment_id = response.deployment_id;
+    tokio::spawn(async move {
+        let follow = follow_watched_deploy(client, backboard, deployment_id.clone(), json_mode);
+        tokio::select! {
+            _ = token.cancelled() => {}
+            followed = tokio::time::timeout(WATCHED_DEPLOY_TIMEOUT, follow) => {
+                if followed.is_err() {
+                    crate::util::reporter::warn(
+                        "WATCH_FOLLOW_TIMEOUT",
+                        format!(
+                            "Stopped following deploy {deployment_id} after {} minutes; it may still be running",
+                            WATCHED_DEPLOY_TIMEOUT.as_secs() / 60
+                        ),
+                        None,
+                    );
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Cancel a superseded deploy that hasn't finished yet. Failing to cancel
+/// isn't fatal: the newer deploy replaces it either way.
+async fn cancel_if_in_progress(watch: &WatchDeploy, deployment_id: &str) {
+    use queries::deployment_status::DeploymentStatus as Status;
+
+    let status = post_graphql::<queries::DeploymentStatus, _>(
+        &watch.client,
+        &watch.backboard,
+        queries::deployment_status::Variables {
+            id: deployment_id.to_string(),
+        },
+    )
+    .await;
+    let in_progress = matches!(
+        status.map(|data| data.deployment.status),
+        Ok(Status::QUEUED
+            | Status::WAITING
+            | Status::INITIALIZING
+            | Status::BUILDING
+            | Status::DEPLOYING)
+    );
+    if !in_progress {
+        return;
+    }
+
+    match post_graphql::<mutations::DeploymentCancel, _>(
+        &watch.client,
+        &watch.backboard,
+        mutations::deployment_cancel::Variables {
+            id: deployment_id.to_string(),
+        },
+    )
+    .await
+    {
+        Ok(_) if !watch.args.json => {
+            println!("{}", format!("Cancelled deploy {deployment_id}").yellow());
+        }
+        Ok(_) => {}
+        Err(e) => crate::util::reporter::warn(
+            "DEPLOY_CANCEL_FAILED",
+            format!("Couldn't cancel deploy {deployment_id}: {e}"),
+            None,
+        ),
+    }
+}
+
+/// Stream a watched deploy's build logs, then report how it ended.
+async fn follow_watched_deploy(
+    client: reqwest::Client,
+    backboard: String,
+    deployment_id: String,
+    json_mode: bool,
+) {
+    use queries::deployment_status::DeploymentStatus as Status;
+
+    // Same grace period as a normal `up`: give the backend a moment to
+    // register the deployment before subscribing.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    if let Err(e) = stream_build_logs(deployment_id.clone(), None, |log| {
+        if json_mode {
+            print_log(log, true, LogFormat::LevelOnly);
+        } else {
+            println!(
+                "{}",
+                crate::util::logs::strip_terminal_controls(&log.message)
+            );
+        }
+    })
+    .await
+    {
+        eprintln!("Failed to stream build logs: {e}");
+    }
+
+    loop {
+        let status = post_graphql::<queries::DeploymentStatus, _>(
+            &client,
+            &backboard,
+            queries::deployment_status::Variables {
+                id: deployment_id.clone(),
+            },
+        )
+        .await
+        .map(|data| data.deployment.status);
+        let (label, message) = match status {
+            Ok(Status::SUCCESS) => ("success", "Deploy complete".green().bold()),
+            Ok(Status::FAILED) => ("failed", "Deploy failed".red().bold()),
+            Ok(Status::CRASHED) => ("crashed", "Deploy crashed".red().bold()),
+            Ok(Status::REMOVED | Status::SKIPPED) => return,
+            _ => {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                continue;
+            }
+        };
+        if json_mode {
+            println!(
+                "{}",
+                serde_json::json!({"deploymentId": deployment_id, "status": label})
+            );
+        } else {
+            println!("{message}");
+            println!("{}", "Watching for changes...".dimmed());
+        }
+        return;
+    }
+}
+
 /// HTTP-polling fallback for the deploy verdict, for when the WebSocket
 /// subscriptions can't connect at all. Plain POSTs ride the same path the
 /// upload just used, so they work whenever the deploy could start in the
@@ -528,6 +815,73 @@ async fn poll_deployment_verdict(deployment_id: String, json_mode: bool) {
     }
 }
 
+/// `railway up --dry-run`: what would be uploaded, and why everything else
+/// isn't.
+fn print_dry_run(deploy_paths: &DeployPaths, no_gitignore: bool, json: bool) -> Result<()> {
+    let report = deploy_file_report(
+        &deploy_paths.project_path,
+        &deploy_paths.archive_prefix_path,
+        no_gitignore,
+    )?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let total: u64 = report.included.iter().map(|f| f.size).sum();
+    println!(
+        "{} ({} files, {})",
+        "Would upload".green().bold(),
+        report.included.len(),
+        format_bytes(total)
+    );
+    for file in &report.included {
+        println!("  {:>9}  {}", format_bytes(file.size), file.path);
+    }
+
+    if !report.excluded.is_empty() {
+        println!(
+            "\n{} ({})",
+            "Excluded".yellow().bold(),
+            report.excluded.len()
+        );
+        let width = report
+            .excluded
+            .iter()
+            .map(|e| e.path.len() + usize::from(e.is_dir))
+            .max()
+            .unwrap_or(0);
+        for excluded in &report.excluded {
+            let path = if excluded.is_dir {
+                format!("{}/", excluded.path)
+            } else {
+                excluded.path.clone()
+            };
+            println!("  {path:<width$}  {}", excluded.rule.dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0usize;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[unit])
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
 struct DeployPaths {
     project_path: PathBuf,
     archive_prefix_path: PathBuf,
@@ -810,18 +1164,11 @@ async fn deploy_new_project(args: &Args) -> Result<()> {
     )?;
     configs.write()?;
 
-    // Bundle the directory.
-    let bundle_spinner = step_spinner(args.json, "Bundling project");
-    let tarball = create_deploy_tarball(&cwd_path, &cwd_path, args.no_gitignore, |_, _| {})?;
-    if let Some(spinner) = bundle_spinner {
-        spinner.finish_and_clear();
-    }
-    if !args.json {
-        println!("  {} Bundled ({} bytes)", "✓".green(), tarball.len());
-    }
-
-    // Upload + queue the build.
-    let upload_spinner = step_spinner(args.json, "Uploading & queuing build");
+    // Bundle the directory and upload it as it's compressed, then queue
+    // the build.
+    let upload_spinner = step_spinner(args.json, "Bundling, uploading & queuing build");
+    let tarball = create_deploy_tarball(&cwd_path, &cwd_path, args.no_gitignore, |_, _| {});
+    let bytes_written = tarball.bytes_written();
 
     // Reuse the GQLClient::new_authorized reqwest client — it bakes the
     // bearer token into default headers, which backboard's
@@ -839,6 +1186,13 @@ async fn deploy_new_project(args: &Args) -> Result<()> {
     if let Some(spinner) = upload_spinner {
         spinner.finish_and_clear();
     }
+    if !args.json {
+        println!(
+            "  {} Uploaded ({} bytes)",
+            "✓".green(),
+            bytes_written.load(Ordering::Relaxed)
+        );
+    }
 
     // backboard's /up endpoint creates a service implicitly but doesn't
     // return its id, so recover it from the logs_url
@@ -1,19 +1,33 @@
 use super::*;
 use crate::{
     controllers::{
-        project::resolve_service_context,
-        variables::{Variable, get_service_variables},
+        config::{EnvironmentConfig, ServiceInstance, fetch_environment_config},
+        environment::get_matched_environment,
+        project::{
+            EnvironmentContext, ServiceContext, resolve_environment_context,
+            resolve_service_context,
+        },
+        template_apply::stage_and_commit_patch,
+        variables::{
+            Variable, VariableDiff, VariableFileFormat, format_variables, get_service_variables,
+            get_shared_variables, get_unrendered_service_variables, is_railway_provided,
+            parse_variables,
+        },
     },
     table::Table,
-    util::progress::create_spinner_if,
+    util::{progress::create_spinner_if, prompt::prompt_confirm_with_default},
+};
+use anyhow::{Context, bail};
+use std::{
+    collections::BTreeMap,
+    io::{IsTerminal, Read},
+    path::PathBuf,
 };
-use anyhow::bail;
-use std::io::{IsTerminal, Read};
 
 /// Manage environment variables for a service
 #[derive(Parser)]
 #[clap(
-    after_help = "Examples:\n\n  railway variable list --service api --json\n  railway variable list --service api --kv\n  railway variable set API_URL=https://example.com --skip-deploys --json\n  echo \"secret\" | railway variable set API_KEY --stdin --skip-deploys --json\n  railway variable delete API_KEY --service api --json\n\nAutomation notes:\n  JSON and KV output include raw variable values. Avoid sharing command output from secret-bearing variable commands.\n  For idempotent deletes, list variables first, check whether the key exists, then delete it."
+    after_help = "Examples:\n\n  railway variable list --service api --json\n  railway variable list --service api --kv\n  railway variable set API_URL=https://example.com --skip-deploys --json\n  echo \"secret\" | railway variable set API_KEY --stdin --skip-deploys --json\n  railway variable delete API_KEY --service api --json\n  railway variable export --service api --format dotenv > .env\n  railway variable import .env --service api --yes\n  railway variable diff --from staging --to production --service api\n  railway variable --shared set DATABASE_URL=postgres://... --environment production\n  railway variable --shared list --json\n  railway variable share DATABASE_URL SENTRY_DSN --to api,worker --yes\n\nAutomation notes:\n  JSON and KV output include raw variable values. Avoid sharing command output from secret-bearing variable commands.\n  For idempotent deletes, list variables first, check whether the key exists, then delete it.\n  --shared works with list, set and delete. `share` sets `${{shared.KEY}}` references on every target service in one staged patch."
 )]
 pub struct Args {
     #[clap(subcommand)]
@@ -51,6 +65,10 @@ pub struct Args {
     /// Skip triggering deploys when setting variables
     #[clap(long)]
     skip_deploys: bool,
+
+    /// Operate on the environment's shared variables instead of a service's
+    #[clap(long, global = true)]
+    shared: bool,
 }
 
 #[derive(Parser)]
@@ -65,6 +83,18 @@ enum Commands {
     /// Delete a variable
     #[clap(visible_alias = "rm", visible_alias = "remove")]
     Delete(DeleteArgs),
+
+    /// Print a service's variables in a file format
+    Export(ExportArgs),
+
+    /// Replace a service's variables with the contents of a file
+    Import(ImportArgs),
+
+    /// Compare a service's variables between two environments
+    Diff(DiffArgs),
+
+    /// Reference shared variables from services via ${{shared.KEY}}
+    Share(ShareArgs),
 }
 
 #[derive(Parser)]
@@ -143,15 +173,186 @@ struct DeleteArgs {
     json: bool,
 }
 
+#[derive(Parser)]
+struct ExportArgs {
+    /// The service to export variables from
+    #[clap(short, long)]
+    service: Option<String>,
+
+    /// The environment to export variables from
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Project ID to use (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT_ID")]
+    project: Option<String>,
+
+    /// Output format. This prints raw values; references such as
+    /// `${{postgres.DATABASE_URL}}` are kept as written, and Railway-provided
+    /// `RAILWAY_*` variables are left out
+    #[clap(long, value_enum, default_value = "dotenv")]
+    format: VariableFileFormat,
+}
+
+#[derive(Parser)]
+struct ImportArgs {
+    /// File to read variables from
+    file: PathBuf,
+
+    /// The service to import variables into
+    #[clap(short, long)]
+    service: Option<String>,
+
+    /// The environment to import variables into
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Project ID to use (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT_ID")]
+    project: Option<String>,
+
+    /// File format (detected from the file extension by default)
+    #[clap(long, value_enum)]
+    format: Option<VariableFileFormat>,
+
+    /// Keep variables that are missing from the file instead of deleting them
+    #[clap(long)]
+    no_delete: bool,
+
+    /// Show values in the diff instead of redacting them
+    #[clap(long)]
+    show_values: bool,
+
+    /// Skip the confirmation prompt
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    /// Skip triggering deploys after applying the changes
+    #[clap(long)]
+    skip_deploys: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct DiffArgs {
+    /// The environment to compare from
+    #[clap(long)]
+    from: String,
+
+    /// The environment to compare to
+    #[clap(long)]
+    to: String,
+
+    /// The service to compare variables for
+    #[clap(short, long)]
+    service: Option<String>,
+
+    /// Project ID to use (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT_ID")]
+    project: Option<String>,
+
+    /// Show values in the diff instead of redacting them
+    #[clap(long)]
+    show_values: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct ShareArgs {
+    /// Shared variable key(s) to reference
+    #[clap(required = true)]
+    keys: Vec<String>,
+
+    /// Comma-separated service names or IDs to add the references to
+    #[clap(long, value_delimiter = ',', required = true)]
+    to: Vec<String>,
+
+    /// The environment the shared variables live in
+    #[clap(short, long)]
+    environment: Option<String>,
+
+    /// Project ID to use (defaults to linked project)
+    #[clap(short = 'p', long, value_name = "PROJECT_ID")]
+    project: Option<String>,
+
+    /// Skip the confirmation prompt
+    #[clap(short = 'y', long = "yes")]
+    yes: bool,
+
+    /// Skip triggering deploys after applying the changes
+    #[clap(long)]
+    skip_deploys: bool,
+
+    /// Output in JSON format
+    #[clap(long)]
+    json: bool,
+}
+
 pub async fn command(args: Args) -> Result<()> {
     if let Some(cmd) = args.command {
+        if args.shared {
+            return match cmd {
+                Commands::List(list_args) => list_shared_variables(list_args).await,
+                Commands::Set(set_args) => set_variable(set_args, true).await,
+                Commands::Delete(delete_args) => delete_shared_variable(delete_args).await,
+                Commands::Share(share_args) => share_variables(share_args).await,
+                Commands::Export(_) | Commands::Import(_) | Commands::Diff(_) => {
+                    bail!("--shared is only supported with list, set and delete")
+                }
+            };
+        }
         return match cmd {
             Commands::List(list_args) => list_variables(list_args).await,
-            Commands::Set(set_args) => set_variable(set_args).await,
+            Commands::Set(set_args) => set_variable(set_args, false).await,
             Commands::Delete(delete_args) => delete_variable(delete_args).await,
+            Commands::Export(export_args) => export_variables(export_args).await,
+            Commands::Import(import_args) => import_variables(import_args).await,
+            Commands::Diff(diff_args) => diff_variables(diff_args).await,
+            Commands::Share(share_args) => share_variables(share_args).await,
         };
     }
 
+    if args.shared {
+        if args.service.is_some() {
+            bail!(SHARED_WITH_SERVICE);
+        }
+        if let Some(key) = args.set_from_stdin {
+            let value = read_value_from_stdin()?;
+            return set_shared_variables(
+                vec![Variable { key, value }],
+                args.environment,
+                args.project,
+                args.skip_deploys,
+                false,
+            )
+            .await;
+        }
+        if !args.set.is_empty() {
+            return set_shared_variables(
+                args.set,
+                args.environment,
+                args.project,
+                args.skip_deploys,
+                false,
+            )
+            .await;
+        }
+        return list_shared_variables(ListArgs {
+            service: None,
+            environment: args.environment,
+            project: args.project,
+            kv: args.kv,
+            json: args.json,
+        })
+        .await;
+    }
+
     // Legacy behavior: handle --set-from-stdin
     if let Some(key) = args.set_from_stdin {
         let value = read_value_from_stdin()?;
@@ -224,7 +425,7 @@ async fn list_variables(args: ListArgs) -> Result<()> {
     Ok(())
 }
 
-async fn set_variable(args: SetArgs) -> Result<()> {
+async fn set_variable(args: SetArgs, shared: bool) -> Result<()> {
     let variables = if args.stdin {
         if args.variables.len() != 1 {
             bail!("--stdin requires exactly one KEY argument");
@@ -247,6 +448,20 @@ async fn set_variable(args: SetArgs) -> Result<()> {
             .collect::<Result<Vec<_>, _>>()?
     };
 
+    if shared {
+        if args.service.is_some() {
+            bail!(SHARED_WITH_SERVICE);
+        }
+        return set_shared_variables(
+            variables,
+            args.environment,
+            args.project,
+            args.skip_deploys,
+            args.json,
+        )
+        .await;
+    }
+
     set_variables_internal(
         variables,
         args.service,
@@ -294,6 +509,542 @@ async fn delete_variable(args: DeleteArgs) -> Result<()> {
     Ok(())
 }
 
+async fn export_variables(args: ExportArgs) -> Result<()> {
+    let ctx = resolve_service_context(args.project, args.service, args.environment).await?;
+
+    let mut variables = get_unrendered_service_variables(
+        &ctx.client,
+        &ctx.configs,
+        ctx.project_id,
+        ctx.environment_id,
+        ctx.service_id,
+    )
+    .await?;
+    variables.retain(|key, _| !is_railway_provided(key));
+
+    print!("{}", format_variables(&variables, args.format)?);
+    Ok(())
+}
+
+async fn import_variables(args: ImportArgs) -> Result<()> {
+    let contents = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let format = args
+        .format
+        .unwrap_or_else(|| VariableFileFormat::from_path(&args.file));
+    let desired = parse_variables(&contents, format)
+        .with_context(|| format!("Failed to parse {}", args.file.display()))?;
+
+    let ctx = resolve_service_context(args.project, args.service, args.environment).await?;
+    let current = get_unrendered_service_variables(
+        &ctx.client,
+        &ctx.configs,
+        ctx.project_id.clone(),
+        ctx.environment_id.clone(),
+        ctx.service_id.clone(),
+    )
+    .await?;
+
+    let diff = plan_import(&current, &desired, args.no_delete);
+
+    if diff.is_empty() {
+        if args.json {
+            println!("{}", diff.to_json(args.show_values));
+        } else {
+            println!(
+                "Variables for {} are already up to date",
+                ctx.service_name.bold()
+            );
+        }
+        return Ok(());
+    }
+
+    if !args.json {
+        println!(
+            "Changes to {} in {}:",
+            ctx.service_name.bold(),
+            ctx.environment_name.bold()
+        );
+        diff.print(args.show_values);
+        println!();
+    }
+
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default("Apply these changes?", false)?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    let mut variables = BTreeMap::new();
+    for (key, value) in diff
+        .added
+        .iter()
+        .chain(diff.changed.iter().map(|(key, (_, new))| (key, new)))
+    {
+        variables.insert(
+            key.clone(),
+            Some(crate::controllers::config::Variable {
+                value: Some(value.clone()),
+                ..Default::default()
+            }),
+        );
+    }
+    for key in diff.removed.keys() {
+        variables.insert(key.clone(), None);
+    }
+    let patch = EnvironmentConfig {
+        services: BTreeMap::from([(
+            ctx.service_id.clone(),
+            ServiceInstance {
+                variables,
+                ..ServiceInstance::default()
+            },
+        )]),
+        ..EnvironmentConfig::default()
+    };
+
+    let spinner = create_spinner_if(!args.json, "Applying variable changes...".into());
+    let deployed = stage_and_commit_patch(&ctx, patch, !args.skip_deploys).await?;
+
+    if let Some(sp) = spinner {
+        sp.finish_with_message(format!(
+            "Applied {} added, {} changed, {} removed{}",
+            diff.added.len(),
+            diff.changed.len(),
+            diff.removed.len(),
+            if deployed {
+                " and triggered a deploy"
+            } else {
+                ""
+            }
+        ));
+    } else {
+        let mut output = diff.to_json(args.show_values);
+        output["deployed"] = deployed.into();
+        println!("{output}");
+    }
+
+    Ok(())
+}
+
+/// What importing `desired` over `current` changes. Railway-provided
+/// variables are never touched, and `no_delete` keeps anything the file
+/// doesn't mention.
+fn plan_import(
+    current: &BTreeMap<String, String>,
+    desired: &BTreeMap<String, String>,
+    no_delete: bool,
+) -> VariableDiff {
+    let mut diff = VariableDiff::new(current, desired).without_railway_provided();
+    if no_delete {
+        diff.removed.clear();
+    }
+    diff
+}
+
+async fn diff_variables(args: DiffArgs) -> Result<()> {
+    let ctx = resolve_service_context(args.project, args.service, Some(args.from)).await?;
+    let to_environment = get_matched_environment(&ctx.project, args.to)?;
+
+    let from = get_unrendered_service_variables(
+        &ctx.client,
+        &ctx.configs,
+        ctx.project_id.clone(),
+        ctx.environment_id.clone(),
+        ctx.service_id.clone(),
+    )
+    .await?;
+    let to = get_unrendered_service_variables(
+        &ctx.client,
+        &ctx.configs,
+        ctx.project_id.clone(),
+        to_environment.id,
+        ctx.service_id.clone(),
+    )
+    .await?;
+
+    let diff = VariableDiff::new(&from, &to).without_railway_provided();
+
+    if args.json {
+        println!("{}", diff.to_json(args.show_values));
+    } else if diff.is_empty() {
+        println!(
+            "{} has the same variables in {} and {}",
+            ctx.service_name.bold(),
+            ctx.environment_name.bold(),
+            to_environment.name.bold()
+        );
+    } else {
+        println!(
+            "{}: {} -> {}",
+            ctx.service_name.bold(),
+            ctx.environment_name.bold(),
+            to_environment.name.bold()
+        );
+        diff.print(args.show_values);
+    }
+
+    Ok(())
+}
+
+const SHARED_WITH_SERVICE: &str =
+    "Shared variables belong to the environment; --shared cannot be combined with --service";
+
+async fn list_shared_variables(args: ListArgs) -> Result<()> {
+    if args.service.is_some() {
+        bail!(SHARED_WITH_SERVICE);
+    }
+    let ctx = resolve_environment_context(args.project, args.environment).await?;
+
+    let variables = get_shared_variables(
+        &ctx.client,
+        &ctx.configs,
+        ctx.project_id.clone(),
+        ctx.environment_id.clone(),
+    )
+    .await?;
+
+    if args.kv {
+        for (key, value) in variables {
+            println!("{key}={value}");
+        }
+        return Ok(());
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&variables)?);
+        return Ok(());
+    }
+
+    if variables.is_empty() {
+        eprintln!("No shared variables found in {}", ctx.environment_name);
+        return Ok(());
+    }
+
+    let table = Table::new(format!("shared ({})", ctx.environment_name), variables);
+    table.print()?;
+
+    Ok(())
+}
+
+async fn set_shared_variables(
+    variables: Vec<Variable>,
+    environment: Option<String>,
+    project: Option<String>,
+    skip_deploys: bool,
+    json: bool,
+) -> Result<()> {
+    let ctx = resolve_environment_context(project, environment).await?;
+
+    let keys: Vec<String> = variables.iter().map(|v| v.key.clone()).collect();
+    let fmt_keys = keys
+        .iter()
+        .map(|k| k.bold().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let spinner = create_spinner_if(!json, format!("Setting shared {fmt_keys}..."));
+
+    let vars = mutations::variable_collection_upsert::Variables {
+        project_id: ctx.project_id,
+        environment_id: ctx.environment_id,
+        service_id: None,
+        variables: variables.into_iter().map(|v| (v.key, v.value)).collect(),
+        skip_deploys: skip_deploys.then_some(true),
+    };
+
+    post_graphql::<mutations::VariableCollectionUpsert, _>(
+        &ctx.client,
+        ctx.configs.get_backboard(),
+        vars,
+    )
+    .await?;
+
+    if let Some(sp) = spinner {
+        sp.finish_with_message(format!(
+            "Set shared variables {fmt_keys} in {}",
+            ctx.environment_name
+        ));
+        if !std::io::stderr().is_terminal() {
+            println!(
+                "Set shared variables {} in {}",
+                keys.join(", "),
+                ctx.environment_name
+            );
+        }
+    } else {
+        println!(
+            "{}",
+            serde_json::json!({"keys": keys, "shared": true, "set": true})
+        );
+    }
+
+    Ok(())
+}
+
+async fn delete_shared_variable(args: DeleteArgs) -> Result<()> {
+    if args.service.is_some() {
+        bail!(SHARED_WITH_SERVICE);
+    }
+    let ctx = resolve_environment_context(args.project, args.environment).await?;
+
+    let variables = get_shared_variables(
+        &ctx.client,
+        &ctx.configs,
+        ctx.project_id.clone(),
+        ctx.environment_id.clone(),
+    )
+    .await?;
+    if !variables.contains_key(&args.key) {
+        bail!(
+            "Shared variable '{}' not found in {}",
+            args.key,
+            ctx.environment_name
+        );
+    }
+
+    let spinner = create_spinner_if(
+        !args.json,
+        format!("Deleting shared {}...", args.key.bold()),
+    );
+
+    let vars = mutations::variable_delete::Variables {
+        project_id: ctx.project_id,
+        environment_id: ctx.environment_id,
+        name: args.key.clone(),
+        service_id: None,
+    };
+
+    post_graphql::<mutations::VariableDelete, _>(&ctx.client, ctx.configs.get_backboard(), vars)
+        .await?;
+
+    if let Some(sp) = spinner {
+        sp.finish_with_message(format!("Deleted shared variable {}", args.key.bold()));
+    } else {
+        println!(
+            "{}",
+            serde_json::json!({"key": args.key, "shared": true, "deleted": true})
+        );
+    }
+
+    Ok(())
+}
+
+/// One service variable that `share` will point at a shared variable.
+#[derive(Debug, PartialEq)]
+struct ShareChange {
+    service_id: String,
+    service_name: String,
+    key: String,
+    /// Whether the service already had its own value for the key
+    replaces: bool,
+}
+
+fn shared_reference(key: &str) -> String {
+    format!("${{{{shared.{key}}}}}")
+}
+
+/// Works out which `(service, key)` pairs need a reference. Services that
+/// already reference the shared variable are left alone.
+fn plan_share(
+    keys: &[String],
+    services: &[(String, String)],
+    config: &EnvironmentConfig,
+) -> Vec<ShareChange> {
+    let mut changes = Vec::new();
+    for (service_id, service_name) in services {
+        let current = config.services.get(service_id).map(|s| &s.variables);
+        for key in keys {
+            let existing = current
+                .and_then(|vars| vars.get(key))
+                .and_then(|var| var.as_ref())
+                .and_then(|var| var.value.as_deref());
+            if existing == Some(shared_reference(key).as_str()) {
+                continue;
+            }
+            changes.push(ShareChange {
+                service_id: service_id.clone(),
+                service_name: service_name.clone(),
+                key: key.clone(),
+                replaces: existing.is_some(),
+            });
+        }
+    }
+    changes
+}
+
+async fn share_variables(args: ShareArgs) -> Result<()> {
+    let EnvironmentContext {
+        client,
+        configs,
+        project,
+        project_id,
+        environment_id,
+        environment_name,
+        ..
+    } = resolve_environment_context(args.project, args.environment).await?;
+
+    let shared = get_shared_variables(
+        &client,
+        &configs,
+        project_id.clone(),
+        environment_id.clone(),
+    )
+    .await?;
+    let missing: Vec<&str> = args
+        .keys
+        .iter()
+        .filter(|key| !shared.contains_key(*key))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "Shared variable(s) {} not found in {environment_name}. Create them first with `railway variable --shared set KEY=VALUE`",
+            missing.join(", ")
+        );
+    }
+
+    let mut targets: Vec<(String, String)> = Vec::new();
+    for name in args.to.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let service = project
+            .services
+            .edges
+            .iter()
+            .find(|s| s.node.name.eq_ignore_ascii_case(name) || s.node.id == name)
+            .with_context(|| format!("Service '{name}' not found"))?;
+        if !targets.iter().any(|(id, _)| *id == service.node.id) {
+            targets.push((service.node.id.clone(), service.node.name.clone()));
+        }
+    }
+    if targets.is_empty() {
+        bail!("--to needs at least one service");
+    }
+
+    // Encrypted values never match a reference, so decrypt to see which
+    // services already point at the shared variable.
+    let current = fetch_environment_config(&client, &configs, &environment_id, true).await?;
+    let changes = plan_share(&args.keys, &targets, &current.config);
+
+    if changes.is_empty() {
+        if args.json {
+            println!("{}", serde_json::json!({"changes": [], "deployed": false}));
+        } else {
+            println!("Every target service already references these shared variables");
+        }
+        return Ok(());
+    }
+
+    if !args.json {
+        println!("Shared variable references in {}:", environment_name.bold());
+        for change in &changes {
+            let line = format!(
+                "{} {}.{} = {}",
+                if change.replaces { "~" } else { "+" },
+                change.service_name,
+                change.key,
+                shared_reference(&change.key)
+            );
+            if change.replaces {
+                println!("{} {}", line.yellow(), "(replaces its own value)".dimmed());
+            } else {
+                println!("{}", line.green());
+            }
+        }
+        println!();
+    }
+
+    let confirmed = if args.yes {
+        true
+    } else if std::io::stdout().is_terminal() {
+        prompt_confirm_with_default("Apply these changes?", false)?
+    } else {
+        bail!(
+            "Cannot prompt for confirmation in non-interactive mode. Use --yes to skip confirmation."
+        );
+    };
+    if !confirmed {
+        return Ok(());
+    }
+
+    let mut services: BTreeMap<String, ServiceInstance> = BTreeMap::new();
+    for change in &changes {
+        services
+            .entry(change.service_id.clone())
+            .or_default()
+            .variables
+            .insert(
+                change.key.clone(),
+                Some(crate::controllers::config::Variable {
+                    value: Some(shared_reference(&change.key)),
+                    ..Default::default()
+                }),
+            );
+    }
+    let patch = EnvironmentConfig {
+        services,
+        ..EnvironmentConfig::default()
+    };
+
+    // Staging is environment-wide; the service only labels the context.
+    let (service_id, service_name) = targets[0].clone();
+    let ctx = ServiceContext {
+        client,
+        configs,
+        project,
+        project_id,
+        environment_id,
+        environment_name,
+        service_id,
+        service_name,
+    };
+
+    let spinner = create_spinner_if(!args.json, "Applying shared variable references...".into());
+    let deployed = stage_and_commit_patch(&ctx, patch, !args.skip_deploys).await?;
+
+    if let Some(sp) = spinner {
+        let service_count = changes
+            .iter()
+            .map(|c| &c.service_id)
+            .collect::<std::collections::BTreeSet<_>>()
+            .len();
+        sp.finish_with_message(format!(
+            "Added {} reference(s) across {service_count} service(s){}",
+            changes.len(),
+            if deployed {
+                " and triggered deploys"
+            } else {
+                ""
+            }
+        ));
+    } else {
+        let changes: Vec<_> = changes
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "serviceId": c.service_id,
+                    "service": c.service_name,
+                    "key": c.key,
+                    "value": shared_reference(&c.key),
+                    "replaced": c.replaces,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({"changes": changes, "deployed": deployed})
+        );
+    }
+
+    Ok(())
+}
+
 // Legacy helper for --set flag
 async fn set_variables_legacy(
     variables: Vec<Variable>,
@@ -335,7 +1086,7 @@ async fn set_variables_internal(
     let vars = mutations::variable_collection_upsert::Variables {
         project_id: ctx.project_id,
         environment_id: ctx.environment_id,
-        service_id: ctx.service_id,
+        service_id: Some(ctx.service_id),
         variables: variables.into_iter().map(|v| (v.key, v.value)).collect(),
         skip_deploys: skip_deploys.then_some(true),
     };
@@ -381,3 +1132,161 @@ fn read_value_from_stdin() -> Result<String> {
 
     Ok(value.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controllers::config::Variable as ConfigVariable;
+
+    #[test]
+    fn import_round_trip_keeps_references_and_skips_railway_variables() {
+        let staging = BTreeMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "${{postgres.DATABASE_URL}}".to_string(),
+            ),
+            ("LOG_LEVEL".to_string(), "debug".to_string()),
+            (
+                "RAILWAY_PUBLIC_DOMAIN".to_string(),
+                "api-staging.up.railway.app".to_string(),
+            ),
+        ]);
+        let production = BTreeMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "${{postgres.DATABASE_URL}}".to_string(),
+            ),
+            ("LOG_LEVEL".to_string(), "info".to_string()),
+            (
+                "RAILWAY_PUBLIC_DOMAIN".to_string(),
+                "api.up.railway.app".to_string(),
+            ),
+            (
+                "RAILWAY_STATIC_URL".to_string(),
+                "api.up.railway.app".to_string(),
+            ),
+        ]);
+
+        let file = format_variables(&staging, VariableFileFormat::Dotenv).unwrap();
+        let desired = parse_variables(&file, VariableFileFormat::Dotenv).unwrap();
+        let diff = plan_import(&production, &desired, false);
+
+        // The reference survives as written, so it isn't a change.
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(
+            diff.changed.keys().collect::<Vec<_>>(),
+            vec!["LOG_LEVEL"],
+            "only the user variable changes; RAILWAY_* stays untouched"
+        );
+    }
+
+    #[test]
+    fn share_plan_skips_existing_references() {
+        let keys = vec!["DATABASE_URL".to_string(), "SENTRY_DSN".to_string()];
+        let services = vec![
+            ("svc_api".to_string(), "api".to_string()),
+            ("svc_worker".to_string(), "worker".to_string()),
+        ];
+        let var = |value: &str| {
+            Some(ConfigVariable {
+                value: Some(value.to_string()),
+                ..Default::default()
+            })
+        };
+        let config = EnvironmentConfig {
+            services: BTreeMap::from([(
+                "svc_api".to_string(),
+                ServiceInstance {
+                    variables: BTreeMap::from([
+                        ("DATABASE_URL".to_string(), var("${{shared.DATABASE_URL}}")),
+                        ("SENTRY_DSN".to_string(), var("https://old@sentry.io/1")),
+                    ]),
+                    ..ServiceInstance::default()
+                },
+            )]),
+            ..EnvironmentConfig::default()
+        };
+
+        let plan = plan_share(&keys, &services, &config);
+        let summary: Vec<_> = plan
+            .iter()
+            .map(|c| (c.service_name.as_str(), c.key.as_str(), c.replaces))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("api", "SENTRY_DSN", true),
+                ("worker", "DATABASE_URL", false),
+                ("worker", "SENTRY_DSN", false),
+            ]
+        );
+        assert_eq!(shared_reference("KEY"), "${{shared.KEY}}");
+    }
+
+    #[test]
+    fn share_plan_skips_services_that_already_reference() {
+        let keys = vec!["DATABASE_URL".to_string()];
+        let services = vec![
+            ("svc_api".to_string(), "api".to_string()),
+            ("svc_worker".to_string(), "worker".to_string()),
+        ];
+        let referencing = || ServiceInstance {
+            variables: BTreeMap::from([(
+                "DATABASE_URL".to_string(),
+                Some(ConfigVariable {
+                    value: Some("${{shared.DATABASE_URL}}".to_string()),
+                    ..Default::default()
+                }),
+            )]),
+            ..ServiceInstance::default()
+        };
+        let config = EnvironmentConfig {
+            services: BTreeMap::from([
+                ("svc_api".to_string(), referencing()),
+                ("svc_worker".to_string(), referencing()),
+            ]),
+            ..EnvironmentConfig::default()
+        };
+        assert_eq!(plan_share(&keys, &services, &config), vec![]);
+    }
+
+    // Without `decryptVariables` the config holds ciphertext, so a service
+    // that already references the shared variable looks like one with its
+    // own value. `share` fetches the decrypted config for this reason.
+    #[test]
+    fn share_plan_cannot_see_references_in_encrypted_config() {
+        let keys = vec!["DATABASE_URL".to_string()];
+        let services = vec![("svc_api".to_string(), "api".to_string())];
+        let with_value = |value: &str| EnvironmentConfig {
+            services: BTreeMap::from([(
+                "svc_api".to_string(),
+                ServiceInstance {
+                    variables: BTreeMap::from([(
+                        "DATABASE_URL".to_string(),
+                        Some(ConfigVariable {
+                            value: Some(value.to_string()),
+                            ..Default::default()
+                        }),
+                    )]),
+                    ..ServiceInstance::default()
+                },
+            )]),
+            ..EnvironmentConfig::default()
+        };
+
+        let encrypted = with_value("c2VhbGVkOmRhdGFiYXNlLXVybA==");
+        assert_eq!(
+            plan_share(&keys, &services, &encrypted),
+            vec![ShareChange {
+                service_id: "svc_api".to_string(),
+                service_name: "api".to_string(),
+                key: "DATABASE_URL".to_string(),
+                replaces: true,
+            }]
+        );
+
+        let decrypted = with_value("${{shared.DATABASE_URL}}");
+        assert_eq!(plan_share(&keys, &services, &decrypted), vec![]);
+    }
+}
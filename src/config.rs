@@ -3,6 +3,7 @@ use std::{
     fs::{self, File, create_dir_all},
     io::Read,
     path::PathBuf,
+    sync::OnceLock,
 };
 
 use anyhow::{Context, Result, anyhow, bail};
@@ -61,6 +62,9 @@ pub struct StoredSandbox {
     pub environment_id: String,
     pub project_id: Option<String>,
     pub created_at: Option<String>,
+    /// Local-only labels for targeting groups of sandboxes, as in
+    /// `railway sandbox exec --label ci`.
+    pub labels: Option<Vec<String>>,
 }
 
 /// A sandbox template recipe the CLI has built. Templates are
@@ -79,6 +83,11 @@ pub struct StoredSandboxTemplate {
     pub environment_id: String,
     pub instructions: Vec<String>,
     pub base_image_digest: Option<String>,
+    /// Build variables (a Sandboxfile's `ENV`); part of the recipe, so they
+    /// are resent with the instructions on create.
+    pub variables: Option<BTreeMap<String, String>>,
+    /// Default idle timeout for sandboxes created from this template.
+    pub idle_timeout_minutes: Option<i64>,
     pub created_at: Option<String>,
 }
 
@@ -105,8 +114,20 @@ pub struct RailwayConfig {
     /// switching projects must not make `railway code` reach for a box in a
     /// different environment.
     pub code_agents: Option<BTreeMap<String, String>>,
+    /// The named profile commands run under when neither `--profile` nor
+    /// `RAILWAY_PROFILE` picks one. Only read from the default profile's file,
+    /// which is where `railway profile use` records it.
+    pub active_profile: Option<String>,
 }
 
+/// The implicit profile backed by the original `~/.railway/config.json`, so
+/// installs that predate profiles keep working untouched.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profile picked with the global `--profile` flag. Set once in `main` right
+/// after argument parsing, before any command builds a `Configs`.
+static PROFILE_OVERRIDE: OnceLock<String> = OnceLock::new();
+
 // NOTE: no serde derives -- `Configs` itself is never (de)serialized, only
 // its `root_config` is. (A stray `skip_serializing_none` used to sit here;
 // it was inert without serde derives and breaks compilation once the struct
@@ -129,9 +150,22 @@ pub enum Environment {
 }
 
 impl Configs {
+    /// Load the config for the active profile (see [`Self::active_profile`]).
     pub fn new() -> Result<Self> {
-        let root_config_path = Self::root_config_path()?;
+        let profile = Self::active_profile();
+        if profile != DEFAULT_PROFILE && !Self::profile_exists(&profile)? {
+            bail!(RailwayError::ProfileNotFound(profile));
+        }
+        Self::load(Self::profile_config_path(&profile)?)
+    }
+
+    /// Load the config for a specific profile, regardless of which one is
+    /// active. Used by `railway profile` to inspect and edit other profiles.
+    pub fn for_profile(name: &str) -> Result<Self> {
+        Self::load(Self::profile_config_path(name)?)
+    }
 
+    fn load(root_config_path: PathBuf) -> Result<Self> {
         if let Ok(mut file) = File::open(&root_config_path) {
             let mut serialized_config = vec![];
             file.read_to_end(&mut serialized_config)?;
@@ -184,16 +218,142 @@ impl Configs {
         }
     }
 
-    /// Absolute path to the root config file for the current environment.
-    fn root_config_path() -> Result<PathBuf> {
-        let root_config_partial_path = match Self::get_environment_id() {
-            Environment::Production => ".railway/config.json",
-            Environment::Staging => ".railway/config-staging.json",
-            Environment::Dev => ".railway/config-dev.json",
-        };
-
+    /// `~/.railway`, the directory every profile's config lives under.
+    fn railway_dir() -> Result<PathBuf> {
         let home_dir = dirs::home_dir().context("Unable to get home directory")?;
-        Ok(std::path::Path::new(&home_dir).join(root_config_partial_path))
+        Ok(home_dir.join(".railway"))
+    }
+
+    /// File name of the root config for the current environment.
+    fn root_config_file_name() -> &'static str {
+        match Self::get_environment_id() {
+            Environment::Production => "config.json",
+            Environment::Staging => "config-staging.json",
+            Environment::Dev => "config-dev.json",
+        }
+    }
+
+    /// Absolute path to a profile's root config file for the current
+    /// environment. The default profile keeps the pre-profile location; named
+    /// profiles each get their own directory so tokens, refresh state, locks
+    /// and linked projects never mix.
+    fn profile_config_path(profile: &str) -> Result<PathBuf> {
+        let railway_dir = Self::railway_dir()?;
+        let file_name = Self::root_config_file_name();
+        if profile == DEFAULT_PROFILE {
+            return Ok(railway_dir.join(file_name));
+        }
+        Self::validate_profile_name(profile)?;
+        Ok(railway_dir.join("profiles").join(profile).join(file_name))
+    }
+
+    /// Pin the profile for this invocation (the global `--profile` flag).
+    /// Takes precedence over `RAILWAY_PROFILE` and the stored active profile.
+    pub fn set_profile_override(name: &str) {
+        let _ = PROFILE_OVERRIDE.set(name.to_string());
+    }
+
+    /// The profile this invocation runs under: `--profile`, then
+    /// `RAILWAY_PROFILE`, then whatever `railway profile use` recorded, then
+    /// [`DEFAULT_PROFILE`].
+    pub fn active_profile() -> String {
+        if let Some(name) = PROFILE_OVERRIDE.get() {
+            return name.clone();
+        }
+        if let Some(name) = std::env::var(consts::RAILWAY_PROFILE_ENV)
+            .ok()
+            .filter(|name| !name.is_empty())
+        {
+            return name;
+        }
+        Self::stored_active_profile().unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    /// The profile recorded by `railway profile use`, if any.
+    pub fn stored_active_profile() -> Option<String> {
+        let path = Self::profile_config_path(DEFAULT_PROFILE).ok()?;
+        Self::read_root_config(&path)?.active_profile
+    }
+
+    /// Profile names are used as directory names, so keep them to a
+    /// conservative, portable character set.
+    pub fn validate_profile_name(name: &str) -> Result<()> {
+        let valid = !name.is_empty()
+            && name.len() <= 64
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            bail!(
+                "Invalid profile name \"{name}\". Use letters, numbers, '-' and '_' (max 64 characters)."
+            );
+        }
+        Ok(())
+    }
+
+    /// True for the default profile, and for named profiles that have been
+    /// created with `railway profile add`.
+    pub fn profile_exists(name: &str) -> Result<bool> {
+        if name == DEFAULT_PROFILE {
+            return Ok(true);
+        }
+        Self::validate_profile_name(name)?;
+        Ok(Self::railway_dir()?.join("profiles").join(name).is_dir())
+    }
+
+    /// Every known profile, default first, then named profiles alphabetically.
+    pub fn list_profiles() -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let profiles_dir = Self::railway_dir()?.join("profiles");
+        if let Ok(entries) = fs::read_dir(&profiles_dir) {
+            for entry in entries.flatten() {
+                if !entry.path().is_dir() {
+                    continue;
+                }
+                if let Some(name) = entry.file_name().to_str() {
+                    if name != DEFAULT_PROFILE && Self::validate_profile_name(name).is_ok() {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+        names.sort();
+        names.insert(0, DEFAULT_PROFILE.to_string());
+        Ok(names)
+    }
+
+    /// Create an empty named profile. Errors if it already exists.
+    pub fn create_profile(name: &str) -> Result<()> {
+        if name == DEFAULT_PROFILE || Self::profile_exists(name)? {
+            bail!("Profile \"{name}\" already exists.");
+        }
+        let configs = Self::for_profile(name)?;
+        configs.write_credentials()
+    }
+
+    /// Delete a named profile, including its credentials and linked projects.
+    pub fn remove_profile(name: &str) -> Result<()> {
+        if name == DEFAULT_PROFILE {
+            bail!("The default profile cannot be removed. Use `railway logout` to clear it.");
+        }
+        if !Self::profile_exists(name)? {
+            bail!(RailwayError::ProfileNotFound(name.to_string()));
+        }
+        let dir = Self::railway_dir()?.join("profiles").join(name);
+        fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to remove profile directory {}", dir.display()))
+    }
+
+    /// Record the profile later invocations should use by default. Stored in
+    /// the default profile's file, which always exists and is always read.
+    pub fn set_stored_active_profile(name: &str) -> Result<()> {
+        if !Self::profile_exists(name)? {
+            bail!(RailwayError::ProfileNotFound(name.to_string()));
+        }
+        let mut default = Self::for_profile(DEFAULT_PROFILE)?;
+        default.root_config.active_profile =
+            Some(name.to_string()).filter(|name| name != DEFAULT_PROFILE);
+        default.write_merged(false)
     }
 
     /// Re-read the root config from disk, discarding any in-memory state.
@@ -204,8 +364,10 @@ impl Configs {
         Ok(())
     }
 
+    /// Forget the stored credentials. Everything else in the root config,
+    /// including which profile is active, is left as it is.
     pub fn reset(&mut self) -> Result<()> {
-        self.root_config = RailwayConfig::default();
+        self.root_config.user = RailwayUser::default();
         Ok(())
     }
 
@@ -578,7 +740,14 @@ impl Configs {
         let id = sandbox.id.clone();
         let sandboxes = self.root_config.sandboxes.get_or_insert_with(Vec::new);
         match sandboxes.iter_mut().find(|s| s.id == sandbox.id) {
-            Some(existing) => *existing = sandbox,
+            // Labels only live here, so a refresh from the API keeps them.
+            Some(existing) => {
+                let labels = existing.labels.take();
+                *existing = sandbox;
+                if existing.labels.is_none() {
+                    existing.labels = labels;
+                }
+            }
             None => sandboxes.push(sandbox),
         }
         if set_active {
@@ -607,6 +776,32 @@ impl Configs {
         self.root_config.active_sandbox = Some(id.to_string());
     }
 
+    /// Replace a known sandbox's labels. Caller persists with `write()`.
+    pub fn set_sandbox_labels(&mut self, id: &str, labels: Vec<String>) {
+        if let Some(sandbox) = self
+            .root_config
+            .sandboxes
+            .as_mut()
+            .and_then(|sandboxes| sandboxes.iter_mut().find(|s| s.id == id))
+        {
+            sandbox.labels = (!labels.is_empty()).then_some(labels);
+        }
+    }
+
+    /// Known sandboxes carrying `label`, oldest first.
+    pub fn sandboxes_with_label(&self, label: &str) -> Vec<StoredSandbox> {
+        let mut sandboxes: Vec<StoredSandbox> = self
+            .root_config
+            .sandboxes
+            .iter()
+            .flatten()
+            .filter(|s| s.labels.iter().flatten().any(|l| l == label))
+            .cloned()
+            .collect();
+        sandboxes.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        sandboxes
+    }
+
     /// Forget a sandbox (e.g. after destroy), clearing the active pointer if it
     /// referenced this id. Caller persists with `write()`.
     pub fn remove_sandbox(&mut self, id: &str) {
@@ -850,6 +1045,13 @@ impl Configs {
     /// another process in the meantime. Credentials belong to the auth paths, so
     /// an ordinary write never carries them: see [`Self::write_credentials`].
     pub fn write(&self) -> Result<()> {
+        self.write_merged(true)
+    }
+
+    /// [`Self::write`], optionally also keeping the active profile recorded
+    /// on disk: only `railway profile use` owns that field, so other writers
+    /// must not undo a concurrent switch.
+    fn write_merged(&self, adopt_active_profile: bool) -> Result<()> {
         let mut to_write = serde_json::to_value(&self.root_config)?;
         // Re-read immediately before writing so the window in which a
         // concurrent refresh could be lost is microseconds rather than the
@@ -860,6 +1062,9 @@ impl Configs {
             // automatically instead of being silently dropped. Everything that
             // is not a credential (`id`) stays owned by this caller, so
             // `save_user_id` still works.
+            if adopt_active_profile {
+                adopt_disk_active_profile(&mut to_write, &disk);
+            }
             let merged = RailwayUser {
                 id: self.root_config.user.id.clone(),
                 ..disk.user
@@ -875,7 +1080,10 @@ impl Configs {
     /// ([`Self::save_oauth_tokens`]), a dead grant
     /// ([`Self::clear_oauth_tokens`]), and logout.
     pub(crate) fn write_credentials(&self) -> Result<()> {
-        let value = serde_json::to_value(&self.root_config)?;
+        let mut value = serde_json::to_value(&self.root_config)?;
+        if let Some(disk) = Self::read_root_config(&self.root_config_path) {
+            adopt_disk_active_profile(&mut value, &disk);
+        }
         self.write_value(&value)
     }
 
@@ -1042,6 +1250,79 @@ mod tests {
 
         assert!(has_credentials);
     }
+
+    #[test]
+    fn profile_names_are_restricted_to_path_safe_characters() {
+        assert!(Configs::validate_profile_name("work").is_ok());
+        assert!(Configs::validate_profile_name("acme-prod_2").is_ok());
+        assert!(Configs::validate_profile_name("").is_err());
+        assert!(Configs::validate_profile_name("../escape").is_err());
+        assert!(Configs::validate_profile_name("with space").is_err());
+    }
+
+    #[test]
+    fn named_profiles_get_their_own_config_file() {
+        let (default, work) = with_env_vars(&[("RAILWAY_ENV", None)], || {
+            (
+                Configs::profile_config_path(DEFAULT_PROFILE).unwrap(),
+                Configs::profile_config_path("work").unwrap(),
+            )
+        });
+        assert!(default.ends_with(".railway/config.json"));
+        assert!(work.ends_with(".railway/profiles/work/config.json"));
+    }
+
+    #[test]
+    fn logout_keeps_the_active_profile_and_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let mut configs = Configs::for_test(path.clone());
+        configs.root_config.user.token = Some("secret".into());
+        configs.root_config.active_profile = Some("work".into());
+        configs.root_config.editor = Some("vim".into());
+        configs.write_credentials().unwrap();
+
+        let mut stale = Configs::for_test(path.clone());
+        stale.reload().unwrap();
+        // A concurrent `railway profile use personal` lands in between.
+        let mut switched = Configs::for_test(path.clone());
+        switched.reload().unwrap();
+        switched.root_config.active_profile = Some("personal".into());
+        switched.write_merged(false).unwrap();
+
+        stale.reset().unwrap();
+        stale.write_credentials().unwrap();
+
+        let mut after = Configs::for_test(path);
+        after.reload().unwrap();
+        assert_eq!(after.root_config.user.token, None);
+        assert_eq!(
+            after.root_config.active_profile.as_deref(),
+            Some("personal")
+        );
+        assert_eq!(after.root_config.editor.as_deref(), Some("vim"));
+    }
+
+    #[test]
+    fn railway_profile_env_selects_active_profile() {
+        let active = with_env_vars(
+            &[("RAILWAY_PROFILE", Some("personal"))],
+            Configs::active_profile,
+        );
+        assert_eq!(active, "personal");
+    }
+}
+
+fn adopt_disk_active_profile(to_write: &mut serde_json::Value, disk: &RailwayConfig) {
+    match (&disk.active_profile, to_write.as_object_mut()) {
+        (Some(profile), Some(object)) => {
+            object.insert("activeProfile".to_string(), profile.clone().into());
+        }
+        (None, Some(object)) => {
+            object.remove("activeProfile");
+        }
+        _ => {}
+    }
 }
 
 /// How long to wait for the config lock before giving up and proceeding without
@@ -7,6 +7,7 @@ pub const RAILWAY_API_TOKEN_ENV: &str = "RAILWAY_API_TOKEN";
 pub const RAILWAY_PROJECT_ID_ENV: &str = "RAILWAY_PROJECT_ID";
 pub const RAILWAY_ENVIRONMENT_ID_ENV: &str = "RAILWAY_ENVIRONMENT_ID";
 pub const RAILWAY_SERVICE_ID_ENV: &str = "RAILWAY_SERVICE_ID";
+pub const RAILWAY_PROFILE_ENV: &str = "RAILWAY_PROFILE";
 pub const RAILWAY_CALLER_ENV: &str = "RAILWAY_CALLER";
 pub const RAILWAY_AGENT_SESSION_ENV: &str = "RAILWAY_AGENT_SESSION";
 pub const RAILWAY_INSTALL_REQUEST_ID_ENV: &str = "RAILWAY_INSTALL_REQUEST_ID";
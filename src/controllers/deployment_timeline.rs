@@ -0,0 +1,460 @@
+//! Phase timeline for a single deployment, built from `deploymentEvents`.
+//!
+//! Each event marks one step of the deploy pipeline. The time between the
+//! deployment being created and its first event is reported as a synthetic
+//! `QUEUED` phase, so a deploy stuck behind others shows up as such instead of
+//! as a slow snapshot.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    client::post_graphql,
+    gql::queries::{self, deployment_events},
+};
+
+const QUEUED_STEP: &str = "QUEUED";
+
+/// Statuses after which nothing in the pipeline is still running.
+const TERMINAL_STATUSES: &[&str] = &["SUCCESS", "FAILED", "CRASHED", "REMOVED", "SKIPPED"];
+
+/// Phases that changed by less than this are noise, not regressions.
+const REGRESSION_THRESHOLD_MS: i64 = 1_000;
+
+/// One `deploymentEvents` entry, decoupled from the generated GraphQL types.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineEvent {
+    pub step: String,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub attempt: Option<i64>,
+    pub max_attempts: Option<i64>,
+    pub duration_ms: Option<i64>,
+    pub detail: Option<String>,
+    pub error: Option<String>,
+    pub skipped: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelinePhase {
+    pub step: String,
+    pub label: String,
+    pub started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Milliseconds from the deployment being created to this phase starting.
+    pub offset_ms: i64,
+    /// `None` while the phase is still running.
+    pub duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempt: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub skipped: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentTimeline {
+    pub deployment_id: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub total_ms: i64,
+    pub phases: Vec<TimelinePhase>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseDelta {
+    pub step: String,
+    pub label: String,
+    pub baseline_ms: Option<i64>,
+    pub current_ms: Option<i64>,
+    pub delta_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineComparison {
+    pub deployment_id: String,
+    pub baseline_id: String,
+    pub total_delta_ms: i64,
+    pub phases: Vec<PhaseDelta>,
+    /// Human-readable reasons the deployment was slower or failed, worst first.
+    pub findings: Vec<String>,
+}
+
+pub async fn fetch_timeline(
+    client: &reqwest::Client,
+    backboard: &str,
+    deployment_id: &str,
+) -> Result<DeploymentTimeline> {
+    let data = post_graphql::<queries::DeploymentEvents, _>(
+        client,
+        backboard,
+        deployment_events::Variables {
+            id: deployment_id.to_string(),
+        },
+    )
+    .await
+    .with_context(|| format!("Failed to fetch events for deployment {deployment_id}"))?;
+
+    let deployment = data.deployment;
+    let events = data
+        .deployment_events
+        .edges
+        .into_iter()
+        .map(|edge| TimelineEvent::from(edge.node))
+        .collect();
+    Ok(build_timeline(
+        deployment.id,
+        format!("{:?}", deployment.status),
+        deployment.created_at,
+        deployment.status_updated_at,
+        events,
+        Utc::now(),
+    ))
+}
+
+impl From<deployment_events::DeploymentEventsDeploymentEventsEdgesNode> for TimelineEvent {
+    fn from(node: deployment_events::DeploymentEventsDeploymentEventsEdgesNode) -> Self {
+        let payload = node.payload;
+        TimelineEvent {
+            step: format!("{:?}", node.step),
+            created_at: node.created_at,
+            completed_at: node.completed_at,
+            attempt: payload.as_ref().and_then(|p| p.attempt),
+            max_attempts: payload.as_ref().and_then(|p| p.max_attempts),
+            duration_ms: payload.as_ref().and_then(|p| p.duration_ms),
+            detail: payload
+                .as_ref()
+                .and_then(|p| p.detail.clone().or_else(|| p.reason.clone())),
+            error: payload.as_ref().and_then(|p| p.error.clone()),
+            skipped: payload.as_ref().and_then(|p| p.skipped).unwrap_or(false),
+        }
+    }
+}
+
+pub fn build_timeline(
+    deployment_id: String,
+    status: String,
+    created_at: DateTime<Utc>,
+    status_updated_at: Option<DateTime<Utc>>,
+    mut events: Vec<TimelineEvent>,
+    now: DateTime<Utc>,
+) -> DeploymentTimeline {
+    events.sort_by_key(|event| event.created_at);
+    // A finished deployment won't send end events for phases it abandoned;
+    // they ended when the deployment did, not now.
+    let closed_at = status_updated_at.filter(|_| TERMINAL_STATUSES.contains(&status.as_str()));
+
+    let mut phases = Vec::with_capacity(events.len() + 1);
+    if let Some(first) = events.first() {
+        if first.created_at > created_at {
+            phases.push(TimelinePhase {
+                step: QUEUED_STEP.to_string(),
+                label: step_label(QUEUED_STEP).to_string(),
+                started_at: created_at,
+                completed_at: Some(first.created_at),
+                offset_ms: 0,
+                duration_ms: Some(millis(created_at, first.created_at)),
+                attempt: None,
+                max_attempts: None,
+                detail: None,
+                error: None,
+                skipped: false,
+            });
+        }
+    }
+    for event in events {
+        let completed_at = event.completed_at.or(closed_at);
+        let duration_ms = event
+            .duration_ms
+            .or_else(|| completed_at.map(|end| millis(event.created_at, end)));
+        phases.push(TimelinePhase {
+            label: step_label(&event.step).to_string(),
+            offset_ms: millis(created_at, event.created_at),
+            started_at: event.created_at,
+            completed_at,
+            duration_ms,
+            step: event.step,
+            attempt: event.attempt,
+            max_attempts: event.max_attempts,
+            detail: event.detail,
+            error: event.error,
+            skipped: event.skipped,
+        });
+    }
+
+    let end = phases
+        .iter()
+        .map(|phase| match phase.duration_ms {
+            Some(duration) => phase.offset_ms + duration,
+            None => millis(created_at, now),
+        })
+        .max()
+        .or_else(|| status_updated_at.map(|updated| millis(created_at, updated)))
+        .unwrap_or(0);
+
+    DeploymentTimeline {
+        deployment_id,
+        status,
+        created_at,
+        total_ms: end,
+        phases,
+    }
+}
+
+/// Compare `current` against `baseline`, phase by phase. Repeated phases
+/// (healthcheck attempts) are summed.
+pub fn compare_timelines(
+    current: &DeploymentTimeline,
+    baseline: &DeploymentTimeline,
+) -> TimelineComparison {
+    let current_totals = totals_by_step(current);
+    let baseline_totals = totals_by_step(baseline);
+
+    let mut steps: Vec<&String> = current
+        .phases
+        .iter()
+        .chain(baseline.phases.iter())
+        .map(|phase| &phase.step)
+        .collect();
+    let mut seen = std::collections::HashSet::new();
+    steps.retain(|step| seen.insert(*step));
+
+    let phases: Vec<PhaseDelta> = steps
+        .into_iter()
+        .map(|step| {
+            let current_ms = current_totals.get(step).map(|t| t.duration_ms);
+            let baseline_ms = baseline_totals.get(step).map(|t| t.duration_ms);
+            PhaseDelta {
+                step: step.clone(),
+                label: step_label(step).to_string(),
+                baseline_ms,
+                current_ms,
+                delta_ms: current_ms.unwrap_or(0) - baseline_ms.unwrap_or(0),
+            }
+        })
+        .collect();
+
+    let mut findings = Vec::new();
+    if let Some(failed) = current.phases.iter().find(|phase| phase.error.is_some()) {
+        findings.push(format!(
+            "{} failed: {}",
+            failed.label,
+            failed.error.as_deref().unwrap_or_default()
+        ));
+    }
+    let attempts = |totals: &BTreeMap<String, StepTotal>| {
+        totals.get("HEALTHCHECK").map(|t| t.count).unwrap_or(0)
+    };
+    let (now_attempts, before_attempts) = (attempts(&current_totals), attempts(&baseline_totals));
+    if now_attempts > before_attempts && before_attempts > 0 {
+        findings.push(format!(
+            "Healthcheck needed {now_attempts} attempts (was {before_attempts})"
+        ));
+    }
+
+    let mut regressions: Vec<&PhaseDelta> = phases
+        .iter()
+        .filter(|delta| delta.delta_ms >= REGRESSION_THRESHOLD_MS)
+        .collect();
+    regressions.sort_by_key(|delta| std::cmp::Reverse(delta.delta_ms));
+    for delta in regressions {
+        findings.push(match delta.baseline_ms {
+            Some(baseline_ms) => format!(
+                "{} took {} longer ({} vs {})",
+                delta.label,
+                format_duration(delta.delta_ms),
+                format_duration(delta.current_ms.unwrap_or(0)),
+                format_duration(baseline_ms)
+            ),
+            None => format!(
+                "{} is new and took {}",
+                delta.label,
+                format_duration(delta.delta_ms)
+            ),
+        });
+    }
+
+    TimelineComparison {
+        deployment_id: current.deployment_id.clone(),
+        baseline_id: baseline.deployment_id.clone(),
+        total_delta_ms: current.total_ms - baseline.total_ms,
+        phases,
+        findings,
+    }
+}
+
+struct StepTotal {
+    duration_ms: i64,
+    count: usize,
+}
+
+fn totals_by_step(timeline: &DeploymentTimeline) -> BTreeMap<String, StepTotal> {
+    let mut totals: BTreeMap<String, StepTotal> = BTreeMap::new();
+    for phase in &timeline.phases {
+        let total = totals.entry(phase.step.clone()).or_insert(StepTotal {
+            duration_ms: 0,
+            count: 0,
+        });
+        total.duration_ms += phase.duration_ms.unwrap_or(0);
+        total.count += 1;
+    }
+    totals
+}
+
+pub fn step_label(step: &str) -> &str {
+    match step {
+        QUEUED_STEP => "Queued",
+        "SNAPSHOT_CODE" => "Snapshot code",
+        "BUILD_IMAGE" => "Build image",
+        "PUBLISH_IMAGE" => "Push image",
+        "WAIT_FOR_DEPENDENCIES" => "Wait for dependencies",
+        "MIGRATE_VOLUMES" => "Migrate volumes",
+        "PRE_DEPLOY_COMMAND" => "Pre-deploy command",
+        "CREATE_CONTAINER" => "Create container",
+        "HEALTHCHECK" => "Healthcheck",
+        "CONFIGURE_NETWORK" => "Switch traffic",
+        "DRAIN_INSTANCES" => "Drain old instances",
+        other => other,
+    }
+}
+
+/// `850ms`, `12.4s`, `3m 05s`.
+pub fn format_duration(ms: i64) -> String {
+    let sign = if ms < 0 { "-" } else { "" };
+    let ms = ms.abs();
+    if ms < 1_000 {
+        format!("{sign}{ms}ms")
+    } else if ms < 60_000 {
+        format!("{sign}{:.1}s", ms as f64 / 1_000.0)
+    } else {
+        format!("{sign}{}m {:02}s", ms / 60_000, (ms % 60_000) / 1_000)
+    }
+}
+
+fn millis(from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+    (to - from).num_milliseconds().max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    fn event(step: &str, start: i64, end: Option<i64>) -> TimelineEvent {
+        TimelineEvent {
+            step: step.to_string(),
+            created_at: at(start),
+            completed_at: end.map(at),
+            ..TimelineEvent::default()
+        }
+    }
+
+    fn timeline(id: &str, events: Vec<TimelineEvent>) -> DeploymentTimeline {
+        build_timeline(
+            id.to_string(),
+            "SUCCESS".to_string(),
+            at(0),
+            None,
+            events,
+            at(0) + Duration::hours(1),
+        )
+    }
+
+    #[test]
+    fn queue_time_becomes_its_own_phase() {
+        let timeline = timeline(
+            "d1",
+            vec![
+                event("BUILD_IMAGE", 10, Some(70)),
+                event("SNAPSHOT_CODE", 5, Some(10)),
+            ],
+        );
+        let steps: Vec<_> = timeline.phases.iter().map(|p| p.step.as_str()).collect();
+        assert_eq!(steps, vec!["QUEUED", "SNAPSHOT_CODE", "BUILD_IMAGE"]);
+        assert_eq!(timeline.phases[0].duration_ms, Some(5_000));
+        assert_eq!(timeline.phases[2].offset_ms, 10_000);
+        assert_eq!(timeline.total_ms, 70_000);
+    }
+
+    #[test]
+    fn running_phase_has_no_duration() {
+        let timeline = timeline("d1", vec![event("HEALTHCHECK", 0, None)]);
+        assert_eq!(timeline.phases[0].duration_ms, None);
+        assert_eq!(timeline.total_ms, 3_600_000);
+    }
+
+    #[test]
+    fn finished_deployment_closes_open_phases_at_its_last_status_change() {
+        let timeline = build_timeline(
+            "d1".to_string(),
+            "FAILED".to_string(),
+            at(0),
+            Some(at(90)),
+            vec![
+                event("BUILD_IMAGE", 0, Some(60)),
+                event("HEALTHCHECK", 60, None),
+            ],
+            at(0) + Duration::hours(1),
+        );
+        assert_eq!(timeline.phases[1].completed_at, Some(at(90)));
+        assert_eq!(timeline.phases[1].duration_ms, Some(30_000));
+        assert_eq!(timeline.total_ms, 90_000);
+    }
+
+    #[test]
+    fn comparison_explains_slower_phases_and_extra_healthchecks() {
+        let baseline = timeline(
+            "old",
+            vec![
+                event("BUILD_IMAGE", 0, Some(60)),
+                event("HEALTHCHECK", 60, Some(65)),
+            ],
+        );
+        let mut failed = event("HEALTHCHECK", 160, Some(190));
+        failed.error = Some("service unavailable".into());
+        let current = timeline(
+            "new",
+            vec![
+                event("BUILD_IMAGE", 0, Some(150)),
+                event("HEALTHCHECK", 150, Some(160)),
+                failed,
+            ],
+        );
+
+        let comparison = compare_timelines(&current, &baseline);
+        assert_eq!(comparison.total_delta_ms, 125_000);
+        assert_eq!(
+            comparison.findings,
+            vec![
+                "Healthcheck failed: service unavailable",
+                "Healthcheck needed 2 attempts (was 1)",
+                "Build image took 1m 30s longer (2m 30s vs 1m 00s)",
+                "Healthcheck took 35.0s longer (40.0s vs 5.0s)",
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(850), "850ms");
+        assert_eq!(format_duration(12_400), "12.4s");
+        assert_eq!(format_duration(185_000), "3m 05s");
+        assert_eq!(format_duration(-2_000), "-2.0s");
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::{BTreeMap, HashMap};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_yaml::Value as YamlValue;
 
 use super::ports::generate_port;
 use crate::controllers::config::ServiceInstance;
@@ -60,6 +61,79 @@ pub struct DockerComposeService {
     pub extra_hosts: Vec<String>,
 }
 
+/// A user-written compose file, as `railway import compose` reads it. Only
+/// what the import preview checks the converted patch against is kept;
+/// Railway converts the file itself.
+#[derive(Debug, Deserialize)]
+pub struct ImportedComposeFile {
+    #[serde(default)]
+    pub services: BTreeMap<String, ImportedComposeService>,
+}
+
+impl ImportedComposeFile {
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportedComposeService {
+    #[serde(default, deserialize_with = "de_environment")]
+    pub environment: BTreeMap<String, String>,
+    #[serde(default, deserialize_with = "de_volumes")]
+    pub volumes: Vec<String>,
+}
+
+fn scalar_string(value: &YamlValue) -> Option<String> {
+    match value {
+        YamlValue::String(s) => Some(s.clone()),
+        YamlValue::Number(n) => Some(n.to_string()),
+        YamlValue::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// `environment:` as a `KEY: value` map or a `KEY=value` list. Keys without a
+/// value (pass-through from the host) become empty strings.
+fn de_environment<'de, D: Deserializer<'de>>(d: D) -> Result<BTreeMap<String, String>, D::Error> {
+    Ok(match YamlValue::deserialize(d)? {
+        YamlValue::Mapping(map) => map
+            .iter()
+            .filter_map(|(k, v)| Some((scalar_string(k)?, scalar_string(v).unwrap_or_default())))
+            .collect(),
+        YamlValue::Sequence(items) => items
+            .iter()
+            .filter_map(scalar_string)
+            .map(|item| match item.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => (item, String::new()),
+            })
+            .collect(),
+        _ => BTreeMap::new(),
+    })
+}
+
+/// Volumes in short (`"data:/var/lib/data"`) or long (`{ source, target }`)
+/// syntax, normalized to the short syntax.
+fn de_volumes<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+    let YamlValue::Sequence(volumes) = YamlValue::deserialize(d)? else {
+        return Ok(Vec::new());
+    };
+    Ok(volumes
+        .iter()
+        .filter_map(|volume| match volume {
+            YamlValue::Mapping(map) => {
+                let target = map.get("target").and_then(scalar_string)?;
+                Some(match map.get("source").and_then(scalar_string) {
+                    Some(source) => format!("{source}:{target}"),
+                    None => target,
+                })
+            }
+            volume => scalar_string(volume),
+        })
+        .collect())
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ComposeServiceStatus {
     #[serde(rename = "Service")]
@@ -216,4 +290,46 @@ mod tests {
         assert!(mapping.contains_key(&8080));
         assert!(mapping.contains_key(&5432));
     }
+
+    #[test]
+    fn test_parse_compose_short_and_long_syntax() {
+        let compose = ImportedComposeFile::from_yaml(
+            r#"
+services:
+  web:
+    image: nginx:1.27
+    command: ["nginx", "-g", "daemon off;"]
+    ports:
+      - "8080:80"
+    environment:
+      - MODE=production
+      - PASSTHROUGH
+    volumes:
+      - static:/usr/share/nginx/html
+      - type: bind
+        source: ./conf
+        target: /etc/nginx/conf.d
+  db:
+    image: postgres:16
+    environment:
+      POSTGRES_PASSWORD: secret
+      PORT: 5432
+volumes:
+  static:
+"#,
+        )
+        .unwrap();
+
+        let web = &compose.services["web"];
+        assert_eq!(web.environment["MODE"], "production");
+        assert_eq!(web.environment["PASSTHROUGH"], "");
+        assert_eq!(
+            web.volumes,
+            vec!["static:/usr/share/nginx/html", "./conf:/etc/nginx/conf.d"]
+        );
+
+        let db = &compose.services["db"];
+        assert_eq!(db.environment["PORT"], "5432");
+        assert!(db.volumes.is_empty());
+    }
 }
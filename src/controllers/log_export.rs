@@ -0,0 +1,868 @@
+//! `railway logs export`: pages through a time window of logs and writes them
+//! to rotated NDJSON files and/or an OTLP/HTTP logs endpoint.
+//!
+//! The log queries return at most a page of lines per call and don't say
+//! whether more exist, so the window is walked in fixed-size slices and any
+//! slice that comes back full is split in half and re-fetched. That keeps the
+//! output chronological without depending on which end of a window the API
+//! truncates from. Progress is checkpointed per service and log type after
+//! every slice, so an interrupted export resumes where it stopped.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use flate2::{Compression, write::GzEncoder};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    controllers::deployment::{
+        FetchDnsQueryLogsParams, FetchLogsParams, FetchNetworkFlowLogsParams, fetch_build_logs,
+        fetch_deploy_logs, fetch_dns_query_logs, fetch_http_logs, fetch_network_flow_logs,
+    },
+    util::logs::{DnsQueryLogLike, HttpLogLike, NetworkFlowLogLike, log_json_map},
+};
+
+/// Lines requested per query. A slice that returns this many is assumed to
+/// have been truncated and is split.
+const EXPORT_PAGE_LIMIT: i64 = 500;
+/// Size of the slices the export window is first cut into.
+const INITIAL_SLICE: ChronoDuration = ChronoDuration::hours(1);
+/// Slices are not split below this; a second with more than a page of lines
+/// per deployment is exported as far as the API returns it.
+const MIN_SLICE: ChronoDuration = ChronoDuration::seconds(1);
+/// Records per OTLP request.
+const OTLP_BATCH_SIZE: usize = 1000;
+/// Resume checkpoint, kept next to the exported files.
+pub const EXPORT_STATE_FILE: &str = ".railway-export-state.json";
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportLogType {
+    Deploy,
+    Build,
+    Http,
+    Network,
+    Dns,
+}
+
+impl ExportLogType {
+    /// Deploy, build and HTTP logs are queried per deployment; network and
+    /// DNS logs per environment and service.
+    pub fn is_per_deployment(self) -> bool {
+        matches!(self, Self::Deploy | Self::Build | Self::Http)
+    }
+}
+
+impl fmt::Display for ExportLogType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deploy => write!(f, "deploy"),
+            Self::Build => write!(f, "build"),
+            Self::Http => write!(f, "http"),
+            Self::Network => write!(f, "network"),
+            Self::Dns => write!(f, "dns"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Newline-delimited JSON
+    Ndjson,
+    /// Gzip-compressed newline-delimited JSON
+    Gzip,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Gzip => "ndjson.gz",
+        }
+    }
+}
+
+/// One stream of logs to export: a log type for a service.
+#[derive(Debug, Clone)]
+pub struct ExportSource {
+    pub service_id: String,
+    pub service_name: String,
+    pub log_type: ExportLogType,
+    /// Deployments to query for per-deployment log types.
+    pub deployment_ids: Vec<String>,
+}
+
+impl ExportSource {
+    /// Checkpoint key. Uses the id so renaming a service mid-export doesn't
+    /// restart it.
+    fn key(&self) -> String {
+        format!("{}/{}", self.service_id, self.log_type)
+    }
+
+    /// File name stem for this source's output files.
+    fn file_stem(&self) -> String {
+        let name: String = self
+            .service_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{name}-{}", self.log_type)
+    }
+}
+
+/// A single exported log line.
+#[derive(Debug, Clone)]
+pub struct ExportRecord {
+    pub timestamp: DateTime<Utc>,
+    pub value: Value,
+}
+
+/// Resume checkpoint for an export directory.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportState {
+    /// The resolved window. Stored so resuming a `--since 7d` export keeps
+    /// the original window instead of re-resolving it against a later now.
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub filter: Option<String>,
+    /// The `--since`/`--until` arguments as given. Relative ones resolve
+    /// differently on every run, so a resume compares these, not the window.
+    pub since_arg: String,
+    pub until_arg: Option<String>,
+    /// Per-source end of the last fully exported slice.
+    #[serde(default)]
+    pub cursors: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl ExportState {
+    pub fn new(
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        filter: Option<String>,
+        since_arg: String,
+        until_arg: Option<String>,
+    ) -> Self {
+        Self {
+            since,
+            until,
+            filter,
+            since_arg,
+            until_arg,
+            cursors: BTreeMap::new(),
+        }
+    }
+
+    /// Whether this checkpoint was started with the same `--since`/`--until`.
+    pub fn requested_with(&self, since_arg: &str, until_arg: Option<&str>) -> bool {
+        self.since_arg == since_arg && self.until_arg.as_deref() == until_arg
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        crate::util::write_atomic(path, &serde_json::to_string_pretty(self)?)
+    }
+
+    fn cursor(&self, source: &ExportSource) -> DateTime<Utc> {
+        self.cursors
+            .get(&source.key())
+            .copied()
+            .unwrap_or(self.since)
+            .max(self.since)
+    }
+}
+
+pub struct ExportFetcher<'a> {
+    pub client: &'a Client,
+    pub backboard: &'a str,
+    pub environment_id: &'a str,
+    pub filter: Option<String>,
+}
+
+impl ExportFetcher<'_> {
+    /// Fetch one slice for a source. The flag is true when any query came
+    /// back full, i.e. the slice may be truncated.
+    async fn fetch_slice(
+        &self,
+        source: &ExportSource,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<(Vec<ExportRecord>, bool)> {
+        let mut records = Vec::new();
+        let mut saturated = false;
+        let base = |deployment_id: Option<&str>| {
+            let mut fields = Map::new();
+            fields.insert("service".into(), source.service_name.clone().into());
+            fields.insert("serviceId".into(), source.service_id.clone().into());
+            fields.insert("type".into(), source.log_type.to_string().into());
+            if let Some(id) = deployment_id {
+                fields.insert("deploymentId".into(), id.into());
+            }
+            fields
+        };
+
+        if source.log_type.is_per_deployment() {
+            for deployment_id in &source.deployment_ids {
+                let before = records.len();
+                let params = FetchLogsParams {
+                    client: self.client,
+                    backboard: self.backboard,
+                    deployment_id: deployment_id.clone(),
+                    limit: Some(EXPORT_PAGE_LIMIT),
+                    filter: self.filter.clone(),
+                    start_date: Some(start),
+                    end_date: Some(end),
+                };
+                let fields = base(Some(deployment_id));
+                match source.log_type {
+                    ExportLogType::Deploy => {
+                        fetch_deploy_logs(params, |log| {
+                            records.push(log_record(&log.timestamp, log_json_map(&log), &fields))
+                        })
+                        .await?
+                    }
+                    ExportLogType::Build => {
+                        fetch_build_logs(params, |log| {
+                            records.push(log_record(&log.timestamp, log_json_map(&log), &fields))
+                        })
+                        .await?
+                    }
+                    _ => {
+                        fetch_http_logs(params, |log| {
+                            records.push(serialized_record(
+                                HttpLogLike::timestamp(&log),
+                                &log,
+                                &fields,
+                            ))
+                        })
+                        .await?
+                    }
+                }
+                saturated |= (records.len() - before) as i64 >= EXPORT_PAGE_LIMIT;
+            }
+        } else {
+            let fields = base(None);
+            if source.log_type == ExportLogType::Network {
+                fetch_network_flow_logs(
+                    FetchNetworkFlowLogsParams {
+                        client: self.client,
+                        backboard: self.backboard,
+                        environment_id: self.environment_id.to_string(),
+                        service_id: Some(source.service_id.clone()),
+                        limit: Some(EXPORT_PAGE_LIMIT),
+                        filter: self.filter.clone(),
+                        start_date: Some(start),
+                        end_date: Some(end),
+                    },
+                    |log| records.push(serialized_record(log.capture_end(), &log, &fields)),
+                )
+                .await?;
+            } else {
+                fetch_dns_query_logs(
+                    FetchDnsQueryLogsParams {
+                        client: self.client,
+                        backboard: self.backboard,
+                        environment_id: self.environment_id.to_string(),
+                        service_id: Some(source.service_id.clone()),
+                        limit: Some(EXPORT_PAGE_LIMIT),
+                        filter: self.filter.clone(),
+                        start_date: Some(start),
+                        end_date: Some(end),
+                    },
+                    |log| records.push(serialized_record(log.queried_at(), &log, &fields)),
+                )
+                .await?;
+            }
+            saturated = records.len() as i64 >= EXPORT_PAGE_LIMIT;
+        }
+
+        Ok((records, saturated))
+    }
+}
+
+fn log_record(
+    timestamp: &str,
+    fields: std::collections::HashMap<String, Value>,
+    extra: &Map<String, Value>,
+) -> ExportRecord {
+    let mut value: Map<String, Value> = fields.into_iter().collect();
+    value.extend(extra.clone());
+    ExportRecord {
+        timestamp: crate::util::logs::parse_log_timestamp(timestamp),
+        value: Value::Object(value),
+    }
+}
+
+fn serialized_record<T: Serialize>(
+    timestamp: &str,
+    log: &T,
+    extra: &Map<String, Value>,
+) -> ExportRecord {
+    let mut value = match serde_json::to_value(log) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    value.extend(extra.clone());
+    ExportRecord {
+        timestamp: crate::util::logs::parse_log_timestamp(timestamp),
+        value: Value::Object(value),
+    }
+}
+
+/// Export one source from its checkpoint to the end of the window. Returns
+/// the number of records written.
+pub async fn export_source(
+    fetcher: &ExportFetcher<'_>,
+    source: &ExportSource,
+    state: &mut ExportState,
+    state_path: &Path,
+    sinks: &mut ExportSinks,
+) -> Result<u64> {
+    let until = state.until;
+    let mut cursor = state.cursor(source);
+    let mut exported = 0u64;
+
+    while cursor < until {
+        let mut slices = VecDeque::from([(cursor, (cursor + INITIAL_SLICE).min(until))]);
+        while let Some((start, end)) = slices.pop_front() {
+            let (mut records, saturated) = fetcher.fetch_slice(source, start, end).await?;
+            if saturated && end - start > MIN_SLICE {
+                let mid = start + (end - start) / 2;
+                slices.push_front((mid, end));
+                slices.push_front((start, mid));
+                continue;
+            }
+            if saturated {
+                crate::util::reporter::warn(
+                    "LOG_EXPORT_TRUNCATED",
+                    format!(
+                        "{} {} logs around {start} exceed {EXPORT_PAGE_LIMIT} lines per second; some lines may be missing",
+                        source.service_name, source.log_type
+                    ),
+                    None,
+                );
+            }
+
+            let unreadable = keep_slice(&mut records, start, end);
+            if unreadable > 0 {
+                crate::util::reporter::warn(
+                    "LOG_EXPORT_UNREADABLE_TIMESTAMP",
+                    format!(
+                        "{unreadable} {} {} log line(s) around {start} have no readable timestamp; they are exported first in their slice",
+                        source.service_name, source.log_type
+                    ),
+                    None,
+                );
+            }
+
+            sinks.write(source, &records).await?;
+            exported += records.len() as u64;
+
+            state.cursors.insert(source.key(), end);
+            state.save(state_path)?;
+            cursor = end;
+        }
+    }
+
+    Ok(exported)
+}
+
+/// Narrow a fetched slice to `[start, end)`, half-open so a line on a
+/// boundary is written once, and sort it. Lines whose timestamp didn't parse
+/// can't be placed, so they're kept rather than dropped; returns how many.
+fn keep_slice(records: &mut Vec<ExportRecord>, start: DateTime<Utc>, end: DateTime<Utc>) -> usize {
+    let unreadable = |r: &ExportRecord| r.timestamp == DateTime::<Utc>::MIN_UTC;
+    records.retain(|r| unreadable(r) || (r.timestamp >= start && r.timestamp < end));
+    records.sort_by_key(|r| r.timestamp);
+    records.iter().filter(|r| unreadable(r)).count()
+}
+
+/// Where exported records go.
+pub struct ExportSinks {
+    pub files: Option<FileSink>,
+    pub otlp: Option<OtlpSink>,
+}
+
+impl ExportSinks {
+    async fn write(&mut self, source: &ExportSource, records: &[ExportRecord]) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        // Files are written last: a failed send leaves them untouched, so the
+        // retried slice doesn't duplicate lines already on disk.
+        if let Some(otlp) = self.otlp.as_ref() {
+            otlp.send(source, records).await?;
+        }
+        if let Some(files) = self.files.as_mut() {
+            files.write(source, records)?;
+        }
+        Ok(())
+    }
+
+    /// Finish every open file (writing gzip trailers).
+    pub fn finish(&mut self) -> Result<Vec<PathBuf>> {
+        match self.files.as_mut() {
+            Some(files) => files.finish(),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Writes each source to its own series of size-rotated files:
+/// `<service>-<type>.0001.ndjson`, `.0002`, ... A resumed export starts a new
+/// file in the series rather than appending to one that may be truncated.
+pub struct FileSink {
+    dir: PathBuf,
+    format: ExportFormat,
+    max_bytes: u64,
+    writers: HashMap<String, RotatingWriter>,
+    written: Vec<PathBuf>,
+}
+
+impl FileSink {
+    pub fn new(dir: PathBuf, format: ExportFormat, max_bytes: u64) -> Result<Self> {
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create export directory {}", dir.display()))?;
+        Ok(Self {
+            dir,
+            format,
+            max_bytes: max_bytes.max(1),
+            writers: HashMap::new(),
+            written: Vec::new(),
+        })
+    }
+
+    fn write(&mut self, source: &ExportSource, records: &[ExportRecord]) -> Result<()> {
+        let writer = match self.writers.entry(source.key()) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                let stem = source.file_stem();
+                let index = next_file_index(&self.dir, &stem, self.format);
+                entry.insert(RotatingWriter {
+                    dir: self.dir.clone(),
+                    stem,
+                    format: self.format,
+                    index,
+                    current: None,
+                    bytes: 0,
+                })
+            }
+        };
+
+        for record in records {
+            let mut line = serde_json::to_vec(&record.value)?;
+            line.push(b'\n');
+            if writer.bytes > 0 && writer.bytes + line.len() as u64 > self.max_bytes {
+                if let Some(path) = writer.rotate()? {
+                    self.written.push(path);
+                }
+            }
+            writer.write_line(&line)?;
+        }
+        // Flush before the checkpoint is saved so a resume never skips lines
+        // that only existed in a buffer, and every file it points past is
+        // complete.
+        writer.flush()
+    }
+
+    fn finish(&mut self) -> Result<Vec<PathBuf>> {
+        for writer in self.writers.values_mut() {
+            if let Some(path) = writer.rotate()? {
+                self.written.push(path);
+            }
+        }
+        self.written.sort();
+        Ok(std::mem::take(&mut self.written))
+    }
+}
+
+enum EncodedWriter {
+    Plain(BufWriter<File>),
+    /// Every flush closes a complete gzip member, so an interrupted export
+    /// still leaves a valid (multi-member) file behind. `gunzip` and `zcat`
+    /// read all members as one stream.
+    Gzip {
+        file: BufWriter<File>,
+        member: Option<GzEncoder<Vec<u8>>>,
+    },
+}
+
+impl EncodedWriter {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Self::Plain(w) => w,
+            Self::Gzip { member, .. } => {
+                member.get_or_insert_with(|| GzEncoder::new(Vec::new(), Compression::default()))
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::Plain(w) => w.flush()?,
+            Self::Gzip { file, member } => {
+                if let Some(member) = member.take() {
+                    file.write_all(&member.finish()?)?;
+                }
+                file.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<()> {
+        self.flush()
+    }
+}
+
+struct RotatingWriter {
+    dir: PathBuf,
+    stem: String,
+    format: ExportFormat,
+    index: u32,
+    current: Option<(PathBuf, EncodedWriter)>,
+    bytes: u64,
+}
+
+impl RotatingWriter {
+    fn write_line(&mut self, line: &[u8]) -> Result<()> {
+        if self.current.is_none() {
+            let path = self.dir.join(format!(
+                "{}.{:04}.{}",
+                self.stem,
+                self.index,
+                self.format.extension()
+            ));
+            let file = BufWriter::new(
+                File::create(&path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?,
+            );
+            let writer = match self.format {
+                ExportFormat::Ndjson => EncodedWriter::Plain(file),
+                ExportFormat::Gzip => EncodedWriter::Gzip { file, member: None },
+            };
+            self.current = Some((path, writer));
+            self.bytes = 0;
+        }
+        let (_, writer) = self.current.as_mut().unwrap();
+        writer.writer().write_all(line)?;
+        self.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if let Some((_, writer)) = self.current.as_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Close the current file, if any; the next line opens the next one.
+    fn rotate(&mut self) -> Result<Option<PathBuf>> {
+        let Some((path, writer)) = self.current.take() else {
+            return Ok(None);
+        };
+        writer.finish()?;
+        self.index += 1;
+        self.bytes = 0;
+        Ok(Some(path))
+    }
+}
+
+/// First unused index in a file series, so resumed runs never overwrite.
+fn next_file_index(dir: &Path, stem: &str, format: ExportFormat) -> u32 {
+    let suffix = format!(".{}", format.extension());
+    let prefix = format!("{stem}.");
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_prefix(&prefix)?
+                .strip_suffix(&suffix)?
+                .parse::<u32>()
+                .ok()
+        })
+        .max()
+        .map_or(1, |max| max + 1)
+}
+
+/// Ships records to an OTLP/HTTP logs endpoint using the JSON encoding.
+pub struct OtlpSink {
+    client: Client,
+    endpoint: String,
+    headers: Vec<(String, String)>,
+}
+
+impl OtlpSink {
+    pub fn new(endpoint: String, headers: &[String]) -> Result<Self> {
+        let headers = headers
+            .iter()
+            .map(|header| {
+                header
+                    .split_once('=')
+                    .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                    .filter(|(k, _)| !k.is_empty())
+                    .with_context(|| format!("Invalid --otlp-header \"{header}\". Use KEY=VALUE"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            client: Client::new(),
+            endpoint,
+            headers,
+        })
+    }
+
+    async fn send(&self, source: &ExportSource, records: &[ExportRecord]) -> Result<()> {
+        for batch in records.chunks(OTLP_BATCH_SIZE) {
+            let mut request = self
+                .client
+                .post(&self.endpoint)
+                .json(&otlp_payload(source, batch));
+            for (key, value) in &self.headers {
+                request = request.header(key, value);
+            }
+            let response = request
+                .send()
+                .await
+                .with_context(|| format!("Failed to send logs to {}", self.endpoint))?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                bail!(
+                    "OTLP endpoint {} rejected logs ({status}): {}",
+                    self.endpoint,
+                    body.chars().take(200).collect::<String>()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn otlp_string(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// An OTLP `ExportLogsServiceRequest` (JSON encoding) for one source.
+fn otlp_payload(source: &ExportSource, records: &[ExportRecord]) -> Value {
+    let log_records: Vec<Value> = records
+        .iter()
+        .map(|record| {
+            let fields = record.value.as_object().cloned().unwrap_or_default();
+            let body = match fields.get("message") {
+                Some(Value::String(message)) => message.clone(),
+                _ => record.value.to_string(),
+            };
+            let severity = ["level", "severity", "lvl"]
+                .iter()
+                .find_map(|key| fields.get(*key).and_then(Value::as_str))
+                .map(str::to_string);
+            let attributes: Vec<Value> = fields
+                .iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "message" | "timestamp"))
+                .map(|(key, value)| match value {
+                    Value::String(s) => otlp_string(key, s),
+                    other => otlp_string(key, &other.to_string()),
+                })
+                .collect();
+
+            let mut log_record = json!({
+                "timeUnixNano": record.timestamp.timestamp_nanos_opt().unwrap_or_default().to_string(),
+                "body": { "stringValue": body },
+                "attributes": attributes,
+            });
+            if let Some(severity) = severity {
+                log_record["severityText"] = Value::String(severity);
+            }
+            log_record
+        })
+        .collect();
+
+    json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [
+                    otlp_string("service.name", &source.service_name),
+                    otlp_string("railway.service.id", &source.service_id),
+                    otlp_string("railway.log.type", &source.log_type.to_string()),
+                ]
+            },
+            "scopeLogs": [{
+                "scope": { "name": "railway-cli", "version": env!("CARGO_PKG_VERSION") },
+                "logRecords": log_records,
+            }]
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> ExportSource {
+        ExportSource {
+            service_id: "svc-1".to_string(),
+            service_name: "api server".to_string(),
+            log_type: ExportLogType::Deploy,
+            deployment_ids: vec!["dep-1".to_string()],
+        }
+    }
+
+    fn record(ts: &str, message: &str) -> ExportRecord {
+        ExportRecord {
+            timestamp: crate::util::logs::parse_log_timestamp(ts),
+            value: json!({ "timestamp": ts, "message": message, "level": "error", "count": 3 }),
+        }
+    }
+
+    #[test]
+    fn state_cursor_falls_back_to_window_start() {
+        let since = crate::util::logs::parse_log_timestamp("2025-01-01T00:00:00Z");
+        let until = crate::util::logs::parse_log_timestamp("2025-01-08T00:00:00Z");
+        let mut state = ExportState::new(since, until, None, "7d".to_string(), None);
+        assert_eq!(state.cursor(&source()), since);
+
+        let checkpoint = crate::util::logs::parse_log_timestamp("2025-01-03T00:00:00Z");
+        state.cursors.insert(source().key(), checkpoint);
+        assert_eq!(state.cursor(&source()), checkpoint);
+    }
+
+    #[test]
+    fn file_sink_rotates_and_resumes_with_a_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let records = vec![
+            record("2025-01-01T00:00:00Z", "a"),
+            record("2025-01-01T00:00:01Z", "b"),
+        ];
+
+        let mut sink = FileSink::new(dir.path().to_path_buf(), ExportFormat::Ndjson, 1).unwrap();
+        sink.write(&source(), &records).unwrap();
+        let written = sink.finish().unwrap();
+        let names: Vec<_> = written
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "api_server-deploy.0001.ndjson",
+                "api_server-deploy.0002.ndjson"
+            ]
+        );
+
+        let mut resumed =
+            FileSink::new(dir.path().to_path_buf(), ExportFormat::Ndjson, 1024).unwrap();
+        resumed.write(&source(), &records[..1]).unwrap();
+        let written = resumed.finish().unwrap();
+        assert!(written[0].ends_with("api_server-deploy.0003.ndjson"));
+    }
+
+    #[test]
+    fn gzip_files_round_trip() {
+        use std::io::Read;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink =
+            FileSink::new(dir.path().to_path_buf(), ExportFormat::Gzip, 1 << 20).unwrap();
+        sink.write(&source(), &[record("2025-01-01T00:00:00Z", "zipped")])
+            .unwrap();
+        let written = sink.finish().unwrap();
+
+        let mut contents = String::new();
+        flate2::read::GzDecoder::new(File::open(&written[0]).unwrap())
+            .read_to_string(&mut contents)
+            .unwrap();
+        let line: Value = serde_json::from_str(contents.trim()).unwrap();
+        assert_eq!(line["message"], "zipped");
+    }
+
+    #[test]
+    fn gzip_files_are_complete_at_every_checkpoint() {
+        use std::io::Read;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink =
+            FileSink::new(dir.path().to_path_buf(), ExportFormat::Gzip, 1 << 20).unwrap();
+        sink.write(&source(), &[record("2025-01-01T00:00:00Z", "first")])
+            .unwrap();
+        sink.write(&source(), &[record("2025-01-01T00:00:01Z", "second")])
+            .unwrap();
+        // Interrupted: no finish(). The file must still decode in full.
+        let path = dir.path().join("api_server-deploy.0001.ndjson.gz");
+        let mut contents = String::new();
+        flate2::read::MultiGzDecoder::new(File::open(&path).unwrap())
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        drop(sink);
+    }
+
+    #[test]
+    fn slices_keep_lines_with_unreadable_timestamps() {
+        let start = crate::util::logs::parse_log_timestamp("2025-01-01T00:00:00Z");
+        let end = crate::util::logs::parse_log_timestamp("2025-01-01T01:00:00Z");
+        let mut records = vec![
+            record("2025-01-01T00:30:00Z", "inside"),
+            record("2025-01-01T01:00:00Z", "next slice"),
+            record("not a time", "unreadable"),
+            record("2024-12-31T23:59:59Z", "previous slice"),
+        ];
+        assert_eq!(keep_slice(&mut records, start, end), 1);
+        let messages: Vec<_> = records.iter().map(|r| r.value["message"].clone()).collect();
+        assert_eq!(messages, vec![json!("unreadable"), json!("inside")]);
+    }
+
+    #[test]
+    fn resume_requires_the_same_requested_window() {
+        let since = crate::util::logs::parse_log_timestamp("2025-01-01T00:00:00Z");
+        let until = crate::util::logs::parse_log_timestamp("2025-01-08T00:00:00Z");
+        let state = ExportState::new(since, until, None, "7d".to_string(), None);
+        assert!(state.requested_with("7d", None));
+        assert!(!state.requested_with("3d", None));
+        assert!(!state.requested_with("7d", Some("1d")));
+    }
+
+    #[test]
+    fn otlp_payload_maps_message_severity_and_attributes() {
+        let payload = otlp_payload(&source(), &[record("2025-01-01T00:00:00Z", "boom")]);
+        let resource = &payload["resourceLogs"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "api server"
+        );
+        let log = &resource["scopeLogs"][0]["logRecords"][0];
+        assert_eq!(log["body"]["stringValue"], "boom");
+        assert_eq!(log["severityText"], "error");
+        assert_eq!(log["timeUnixNano"], "1735689600000000000");
+        let attributes = log["attributes"].as_array().unwrap();
+        assert!(
+            attributes
+                .iter()
+                .any(|a| a["key"] == "count" && a["value"]["stringValue"] == "3")
+        );
+        assert!(!attributes.iter().any(|a| a["key"] == "message"));
+    }
+}
@@ -5,11 +5,13 @@ pub mod config;
 pub mod database;
 pub mod db_stats;
 pub mod deployment;
+pub mod deployment_timeline;
 pub mod develop;
 pub mod environment;
 pub mod exec;
 pub mod github;
 pub mod local_override;
+pub mod log_export;
 pub mod metrics;
 pub mod metrics_tui;
 pub mod outbound_networking;
@@ -19,6 +21,8 @@ pub mod private_network;
 pub mod project;
 pub mod regions;
 pub mod sandbox_exec;
+pub mod sandbox_sync;
+pub mod sandboxfile;
 pub mod scale_tui;
 pub mod service;
 pub mod signals;
@@ -320,13 +320,26 @@ pub struct ServiceContext {
     pub service_name: String,
 }
 
-/// Resolves project, environment, and service from args and linked project.
+/// Resolved project and environment for operations that span services
+/// (e.g. merged logs across several services).
+pub struct EnvironmentContext {
+    pub client: Client,
+    pub configs: Configs,
+    pub project: ProjectProject,
+    pub project_id: String,
+    pub environment_id: String,
+    pub environment_name: String,
+    /// The service linked to the current directory, if the project came from
+    /// the local link rather than `--project`.
+    pub linked_service: Option<String>,
+}
+
+/// Resolves project and environment from args and linked project.
 /// When project_arg is provided, environment_arg must also be provided.
-pub async fn resolve_service_context(
+pub async fn resolve_environment_context(
     project_arg: Option<String>,
-    service_arg: Option<String>,
     environment_arg: Option<String>,
-) -> Result<ServiceContext> {
+) -> Result<EnvironmentContext> {
     let configs = Configs::new()?;
     let client = GQLClient::new_authorized(&configs)?;
 
@@ -366,10 +379,35 @@ pub async fn resolve_service_context(
             .to_string(),
     };
     let environment = get_matched_environment(&project, env)?;
-    let environment_id = environment.id;
-    let environment_name = environment.name;
 
-    let linked_service = linked_project.and_then(|lp| lp.service);
+    Ok(EnvironmentContext {
+        client,
+        configs,
+        project,
+        project_id,
+        environment_id: environment.id,
+        environment_name: environment.name,
+        linked_service: linked_project.and_then(|lp| lp.service),
+    })
+}
+
+/// Resolves project, environment, and service from args and linked project.
+/// When project_arg is provided, environment_arg must also be provided.
+pub async fn resolve_service_context(
+    project_arg: Option<String>,
+    service_arg: Option<String>,
+    environment_arg: Option<String>,
+) -> Result<ServiceContext> {
+    let EnvironmentContext {
+        client,
+        configs,
+        project,
+        project_id,
+        environment_id,
+        environment_name,
+        linked_service,
+    } = resolve_environment_context(project_arg, environment_arg).await?;
+
     let services = &project.services.edges;
     if services.is_empty() {
         bail!(RailwayError::ProjectHasNoServices);
@@ -16,7 +16,7 @@
 use std::time::Duration;
 
 use anyhow::{Context, Result, bail};
-use futures::{SinkExt, StreamExt};
+use futures::{SinkExt, StreamExt, stream::SplitStream};
 use reqwest_websocket::{CloseCode, Message, RequestBuilderExt, WebSocket};
 use serde_json::json;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -34,6 +34,9 @@ pub const STREAM_STDERR: u8 = 0x03;
 /// Exit code for a client-side `--timeout` expiry, per GNU timeout convention.
 pub const TIMEOUT_EXIT_CODE: i32 = 124;
 
+/// Input bytes per stdin frame for [`capture`].
+const CAPTURE_STDIN_CHUNK: usize = 64 * 1024;
+
 /// The bridge requires a non-empty command even on reattach; the VM ignores it
 /// when the durable session name resolves to a live session.
 const REATTACH_PLACEHOLDER_COMMAND: &str = ":";
@@ -52,9 +55,13 @@ pub struct ExecOptions {
     /// Return as soon as the server assigns a durable session, leaving the
     /// command running.
     pub detach: bool,
-    /// When stdin is a TTY we never read it — EOF is sent immediately so
-    /// commands that read stdin can finish. Piped stdin is forwarded.
-    pub stdin_is_tty: bool,
+    /// Forward this process's stdin to the command. Otherwise EOF is sent
+    /// immediately so commands that read stdin can finish; callers forward
+    /// piped stdin and never a TTY.
+    pub forward_stdin: bool,
+    /// Write output line by line behind this prefix, so several runs can
+    /// share the terminal without mixing partial lines.
+    pub output_prefix: Option<String>,
 }
 
 /// How an exec run ended. `session_name` is the durable session (server-assigned
@@ -179,12 +186,11 @@ pub async fn run(ws: WebSocket, opts: ExecOptions) -> Result<ExecOutcome> {
     let mut wrote_output = false;
     let mut interrupted = false;
 
-    // Piped stdin is forwarded; TTY stdin is never read — EOF it up front.
-    let mut stdin = if opts.stdin_is_tty {
+    let mut stdin = if opts.forward_stdin {
+        Some(tokio::io::stdin())
+    } else {
         tx.send(Message::Text(stdin_close_payload())).await?;
         None
-    } else {
-        Some(tokio::io::stdin())
     };
     let mut stdin_buf = [0u8; 8192];
 
@@ -192,6 +198,12 @@ pub async fn run(ws: WebSocket, opts: ExecOptions) -> Result<ExecOutcome> {
 
     let mut stdout = tokio::io::stdout();
     let mut stderr = tokio::io::stderr();
+    let mut prefixed = opts.output_prefix.as_ref().map(|prefix| {
+        (
+            PrefixedLines::new(prefix, false),
+            PrefixedLines::new(prefix, true),
+        )
+    });
 
     loop {
         tokio::select! {
@@ -199,12 +211,15 @@ pub async fn run(ws: WebSocket, opts: ExecOptions) -> Result<ExecOutcome> {
                 Some(Ok(Message::Binary(data))) => {
                     if let Some((tag, payload)) = decode_binary_frame(&data) {
                         wrote_output = true;
-                        match tag {
-                            STREAM_STDOUT => {
+                        match (tag, prefixed.as_mut()) {
+                            (STREAM_STDOUT, Some((lines, _))) | (STREAM_STDERR, Some((_, lines))) => {
+                                lines.write(payload)?;
+                            }
+                            (STREAM_STDOUT, None) => {
                                 stdout.write_all(payload).await?;
                                 stdout.flush().await?;
                             }
-                            STREAM_STDERR => {
+                            (STREAM_STDERR, None) => {
                                 stderr.write_all(payload).await?;
                                 stderr.flush().await?;
                             }
@@ -309,6 +324,147 @@ pub async fn run(ws: WebSocket, opts: ExecOptions) -> Result<ExecOutcome> {
     }
 }
 
+/// Output written one whole line at a time behind a prefix. A trailing
+/// partial line is written when the run ends (on drop).
+struct PrefixedLines {
+    prefix: String,
+    stderr: bool,
+    pending: Vec<u8>,
+}
+
+impl PrefixedLines {
+    fn new(prefix: &str, stderr: bool) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            stderr,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Buffer `bytes` and return every line they complete, prefixed.
+    fn complete_lines(&mut self, bytes: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(bytes);
+        let Some(end) = self.pending.iter().rposition(|&b| b == b'\n') else {
+            return Vec::new();
+        };
+        let rest = self.pending.split_off(end + 1);
+        let done = std::mem::replace(&mut self.pending, rest);
+        let mut out = Vec::with_capacity(done.len());
+        for line in done.split_inclusive(|&b| b == b'\n') {
+            out.extend_from_slice(self.prefix.as_bytes());
+            out.extend_from_slice(line);
+        }
+        out
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        let out = self.complete_lines(bytes);
+        self.emit(&out)
+    }
+
+    /// One locked write per batch, so concurrent runs never split a line.
+    fn emit(&self, out: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+        if out.is_empty() {
+            return Ok(());
+        }
+        if self.stderr {
+            let mut stderr = std::io::stderr().lock();
+            stderr.write_all(out)?;
+            stderr.flush()
+        } else {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(out)?;
+            stdout.flush()
+        }
+    }
+}
+
+impl Drop for PrefixedLines {
+    fn drop(&mut self) {
+        if !self.pending.is_empty() {
+            let mut out = self.prefix.as_bytes().to_vec();
+            out.append(&mut self.pending);
+            out.push(b'\n');
+            let _ = self.emit(&out);
+        }
+    }
+}
+
+/// Output of a [`capture`] run.
+pub struct Captured {
+    pub code: i32,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Run `command` to completion with `stdin` as its whole input, buffering
+/// its output instead of streaming it. For file transfer, where stdout
+/// carries data rather than text for the terminal. Input is sent while
+/// output is read so large transfers can't stall on a full pipe.
+pub async fn capture(ws: WebSocket, command: &str, stdin: &[u8]) -> Result<Captured> {
+    let (mut tx, mut rx) = ws.split();
+
+    let opts = ExecOptions {
+        command: Some(command.to_string()),
+        session: None,
+        resume_from_last_read: false,
+        timeout: None,
+        detach: false,
+        forward_stdin: false,
+        output_prefix: None,
+    };
+    tx.send(Message::Text(init_exec_payload(&opts).to_string()))
+        .await
+        .context("Failed to start command")?;
+
+    let send = async {
+        for chunk in stdin.chunks(CAPTURE_STDIN_CHUNK) {
+            tx.send(Message::Binary(encode_stdin_frame(chunk).into()))
+                .await?;
+        }
+        tx.send(Message::Text(stdin_close_payload())).await
+    };
+
+    // A command that exits early (say, a failing `mkdir`) stops reading its
+    // input; its exit code says more than the failed send does.
+    let (sent, received) = tokio::join!(send, receive_captured(&mut rx));
+    let captured = received?;
+    if captured.code == 0 {
+        sent.context("Failed to send input")?;
+    }
+    Ok(captured)
+}
+
+async fn receive_captured(rx: &mut SplitStream<WebSocket>) -> Result<Captured> {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    while let Some(message) = rx.next().await {
+        match message.context("exec stream failed")? {
+            Message::Binary(data) => match decode_binary_frame(&data) {
+                Some((STREAM_STDOUT, payload)) => stdout.extend_from_slice(payload),
+                Some((STREAM_STDERR, payload)) => stderr.extend_from_slice(payload),
+                _ => {}
+            },
+            Message::Text(text) => {
+                let Ok(frame) = serde_json::from_str::<WsFrame>(&text) else {
+                    continue;
+                };
+                if frame.kind == "exit" {
+                    return Ok(Captured {
+                        code: frame.data["exit_code"].as_i64().unwrap_or(0) as i32,
+                        stdout,
+                        stderr,
+                    });
+                }
+            }
+            Message::Close { .. } => break,
+            _ => {}
+        }
+    }
+    bail!("connection closed before the command finished")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,7 +476,8 @@ mod tests {
             resume_from_last_read: resume,
             timeout: None,
             detach: false,
-            stdin_is_tty: true,
+            forward_stdin: false,
+            output_prefix: None,
         }
     }
 
@@ -371,6 +528,14 @@ mod tests {
         assert_eq!(payload["data"]["resume_from_last_read"], true);
     }
 
+    #[test]
+    fn prefixed_lines_hold_partial_lines_back() {
+        let mut lines = PrefixedLines::new("[a] ", false);
+        assert_eq!(lines.complete_lines(b"one\ntw"), b"[a] one\n");
+        assert_eq!(lines.complete_lines(b"o\nthree\n"), b"[a] two\n[a] three\n");
+        assert!(lines.complete_lines(b"").is_empty());
+    }
+
     #[test]
     fn endpoint_derives_from_relay_host() {
         assert_eq!(
@@ -0,0 +1,236 @@
+//! Moving files between a local tree and a sandbox over the exec bridge.
+//!
+//! There is no file API on the sandbox side, so transfers are shell commands
+//! run through [`sandbox_exec::capture`]: pushes stream a gzipped tarball into
+//! `tar xzf -`, downloads read `cat` from stdout. The sandbox image needs
+//! `tar` and `gzip`, which the standard image has.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, Result, bail};
+use flate2::{Compression, write::GzEncoder};
+
+use crate::controllers::sandbox_exec::{self, Captured};
+use crate::controllers::upload::build_upload_manifest;
+use crate::util::shell::shell_quote;
+
+/// One side of a `sync`/`cp`: a local path, or a path inside the sandbox
+/// written with a leading `:` (`:/workspace`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Location {
+    Local(PathBuf),
+    Sandbox(String),
+}
+
+impl FromStr for Location {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.strip_prefix(':') {
+            Some("") => bail!("Missing sandbox path after ':' (e.g. :/workspace)"),
+            Some(path) => Ok(Location::Sandbox(path.to_string())),
+            None if s.is_empty() => bail!("Missing path"),
+            None => Ok(Location::Local(PathBuf::from(s))),
+        }
+    }
+}
+
+/// Archive path → sha256 of every file `railway up` would upload from `root`,
+/// so sync honors the same .gitignore and .railwayignore rules.
+pub type Snapshot = BTreeMap<String, String>;
+
+pub fn snapshot(root: &Path, no_gitignore: bool) -> Result<Snapshot> {
+    Ok(build_upload_manifest(root, root, no_gitignore)?
+        .into_iter()
+        .map(|entry| (entry.path, entry.sha256))
+        .collect())
+}
+
+/// What an incremental sync has to do to bring the sandbox from `previous`
+/// to `current`.
+#[derive(Debug, Default, PartialEq)]
+pub struct SyncPlan {
+    pub upload: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl SyncPlan {
+    pub fn between(previous: &Snapshot, current: &Snapshot) -> Self {
+        SyncPlan {
+            upload: current
+                .iter()
+                .filter(|(path, hash)| previous.get(*path) != Some(hash))
+                .map(|(path, _)| path.clone())
+                .collect(),
+            remove: previous
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.upload.is_empty() && self.remove.is_empty()
+    }
+}
+
+/// A gzipped tarball of `paths` (relative to `root`) with modes preserved.
+pub fn archive(root: &Path, paths: &[String]) -> Result<Vec<u8>> {
+    let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for path in paths {
+        archive
+            .append_path_with_name(root.join(path), path)
+            .with_context(|| format!("Failed to read {path}"))?;
+    }
+    Ok(archive.into_inner()?.finish()?)
+}
+
+/// A gzipped tarball holding one file under a new name, for `cp`.
+pub fn archive_file(file: &Path, name: &str) -> Result<Vec<u8>> {
+    let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    archive
+        .append_path_with_name(file, name)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    Ok(archive.into_inner()?.finish()?)
+}
+
+/// Unpack a tarball from stdin into `dest`, creating it first.
+pub fn extract_command(dest: &str) -> String {
+    let dest = shell_quote(dest);
+    format!("mkdir -p -- {dest} && tar xzf - -C {dest}")
+}
+
+/// Delete `paths` (relative to `dest`).
+pub fn remove_command(dest: &str, paths: &[String]) -> String {
+    let paths = paths
+        .iter()
+        .map(|path| shell_quote(path))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("cd -- {} && rm -f -- {paths}", shell_quote(dest))
+}
+
+/// Print a file to stdout.
+pub fn read_command(path: &str) -> String {
+    format!("cat -- {}", shell_quote(path))
+}
+
+/// Split a sandbox file path into its directory and file name.
+pub fn split_remote(path: &str) -> Result<(String, String)> {
+    let trimmed = path.trim_end_matches('/');
+    let (dir, name) = match trimmed.rsplit_once('/') {
+        Some(("", name)) => ("/", name),
+        Some((dir, name)) => (dir, name),
+        None => (".", trimmed),
+    };
+    if name.is_empty() || name == "." || name == ".." {
+        bail!("{path} is not a file path");
+    }
+    Ok((dir.to_string(), name.to_string()))
+}
+
+/// Run `command` in the sandbox, failing with its stderr when it exits
+/// non-zero.
+pub async fn run(jwt: &str, command: &str, stdin: &[u8]) -> Result<Captured> {
+    let ws = sandbox_exec::connect(jwt).await?;
+    let captured = sandbox_exec::capture(ws, command, stdin).await?;
+    if captured.code != 0 {
+        let stderr = String::from_utf8_lossy(&captured.stderr);
+        bail!(
+            "`{command}` failed in the sandbox (exit {}){}",
+            captured.code,
+            match stderr.trim() {
+                "" => String::new(),
+                stderr => format!(": {stderr}"),
+            }
+        );
+    }
+    Ok(captured)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(entries: &[(&str, &str)]) -> Snapshot {
+        entries
+            .iter()
+            .map(|(path, hash)| (path.to_string(), hash.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn locations() {
+        assert_eq!(
+            ":/workspace".parse::<Location>().unwrap(),
+            Location::Sandbox("/workspace".into())
+        );
+        assert_eq!(
+            "./".parse::<Location>().unwrap(),
+            Location::Local(PathBuf::from("./"))
+        );
+        assert!(":".parse::<Location>().is_err());
+    }
+
+    #[test]
+    fn plan_uploads_changes_and_removes_deletions() {
+        let previous = snap(&[("a.rs", "1"), ("b.rs", "2"), ("c.rs", "3")]);
+        let current = snap(&[("a.rs", "1"), ("b.rs", "9"), ("d.rs", "4")]);
+        assert_eq!(
+            SyncPlan::between(&previous, &current),
+            SyncPlan {
+                upload: vec!["b.rs".into(), "d.rs".into()],
+                remove: vec!["c.rs".into()],
+            }
+        );
+        assert!(SyncPlan::between(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn snapshot_honors_ignore_files_and_archives_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join(".railwayignore"), "target/\n").unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("target/out"), "built").unwrap();
+
+        let snapshot = snapshot(root, false).unwrap();
+        let paths: Vec<String> = snapshot.keys().cloned().collect();
+        assert_eq!(paths, vec![".railwayignore", "src/main.rs"]);
+
+        let gz = archive(root, &paths).unwrap();
+        let mut unpacked = tar::Archive::new(flate2::read::GzDecoder::new(gz.as_slice()));
+        let names: Vec<String> = unpacked
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, paths);
+    }
+
+    #[test]
+    fn remote_commands_quote_paths() {
+        assert_eq!(
+            extract_command("/work space"),
+            "mkdir -p -- '/work space' && tar xzf - -C '/work space'"
+        );
+        assert_eq!(
+            remove_command("/workspace", &["a b.txt".into()]),
+            "cd -- /workspace && rm -f -- 'a b.txt'"
+        );
+        assert_eq!(
+            split_remote("/workspace/app.py").unwrap(),
+            ("/workspace".into(), "app.py".into())
+        );
+        assert_eq!(
+            split_remote("/app.py").unwrap(),
+            ("/".into(), "app.py".into())
+        );
+        assert!(split_remote("/").is_err());
+    }
+}
@@ -0,0 +1,415 @@
+//! `Sandboxfile`: a Dockerfile-like recipe for sandbox templates.
+//!
+//! ```text
+//! FROM sha256:…                 # optional base image digest
+//! ENV NODE_ENV=development      # build variables, also set on sandboxes
+//! RUN npm i -g pnpm \
+//!     && pnpm --version
+//! COPY scripts/setup.sh /usr/local/bin/setup.sh
+//! IDLE_TIMEOUT 30               # default for `sandbox create --template`
+//! ```
+//!
+//! The template API only takes shell instructions, so a recipe compiles down
+//! to the same instruction list `template build -c ...` sends: `RUN` steps
+//! pass through verbatim and `COPY` becomes one step per file that writes the
+//! file's contents inline. The server hashes that list, so a Sandboxfile and
+//! the equivalent `-c` flags produce the same template id.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use base64::prelude::*;
+
+use crate::util::shell::shell_quote;
+
+/// Largest single file `COPY` will inline into an instruction. Each
+/// instruction runs as one `sh -c` argument, which Linux caps at 128 KiB
+/// (`MAX_ARG_STRLEN`); 64 KiB is about 86 KiB once base64-encoded.
+const MAX_COPY_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Recipe {
+    pub base_image_digest: Option<String>,
+    pub env: BTreeMap<String, String>,
+    pub steps: Vec<Step>,
+    pub idle_timeout_minutes: Option<i64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    Run(String),
+    Copy { src: String, dest: String },
+}
+
+/// One build instruction, with the recipe line it came from for display.
+#[derive(Debug, PartialEq)]
+pub struct Instruction {
+    pub label: String,
+    pub command: String,
+}
+
+impl Recipe {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Expands the steps into build instructions. `COPY` sources resolve
+    /// relative to `root` (the Sandboxfile's directory).
+    pub fn instructions(&self, root: &Path) -> Result<Vec<Instruction>> {
+        let mut instructions = Vec::new();
+        for step in &self.steps {
+            match step {
+                Step::Run(command) => instructions.push(Instruction {
+                    label: format!("RUN {command}"),
+                    command: command.clone(),
+                }),
+                Step::Copy { src, dest } => {
+                    let source = root.join(src);
+                    let files = collect_files(&source).with_context(|| {
+                        format!("COPY {src}: failed to read {}", source.display())
+                    })?;
+                    if files.is_empty() {
+                        bail!("COPY {src}: no files to copy");
+                    }
+                    for (file, relative) in files {
+                        let target = copy_target(&source, dest, &relative);
+                        instructions.push(Instruction {
+                            label: format!("COPY {} -> {target}", display_src(src, &relative)),
+                            command: copy_command(&file, &target)?,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(instructions)
+    }
+}
+
+impl std::str::FromStr for Recipe {
+    type Err = anyhow::Error;
+
+    fn from_str(content: &str) -> Result<Self> {
+        let mut recipe = Recipe::default();
+        for (number, line) in logical_lines(content) {
+            let (keyword, rest) = line
+                .split_once(char::is_whitespace)
+                .map(|(k, r)| (k, r.trim()))
+                .unwrap_or((line.as_str(), ""));
+            let at = || format!("line {number}");
+            match keyword.to_ascii_uppercase().as_str() {
+                "FROM" => {
+                    if recipe.base_image_digest.is_some() {
+                        bail!("{}: FROM may only appear once", at());
+                    }
+                    if !recipe.steps.is_empty() {
+                        bail!("{}: FROM must come before RUN and COPY", at());
+                    }
+                    if rest.is_empty() {
+                        bail!("{}: FROM needs a base image digest", at());
+                    }
+                    recipe.base_image_digest = Some(rest.to_string());
+                }
+                "ENV" => {
+                    for (key, value) in parse_env(rest).with_context(at)? {
+                        recipe.env.insert(key, value);
+                    }
+                }
+                "RUN" => {
+                    if rest.is_empty() {
+                        bail!("{}: RUN needs a command", at());
+                    }
+                    recipe.steps.push(Step::Run(rest.to_string()));
+                }
+                "COPY" => {
+                    let parts: Vec<&str> = rest.split_whitespace().collect();
+                    let [src, dest] = parts.as_slice() else {
+                        bail!("{}: COPY takes a source and a destination", at());
+                    };
+                    if !dest.starts_with('/') {
+                        bail!("{}: COPY destination must be an absolute path", at());
+                    }
+                    recipe.steps.push(Step::Copy {
+                        src: src.to_string(),
+                        dest: dest.to_string(),
+                    });
+                }
+                "IDLE_TIMEOUT" => {
+                    let minutes = rest
+                        .trim_end_matches('m')
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|m| *m > 0)
+                        .with_context(|| {
+                            format!("{}: IDLE_TIMEOUT takes a number of minutes", at())
+                        })?;
+                    recipe.idle_timeout_minutes = Some(minutes);
+                }
+                other => bail!(
+                    "{}: unknown instruction `{other}` (expected FROM, ENV, RUN, COPY or IDLE_TIMEOUT)",
+                    at()
+                ),
+            }
+        }
+        if recipe.steps.is_empty() {
+            bail!("A Sandboxfile needs at least one RUN or COPY step");
+        }
+        Ok(recipe)
+    }
+}
+
+/// Joins `\`-continued lines and drops comments and blanks, keeping the
+/// number of each instruction's first line.
+fn logical_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (index, raw) in content.lines().enumerate() {
+        let trimmed = raw.trim();
+        if current.is_none() && (trimmed.is_empty() || trimmed.starts_with('#')) {
+            continue;
+        }
+        let (continues, text) = match trimmed.strip_suffix('\\') {
+            Some(text) => (true, text.trim_end()),
+            None => (false, trimmed),
+        };
+        let (_, line) = current.get_or_insert_with(|| (index + 1, String::new()));
+        if !line.is_empty() && !text.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(text);
+        if !continues {
+            lines.extend(current.take());
+        }
+    }
+    lines.extend(current);
+    lines
+}
+
+/// `ENV KEY=VALUE [KEY=VALUE...]` (values may be double-quoted) or the
+/// legacy `ENV KEY value with spaces`.
+fn parse_env(rest: &str) -> Result<Vec<(String, String)>> {
+    let Some((first, _)) = rest.split_once('=') else {
+        let (key, value) = rest
+            .split_once(char::is_whitespace)
+            .context("ENV takes KEY=VALUE pairs")?;
+        return Ok(vec![(key.to_string(), value.trim().to_string())]);
+    };
+    if first.contains(char::is_whitespace) {
+        let (key, value) = rest.split_once(char::is_whitespace).unwrap();
+        return Ok(vec![(key.to_string(), value.trim().to_string())]);
+    }
+
+    let mut pairs = Vec::new();
+    let mut chars = rest.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            break;
+        }
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+        if chars.next() != Some('=') || key.is_empty() || key.contains(char::is_whitespace) {
+            bail!("ENV takes KEY=VALUE pairs");
+        }
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => value.extend(chars.next()),
+                    Some(c) => value.push(c),
+                    None => bail!("ENV {key}: unterminated quote"),
+                }
+            }
+        } else {
+            value.extend(std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())));
+        }
+        pairs.push((key, value));
+    }
+    Ok(pairs)
+}
+
+/// Every file under `source` (or `source` itself), sorted, with its path
+/// relative to `source`.
+fn collect_files(source: &Path) -> Result<Vec<(PathBuf, PathBuf)>> {
+    if source.is_file() {
+        return Ok(vec![(source.to_path_buf(), PathBuf::new())]);
+    }
+    let mut files = Vec::new();
+    let mut dirs = vec![source.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = std::fs::read_dir(&dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort();
+        for path in entries.into_iter().rev() {
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                let relative = path.strip_prefix(source)?.to_path_buf();
+                files.push((path, relative));
+            }
+        }
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+/// Where a copied file lands: `dest` itself for a single file (or inside it
+/// when it ends with `/`), else `dest` joined with the file's relative path.
+fn copy_target(source: &Path, dest: &str, relative: &Path) -> String {
+    let relative = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if relative.is_empty() {
+        if dest.ends_with('/') {
+            let name = source
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            return format!("{dest}{name}");
+        }
+        return dest.to_string();
+    }
+    format!("{}/{relative}", dest.trim_end_matches('/'))
+}
+
+fn display_src(src: &str, relative: &Path) -> String {
+    if relative.as_os_str().is_empty() {
+        src.to_string()
+    } else {
+        format!("{}/{}", src.trim_end_matches('/'), relative.display())
+    }
+}
+
+fn copy_command(file: &Path, target: &str) -> Result<String> {
+    let metadata = std::fs::metadata(file)?;
+    if metadata.len() > MAX_COPY_BYTES {
+        bail!(
+            "{} is larger than {}KiB; download it in a RUN step instead",
+            file.display(),
+            MAX_COPY_BYTES / 1024
+        );
+    }
+    let content = BASE64_STANDARD.encode(std::fs::read(file)?);
+    let dir = target.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+    let mut command = String::new();
+    if !dir.is_empty() {
+        command.push_str(&format!("mkdir -p {} && ", shell_quote(dir)));
+    }
+    command.push_str(&format!(
+        "echo {content} | base64 -d > {}",
+        shell_quote(target)
+    ));
+    if is_executable(&metadata) {
+        command.push_str(&format!(" && chmod +x {}", shell_quote(target)));
+    }
+    Ok(command)
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_recipe() {
+        let recipe: Recipe = "# dev box\nFROM sha256:abc\nENV NODE_ENV=development GREETING=\"hello world\"\nENV EDITOR vim -u NONE\nRUN npm i -g pnpm \\\n    && pnpm --version\n\nCOPY scripts /opt/scripts\nidle_timeout 30m\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            recipe,
+            Recipe {
+                base_image_digest: Some("sha256:abc".into()),
+                env: BTreeMap::from([
+                    ("EDITOR".into(), "vim -u NONE".into()),
+                    ("GREETING".into(), "hello world".into()),
+                    ("NODE_ENV".into(), "development".into()),
+                ]),
+                steps: vec![
+                    Step::Run("npm i -g pnpm && pnpm --version".into()),
+                    Step::Copy {
+                        src: "scripts".into(),
+                        dest: "/opt/scripts".into()
+                    },
+                ],
+                idle_timeout_minutes: Some(30),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_bad_recipes() {
+        let err = |s: &str| format!("{:#}", s.parse::<Recipe>().unwrap_err());
+        assert!(err("RUN a\nFROM x\n").contains("line 2: FROM must come before"));
+        assert!(err("ADD a b\n").contains("unknown instruction `ADD`"));
+        assert!(err("COPY a relative/dest\n").contains("absolute path"));
+        assert!(err("ENV A=1\n").contains("at least one RUN or COPY"));
+        assert!(err("RUN x\nIDLE_TIMEOUT soon\n").contains("number of minutes"));
+    }
+
+    #[test]
+    fn copy_expands_to_one_instruction_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("scripts/lib")).unwrap();
+        std::fs::write(dir.path().join("scripts/setup.sh"), "echo hi\n").unwrap();
+        std::fs::write(dir.path().join("scripts/lib/util.sh"), "true\n").unwrap();
+        std::fs::write(dir.path().join("motd"), "welcome\n").unwrap();
+
+        let recipe: Recipe = "RUN apt-get update\nCOPY scripts /opt/scripts/\nCOPY motd /etc/\n"
+            .parse()
+            .unwrap();
+        let instructions = recipe.instructions(dir.path()).unwrap();
+        let labels: Vec<_> = instructions.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "RUN apt-get update",
+                "COPY scripts/lib/util.sh -> /opt/scripts/lib/util.sh",
+                "COPY scripts/setup.sh -> /opt/scripts/setup.sh",
+                "COPY motd -> /etc/motd",
+            ]
+        );
+        assert_eq!(instructions[0].command, "apt-get update");
+        assert_eq!(
+            instructions[3].command,
+            "mkdir -p /etc && echo d2VsY29tZQo= | base64 -d > /etc/motd"
+        );
+    }
+
+    #[test]
+    fn copy_instructions_fit_in_one_shell_argument() {
+        const MAX_ARG_STRLEN: usize = 128 * 1024;
+        let dir = tempfile::tempdir().unwrap();
+        let largest = dir.path().join("largest.bin");
+        std::fs::write(&largest, vec![0xa5; MAX_COPY_BYTES as usize]).unwrap();
+        let command = copy_command(&largest, "/opt/some/deeply/nested/largest.bin").unwrap();
+        assert!(
+            command.len() < MAX_ARG_STRLEN,
+            "{} bytes exceeds MAX_ARG_STRLEN",
+            command.len()
+        );
+
+        let too_large = dir.path().join("too-large.bin");
+        std::fs::write(&too_large, vec![0; MAX_COPY_BYTES as usize + 1]).unwrap();
+        let err = copy_command(&too_large, "/opt/too-large.bin").unwrap_err();
+        assert!(err.to_string().contains("larger than 64KiB"));
+    }
+}
@@ -1,17 +1,37 @@
-use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use gzp::{ZBuilder, deflate::Gzip};
-use ignore::WalkBuilder;
+use ignore::{
+    Match, WalkBuilder,
+    gitignore::{Gitignore, GitignoreBuilder},
+};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use synchronized_writer::SynchronizedWriter;
+use sha2::{Digest, Sha256};
 use tar::Builder;
+use tokio::{sync::mpsc, task::JoinHandle};
 use url::Url;
 
 use crate::errors::RailwayError;
 
+/// Paths that are never uploaded, whatever the ignore files say.
+const BUILTIN_IGNORES: [&str; 2] = [".git", "node_modules"];
+const RAILWAY_IGNORE_FILE: &str = ".railwayignore";
+
+/// Compressed bytes handed to the HTTP body at a time.
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks buffered between the compressor and the upload. Together with
+/// `UPLOAD_CHUNK_SIZE` this bounds how far compression runs ahead of the
+/// network.
+const UPLOAD_CHANNEL_CAPACITY: usize = 8;
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpResponse {
@@ -26,63 +46,258 @@ pub struct UpErrorResponse {
     pub message: String,
 }
 
-/// Create a gzipped tarball from a project directory, respecting .railwayignore and .gitignore.
-///
-/// `on_progress` is called with `(current, total)` after each entry is processed.
-/// The first call is `(0, total)` once indexing is complete (before compression begins).
-pub fn create_deploy_tarball(
+/// A deploy tarball being compressed on a blocking thread and streamed into
+/// an upload body as it's produced.
+pub struct DeployArchive {
+    chunks: mpsc::Receiver<io::Result<Vec<u8>>>,
+    bytes_written: Arc<AtomicU64>,
+    task: JoinHandle<Result<()>>,
+}
+
+impl DeployArchive {
+    /// Compressed bytes produced so far. Complete once the upload finishes.
+    pub fn bytes_written(&self) -> Arc<AtomicU64> {
+        self.bytes_written.clone()
+    }
+}
+
+/// Walks the files `railway up` would upload, respecting .railwayignore and
+/// .gitignore. Yields directories too.
+fn deploy_walker(project_path: &Path, no_gitignore: bool) -> ignore::Walk {
+    let mut builder = WalkBuilder::new(project_path);
+    builder.add_custom_ignore_filename(RAILWAY_IGNORE_FILE);
+    if no_gitignore {
+        builder.git_ignore(false);
+    }
+    builder.follow_links(true).hidden(false).build()
+}
+
+fn builtin_ignore(path: &Path) -> Option<&'static str> {
+    path.components().find_map(|c| {
+        BUILTIN_IGNORES
+            .iter()
+            .find(|name| c.as_os_str() == std::ffi::OsStr::new(name))
+            .copied()
+    })
+}
+
+/// Path of an entry inside the archive, without the leading `./`, using `/`
+/// separators on every platform.
+fn archive_path(path: &Path, archive_prefix_path: &Path) -> Result<String> {
+    let relative = path.strip_prefix(archive_prefix_path)?;
+    Ok(relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Write a gzipped tarball of a project directory to `writer`.
+fn write_deploy_tarball<W: Write + Send + 'static>(
+    writer: W,
     project_path: &Path,
     archive_prefix_path: &Path,
     no_gitignore: bool,
     mut on_progress: impl FnMut(usize, usize),
-) -> Result<Vec<u8>> {
-    let bytes = Vec::<u8>::new();
-    let arc = Arc::new(Mutex::new(bytes));
+) -> Result<()> {
     let mut parz = ZBuilder::<Gzip, _>::new()
         .num_threads(num_cpus::get())
-        .from_writer(SynchronizedWriter::new(arc.clone()));
-
-    let ignore_paths = [".git", "node_modules"];
-    let ignore_paths: Vec<&std::ffi::OsStr> =
-        ignore_paths.iter().map(std::ffi::OsStr::new).collect();
+        .from_writer(writer);
 
     {
         let mut archive = Builder::new(&mut parz);
-        let mut builder = WalkBuilder::new(project_path);
-        builder.add_custom_ignore_filename(".railwayignore");
-        if no_gitignore {
-            builder.git_ignore(false);
-        }
-
-        let walker = builder.follow_links(true).hidden(false);
-        let walked = walker.build().collect::<Vec<_>>();
+        let walked = deploy_walker(project_path, no_gitignore).collect::<Vec<_>>();
         let total = walked.len();
         on_progress(0, total);
 
         for (i, entry) in walked.into_iter().enumerate() {
             let entry = entry?;
             let path = entry.path();
-            if path
-                .components()
-                .any(|c| ignore_paths.contains(&c.as_os_str()))
-            {
+            if builtin_ignore(path).is_some() {
                 continue;
             }
-            let stripped =
-                std::path::PathBuf::from(".").join(path.strip_prefix(archive_prefix_path)?);
+            let stripped = PathBuf::from(".").join(path.strip_prefix(archive_prefix_path)?);
             archive.append_path_with_name(path, stripped)?;
             on_progress(i + 1, total);
         }
+        archive.finish()?;
     }
     parz.finish()?;
+    Ok(())
+}
 
-    let body = Arc::try_unwrap(arc)
-        .map_err(|_| {
-            anyhow::anyhow!("internal error: tarball buffer still has references after compression")
-        })?
-        .into_inner()
-        .map_err(|e| anyhow::anyhow!("internal error: failed to unwrap tarball buffer: {e}"))?;
-    Ok(body)
+/// Forwards compressed output to the upload body in fixed-size chunks.
+/// Blocks when the upload falls behind, and fails once the upload has gone
+/// away so compression stops early.
+struct ChannelWriter {
+    tx: mpsc::Sender<io::Result<Vec<u8>>>,
+    buf: Vec<u8>,
+    bytes_written: Arc<AtomicU64>,
+}
+
+impl ChannelWriter {
+    fn send_buffered(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(UPLOAD_CHUNK_SIZE));
+        let len = chunk.len() as u64;
+        self.tx.blocking_send(Ok(chunk)).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "upload ended before the archive was complete",
+            )
+        })?;
+        self.bytes_written.fetch_add(len, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if self.buf.len() >= UPLOAD_CHUNK_SIZE {
+            self.send_buffered()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_buffered()
+    }
+}
+
+impl Drop for ChannelWriter {
+    fn drop(&mut self) {
+        let _ = self.send_buffered();
+    }
+}
+
+/// Start streaming a gzipped tarball of a project directory, respecting
+/// .railwayignore and .gitignore. Compression runs on a blocking thread and
+/// only stays a few chunks ahead of the upload, so memory use doesn't grow
+/// with the size of the project.
+///
+/// `on_progress` is called with `(current, total)` after each entry is processed.
+/// The first call is `(0, total)` once indexing is complete (before compression begins).
+pub fn create_deploy_tarball(
+    project_path: &Path,
+    archive_prefix_path: &Path,
+    no_gitignore: bool,
+    on_progress: impl FnMut(usize, usize) + Send + 'static,
+) -> DeployArchive {
+    let (tx, rx) = mpsc::channel::<io::Result<Vec<u8>>>(UPLOAD_CHANNEL_CAPACITY);
+    let bytes_written = Arc::new(AtomicU64::new(0));
+    let writer = ChannelWriter {
+        tx: tx.clone(),
+        buf: Vec::with_capacity(UPLOAD_CHUNK_SIZE),
+        bytes_written: bytes_written.clone(),
+    };
+
+    let project_path = project_path.to_path_buf();
+    let archive_prefix_path = archive_prefix_path.to_path_buf();
+    let task = tokio::task::spawn_blocking(move || {
+        let result = write_deploy_tarball(
+            writer,
+            &project_path,
+            &archive_prefix_path,
+            no_gitignore,
+            on_progress,
+        );
+        // Fail the body rather than letting it end early, so a partial
+        // archive is never mistaken for a complete one.
+        if let Err(e) = &result {
+            let _ = tx.blocking_send(Err(io::Error::other(e.to_string())));
+        }
+        result
+    });
+
+    DeployArchive {
+        chunks: rx,
+        bytes_written,
+        task,
+    }
+}
+
+/// A file `railway up` would upload, with its content hash.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    pub mode: u32,
+}
+
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o7777
+    }
+    #[cfg(not(unix))]
+    {
+        if metadata.permissions().readonly() {
+            0o444
+        } else {
+            0o644
+        }
+    }
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Hash every file `railway up` would upload.
+pub fn build_upload_manifest(
+    project_path: &Path,
+    archive_prefix_path: &Path,
+    no_gitignore: bool,
+) -> Result<Vec<ManifestEntry>> {
+    let mut entries = Vec::new();
+    for entry in deploy_walker(project_path, no_gitignore) {
+        let entry = entry?;
+        let path = entry.path();
+        if builtin_ignore(path).is_some() || !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let metadata = std::fs::metadata(path)?;
+        entries.push(ManifestEntry {
+            path: archive_path(path, archive_prefix_path)?,
+            size: metadata.len(),
+            sha256: sha256_file(path)?,
+            mode: file_mode(&metadata),
+        });
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+fn up_url(
+    hostname: &str,
+    project_id: &str,
+    environment_id: &str,
+    suffix: &str,
+) -> Result<Url, url::ParseError> {
+    Url::parse(&format!(
+        "https://backboard.{hostname}/project/{project_id}/environment/{environment_id}/up{suffix}",
+    ))
+}
+
+async fn archive_result(task: JoinHandle<Result<()>>) -> Result<()> {
+    task.await.context("Failed to create deploy tarball")?
 }
 
 /// Upload a deploy tarball to Railway's backboard API.
@@ -93,11 +308,15 @@ pub async fn upload_deploy_tarball(
     environment_id: &str,
     service_id: Option<&str>,
     message: Option<&str>,
-    body: Vec<u8>,
+    archive: DeployArchive,
 ) -> Result<UpResponse> {
-    let mut url = Url::parse(&format!(
-        "https://backboard.{hostname}/project/{project_id}/environment/{environment_id}/up",
-    ))?;
+    let DeployArchive {
+        mut chunks,
+        bytes_written,
+        task,
+    } = archive;
+
+    let mut url = up_url(hostname, project_id, environment_id, "")?;
 
     url.query_pairs_mut()
         .append_pair("serviceId", service_id.unwrap_or_default());
@@ -106,7 +325,6 @@ pub async fn upload_deploy_tarball(
         url.query_pairs_mut().append_pair("message", message);
     }
 
-    let body_len = body.len();
     let mut request = client
         .post(url.to_string())
         .header("Content-Type", "application/gzip");
@@ -122,7 +340,16 @@ pub async fn upload_deploy_tarball(
         }
     }
 
-    let res = request.body(body).send().await?;
+    let body = reqwest::Body::wrap_stream(futures::stream::poll_fn(move |cx| chunks.poll_recv(cx)));
+    let res = match request.body(body).send().await {
+        Ok(res) => res,
+        Err(e) => {
+            // A failed archive aborts the body; report why rather than the
+            // resulting transport error.
+            archive_result(task).await?;
+            return Err(e.into());
+        }
+    };
 
     let status = res.status();
     if status != 200 {
@@ -133,8 +360,9 @@ pub async fn upload_deploy_tarball(
 
         if status == 413 {
             let err = res.text().await?;
+            let body_len = bytes_written.load(Ordering::Relaxed);
             return Err(RailwayError::FailedToUpload(format!(
-                "Failed to upload code. File too large ({body_len} bytes): {err}",
+                "Failed to upload code. File too large ({body_len}+ bytes): {err}",
             ))
             .into());
         }
@@ -145,6 +373,322 @@ pub async fn upload_deploy_tarball(
         .into());
     }
 
+    archive_result(task).await?;
+
     let response = res.json::<UpResponse>().await?;
     Ok(response)
 }
+
+/// A file that would be uploaded.
+#[derive(Debug, Serialize)]
+pub struct IncludedFile {
+    pub path: String,
+    pub size: u64,
+}
+
+/// A file or directory left out of the upload, and the rule that did it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExcludedPath {
+    pub path: String,
+    pub is_dir: bool,
+    pub rule: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DeployFileReport {
+    pub included: Vec<IncludedFile>,
+    pub excluded: Vec<ExcludedPath>,
+}
+
+/// Re-evaluates the ignore files the walker reads, in the order it gives
+/// them precedence (.railwayignore, then .ignore, then .gitignore, each from
+/// the closest directory outwards), for paths the walker itself can't be
+/// asked about: why something was skipped, or whether a changed file counts.
+struct IgnoreExplainer {
+    root: PathBuf,
+    file_names: Vec<&'static str>,
+    cache: HashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl IgnoreExplainer {
+    fn new(root: &Path, no_gitignore: bool) -> Self {
+        let mut file_names = vec![RAILWAY_IGNORE_FILE, ".ignore"];
+        if !no_gitignore {
+            file_names.push(".gitignore");
+        }
+        Self {
+            root: std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf()),
+            file_names,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn matcher(&mut self, dir: &Path, file_name: &str) -> Option<&Gitignore> {
+        let file = dir.join(file_name);
+        self.cache
+            .entry(file.clone())
+            .or_insert_with(|| {
+                if !file.is_file() {
+                    return None;
+                }
+                let mut builder = GitignoreBuilder::new(dir);
+                builder.add(&file);
+                builder.build().ok()
+            })
+            .as_ref()
+    }
+
+    /// The rule that excludes `path` (or one of its parent directories), as
+    /// `pattern (file)`, or `None` if no ignore file excludes it.
+    fn ignoring_rule(&mut self, path: &Path, is_dir: bool) -> Option<String> {
+        let path = std::path::absolute(path).ok()?;
+        let root = self.root.clone();
+        for file_name in self.file_names.clone() {
+            for dir in path.ancestors().skip(1) {
+                let Some(matcher) = self.matcher(dir, file_name) else {
+                    continue;
+                };
+                match matcher.matched_path_or_any_parents(&path, is_dir) {
+                    Match::None => continue,
+                    // Re-included by a `!pattern`
+                    Match::Whitelist(_) => return None,
+                    Match::Ignore(glob) => {
+                        let from = glob
+                            .from()
+                            .map(|f| f.strip_prefix(&root).unwrap_or(f).display().to_string())
+                            .unwrap_or_else(|| file_name.to_string());
+                        return Some(format!("{} ({from})", glob.original()));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn explain(&mut self, path: &Path, is_dir: bool) -> String {
+        self.ignoring_rule(path, is_dir)
+            .unwrap_or_else(|| "git exclude or global gitignore".to_string())
+    }
+
+    fn is_ignore_file(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| self.file_names.contains(&name))
+    }
+}
+
+/// A predicate for `FileWatcher::directory` that accepts changes to files
+/// `railway up` would upload. Edits to the ignore files themselves always
+/// count, and drop the cached rules so the new ones apply.
+pub fn deploy_change_filter(
+    project_path: &Path,
+    no_gitignore: bool,
+) -> impl Fn(&Path) -> bool + Send + Sync + 'static {
+    let explainer = std::sync::Mutex::new(IgnoreExplainer::new(project_path, no_gitignore));
+    move |path: &Path| {
+        let Ok(mut explainer) = explainer.lock() else {
+            return true;
+        };
+        let relative = path.strip_prefix(&explainer.root).unwrap_or(path);
+        if builtin_ignore(relative).is_some() {
+            return false;
+        }
+        if explainer.is_ignore_file(path) {
+            explainer.cache.clear();
+            return true;
+        }
+        explainer.ignoring_rule(path, path.is_dir()).is_none()
+    }
+}
+
+/// List what `railway up` would upload from a directory, and why everything
+/// else is left out.
+pub fn deploy_file_report(
+    project_path: &Path,
+    archive_prefix_path: &Path,
+    no_gitignore: bool,
+) -> Result<DeployFileReport> {
+    let mut walked = HashSet::new();
+    for entry in deploy_walker(project_path, no_gitignore) {
+        let entry = entry?;
+        if builtin_ignore(entry.path()).is_none() {
+            walked.insert(entry.path().to_path_buf());
+        }
+    }
+
+    let mut report = DeployFileReport::default();
+    let mut explainer = IgnoreExplainer::new(project_path, no_gitignore);
+    let mut pending = vec![project_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut children = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .collect::<std::io::Result<Vec<_>>>()?;
+        children.sort_by_key(|c| c.file_name());
+
+        for child in children {
+            let path = child.path();
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            let is_dir = metadata.is_dir();
+            if walked.contains(&path) {
+                if is_dir {
+                    pending.push(path);
+                } else {
+                    report.included.push(IncludedFile {
+                        path: archive_path(&path, archive_prefix_path)?,
+                        size: metadata.len(),
+                    });
+                }
+                continue;
+            }
+
+            let rule = match builtin_ignore(&path) {
+                Some(name) => format!("always excluded ({name})"),
+                None => explainer.explain(&path, is_dir),
+            };
+            report.excluded.push(ExcludedPath {
+                path: archive_path(&path, archive_prefix_path)?,
+                is_dir,
+                rule,
+            });
+        }
+    }
+
+    report.included.sort_by(|a, b| a.path.cmp(&b.path));
+    report.excluded.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("dist")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(root.join("dist/out.js"), "built").unwrap();
+        std::fs::write(root.join("debug.log"), "noise").unwrap();
+        std::fs::write(root.join("node_modules/pkg/index.js"), "dep").unwrap();
+        std::fs::write(root.join(".railwayignore"), "dist/\n*.log\n").unwrap();
+        dir
+    }
+
+    fn tar_entries(gz: &[u8]) -> Vec<String> {
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(gz));
+        let mut names: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|e| {
+                let path = e.unwrap().path().unwrap().display().to_string();
+                path.trim_start_matches("./").to_string()
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// `Vec<u8>` can't be read back once the compressor owns it.
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(data);
+            Ok(data.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn tarball_respects_ignore_files() {
+        let dir = project();
+        let buf = SharedBuf::default();
+        write_deploy_tarball(buf.clone(), dir.path(), dir.path(), false, |_, _| {}).unwrap();
+        let names = tar_entries(&buf.0.lock().unwrap());
+        assert!(names.contains(&"src/main.rs".to_string()));
+        assert!(
+            !names
+                .iter()
+                .any(|n| n.contains("dist") || n.contains("node_modules"))
+        );
+        assert!(!names.iter().any(|n| n.ends_with(".log")));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn streamed_archive_produces_a_complete_tarball() {
+        let dir = project();
+        let mut archive = create_deploy_tarball(dir.path(), dir.path(), false, |_, _| {});
+        let bytes = archive.bytes_written();
+        let mut gz = Vec::new();
+        while let Some(chunk) = archive.chunks.recv().await {
+            gz.extend_from_slice(&chunk.unwrap());
+        }
+        archive_result(archive.task).await.unwrap();
+
+        assert_eq!(bytes.load(Ordering::Relaxed), gz.len() as u64);
+        assert!(tar_entries(&gz).contains(&"src/main.rs".to_string()));
+    }
+
+    #[test]
+    fn manifest_hashes_every_uploaded_file() {
+        let dir = project();
+        let entries = build_upload_manifest(dir.path(), dir.path(), false).unwrap();
+        let paths: Vec<_> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec![".railwayignore", "src/main.rs"]);
+
+        let main = entries.iter().find(|e| e.path == "src/main.rs").unwrap();
+        assert_eq!(main.size, 12);
+        assert_eq!(
+            main.sha256,
+            format!("{:x}", Sha256::digest(b"fn main() {}"))
+        );
+    }
+
+    #[test]
+    fn change_filter_skips_ignored_paths() {
+        let dir = project();
+        let root = std::path::absolute(dir.path()).unwrap();
+        let filter = deploy_change_filter(dir.path(), false);
+
+        assert!(filter(&root.join("src/main.rs")));
+        assert!(filter(&root.join("src/new_file.rs")));
+        assert!(!filter(&root.join("dist/out.js")));
+        assert!(!filter(&root.join("server.log")));
+        assert!(!filter(&root.join("node_modules/pkg/index.js")));
+
+        // Changing the ignore file applies the new rules
+        std::fs::write(root.join(".railwayignore"), "src/\n").unwrap();
+        assert!(filter(&root.join(".railwayignore")));
+        assert!(!filter(&root.join("src/main.rs")));
+        assert!(filter(&root.join("dist/out.js")));
+    }
+
+    #[test]
+    fn dry_run_report_names_the_excluding_rule() {
+        let dir = project();
+        let report = deploy_file_report(dir.path(), dir.path(), false).unwrap();
+
+        let included: Vec<_> = report.included.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(included, vec![".railwayignore", "src/main.rs"]);
+
+        let rule = |path: &str| {
+            report
+                .excluded
+                .iter()
+                .find(|e| e.path == path)
+                .map(|e| e.rule.clone())
+                .unwrap()
+        };
+        assert_eq!(rule("dist"), "dist/ (.railwayignore)");
+        assert!(rule("debug.log").starts_with("*.log"));
+        assert_eq!(rule("node_modules"), "always excluded (node_modules)");
+    }
+}
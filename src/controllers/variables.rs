@@ -2,8 +2,9 @@ use crate::{
     client::post_graphql,
     commands::{Configs, queries},
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
 use reqwest::Client;
+use serde::Serialize;
 use std::fmt::Display;
 use std::{collections::BTreeMap, str::FromStr};
 
@@ -41,6 +42,59 @@ pub async fn get_service_variables(
     Ok(variables)
 }
 
+/// Fetches an environment's shared variables, unrendered, so references
+/// inside them come back as written.
+pub async fn get_shared_variables(
+    client: &Client,
+    configs: &Configs,
+    project_id: String,
+    environment_id: String,
+) -> Result<BTreeMap<String, String>> {
+    let vars = queries::shared_variables::Variables {
+        project_id,
+        environment_id,
+    };
+    let response =
+        post_graphql::<queries::SharedVariables, _>(client, configs.get_backboard(), vars).await?;
+
+    Ok(response
+        .variables
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .collect())
+}
+
+/// Fetches a service's own variables unrendered, so `${{...}}` references
+/// come back as written instead of resolved. For copying variables between
+/// environments, where a resolved value would pin the source's.
+pub async fn get_unrendered_service_variables(
+    client: &Client,
+    configs: &Configs,
+    project_id: String,
+    environment_id: String,
+    service_id: String,
+) -> Result<BTreeMap<String, String>> {
+    let vars = queries::service_variables::Variables {
+        project_id,
+        environment_id,
+        service_id,
+    };
+    let response =
+        post_graphql::<queries::ServiceVariables, _>(client, configs.get_backboard(), vars).await?;
+
+    Ok(response
+        .variables
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .collect())
+}
+
+/// Variables Railway sets itself (`RAILWAY_*`). They differ per environment
+/// and can't be overridden meaningfully, so copies leave them out.
+pub fn is_railway_provided(key: &str) -> bool {
+    key.starts_with("RAILWAY_")
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Variable {
     pub key: String,
@@ -104,3 +158,376 @@ impl Display for Variable {
         write!(f, "{}={}", self.key, self.value)
     }
 }
+
+/// File formats for `railway variable export` / `import`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum VariableFileFormat {
+    /// KEY=value lines, as read by most dotenv loaders
+    Dotenv,
+    /// A JSON object of string values
+    Json,
+    /// A YAML mapping of string values
+    Yaml,
+    /// `export KEY='value'` lines to `source` from a shell (export only)
+    Shell,
+}
+
+impl VariableFileFormat {
+    /// Guess the format of a file to import from its extension.
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::Json,
+            Some("yaml" | "yml") => Self::Yaml,
+            _ => Self::Dotenv,
+        }
+    }
+}
+
+/// Render variables in a file format.
+pub fn format_variables(
+    variables: &BTreeMap<String, String>,
+    format: VariableFileFormat,
+) -> Result<String> {
+    Ok(match format {
+        VariableFileFormat::Dotenv => variables
+            .iter()
+            .map(|(key, value)| format!("{key}={}\n", dotenv_quote(value)))
+            .collect(),
+        VariableFileFormat::Json => format!("{}\n", serde_json::to_string_pretty(variables)?),
+        VariableFileFormat::Yaml => serde_yaml::to_string(variables)?,
+        VariableFileFormat::Shell => variables
+            .iter()
+            .map(|(key, value)| {
+                // try_quote only fails on interior NUL bytes, which variable
+                // values can't contain
+                let quoted = shlex::try_quote(value).unwrap_or_default();
+                format!("export {key}={quoted}\n")
+            })
+            .collect(),
+    })
+}
+
+/// Leave simple values bare; double-quote anything a dotenv parser could
+/// misread, escaping so the value round-trips.
+fn dotenv_quote(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '\\' | '$' | '`'));
+    if !needs_quotes {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '$' => quoted.push_str("\\$"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Parse a variables file.
+pub fn parse_variables(
+    contents: &str,
+    format: VariableFileFormat,
+) -> Result<BTreeMap<String, String>> {
+    match format {
+        VariableFileFormat::Dotenv | VariableFileFormat::Shell => parse_dotenv(contents),
+        VariableFileFormat::Json => {
+            let map: BTreeMap<String, serde_json::Value> =
+                serde_json::from_str(contents).context("Expected a JSON object of variables")?;
+            map.into_iter()
+                .map(|(key, value)| Ok((key.clone(), scalar_to_string(&key, value)?)))
+                .collect()
+        }
+        VariableFileFormat::Yaml => {
+            let map: BTreeMap<String, serde_yaml::Value> =
+                serde_yaml::from_str(contents).context("Expected a YAML mapping of variables")?;
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = serde_json::to_value(value)?;
+                    Ok((key.clone(), scalar_to_string(&key, value)?))
+                })
+                .collect()
+        }
+    }
+}
+
+fn scalar_to_string(key: &str, value: serde_json::Value) -> Result<String> {
+    match value {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        serde_json::Value::Bool(b) => Ok(b.to_string()),
+        serde_json::Value::Null => Ok(String::new()),
+        _ => anyhow::bail!("Variable {key} must be a string, number or boolean"),
+    }
+}
+
+/// Parse dotenv syntax: `KEY=value` lines with optional `export `, `#`
+/// comments, single-quoted literals, and double-quoted values with escapes
+/// that may span lines.
+fn parse_dotenv(contents: &str) -> Result<BTreeMap<String, String>> {
+    let mut variables = BTreeMap::new();
+    let mut lines = contents.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, rest)) = line.split_once('=') else {
+            anyhow::bail!("Line {}: expected KEY=value", index + 1);
+        };
+        let key = key.trim();
+        if key.is_empty() || key.chars().any(char::is_whitespace) {
+            anyhow::bail!("Line {}: invalid variable name \"{key}\"", index + 1);
+        }
+
+        let rest = rest.trim_start();
+        let value = if let Some(quoted) = rest.strip_prefix('"') {
+            let mut raw = quoted.to_string();
+            // A value without its closing quote continues on the next line
+            while !has_closing_quote(&raw) {
+                let Some((_, next)) = lines.next() else {
+                    anyhow::bail!("Line {}: unterminated quoted value for {key}", index + 1);
+                };
+                raw.push('\n');
+                raw.push_str(next);
+            }
+            unescape_double_quoted(&raw)
+        } else if let Some(quoted) = rest.strip_prefix('\'') {
+            match quoted.find('\'') {
+                Some(end) => quoted[..end].to_string(),
+                None => anyhow::bail!("Line {}: unterminated quoted value for {key}", index + 1),
+            }
+        } else {
+            // Unquoted: a ` #` starts a trailing comment
+            let value = match rest.find(" #") {
+                Some(comment) => &rest[..comment],
+                None => rest,
+            };
+            value.trim_end().to_string()
+        };
+        variables.insert(key.to_string(), value);
+    }
+
+    Ok(variables)
+}
+
+fn has_closing_quote(raw: &str) -> bool {
+    let mut escaped = false;
+    for c in raw.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Unescape a double-quoted value up to its closing quote.
+fn unescape_double_quoted(raw: &str) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some(other) => value.push(other),
+                None => value.push('\\'),
+            },
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
+/// Differences between two sets of variables, going from `from` to `to`.
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct VariableDiff {
+    pub added: BTreeMap<String, String>,
+    /// `(from, to)` values
+    pub changed: BTreeMap<String, (String, String)>,
+    pub removed: BTreeMap<String, String>,
+}
+
+impl VariableDiff {
+    pub fn new(from: &BTreeMap<String, String>, to: &BTreeMap<String, String>) -> Self {
+        let mut diff = Self::default();
+        for (key, value) in to {
+            match from.get(key) {
+                None => {
+                    diff.added.insert(key.clone(), value.clone());
+                }
+                Some(old) if old != value => {
+                    diff.changed
+                        .insert(key.clone(), (old.clone(), value.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, value) in from {
+            if !to.contains_key(key) {
+                diff.removed.insert(key.clone(), value.clone());
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Drop Railway-provided variables from every side of the diff.
+    pub fn without_railway_provided(mut self) -> Self {
+        self.added.retain(|key, _| !is_railway_provided(key));
+        self.changed.retain(|key, _| !is_railway_provided(key));
+        self.removed.retain(|key, _| !is_railway_provided(key));
+        self
+    }
+
+    /// Print as `+`/`~`/`-` lines. Values are only shown with `show_values`.
+    pub fn print(&self, show_values: bool) {
+        use colored::Colorize;
+
+        for (key, value) in &self.added {
+            let line = if show_values {
+                format!("+ {key}={value}")
+            } else {
+                format!("+ {key}")
+            };
+            println!("{}", line.green());
+        }
+        for (key, (old, new)) in &self.changed {
+            let line = if show_values {
+                format!("~ {key}: {old} -> {new}")
+            } else {
+                format!("~ {key}")
+            };
+            println!("{}", line.yellow());
+        }
+        for (key, value) in &self.removed {
+            let line = if show_values {
+                format!("- {key}={value}")
+            } else {
+                format!("- {key}")
+            };
+            println!("{}", line.red());
+        }
+    }
+
+    /// The diff as JSON; values are replaced by `null` unless `show_values`.
+    pub fn to_json(&self, show_values: bool) -> serde_json::Value {
+        if show_values {
+            return serde_json::json!(self);
+        }
+        let keys = |map: Vec<&String>| map.into_iter().cloned().collect::<Vec<_>>();
+        serde_json::json!({
+            "added": keys(self.added.keys().collect()),
+            "changed": keys(self.changed.keys().collect()),
+            "removed": keys(self.removed.keys().collect()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod variable_file_tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn dotenv_parses_quotes_comments_and_exports() {
+        let parsed = parse_variables(
+            "# comment\nexport A=1\nB = plain value # trailing\nC=\"line1\\nline2 \\\"q\\\"\"\nD='$literal # kept'\nE=\"multi\nline\"\nF=\n",
+            VariableFileFormat::Dotenv,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            vars(&[
+                ("A", "1"),
+                ("B", "plain value"),
+                ("C", "line1\nline2 \"q\""),
+                ("D", "$literal # kept"),
+                ("E", "multi\nline"),
+                ("F", ""),
+            ])
+        );
+        assert!(parse_variables("NOT A LINE\n", VariableFileFormat::Dotenv).is_err());
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        let original = vars(&[
+            ("PLAIN", "abc"),
+            ("SPACES", "a b # c"),
+            ("QUOTES", "say \"hi\" and 'bye'"),
+            ("MULTI", "one\ntwo"),
+            ("DOLLAR", "${{Postgres.DATABASE_URL}}"),
+            ("EMPTY", ""),
+        ]);
+        for format in [
+            VariableFileFormat::Dotenv,
+            VariableFileFormat::Json,
+            VariableFileFormat::Yaml,
+        ] {
+            let rendered = format_variables(&original, format).unwrap();
+            assert_eq!(
+                parse_variables(&rendered, format).unwrap(),
+                original,
+                "{format:?}"
+            );
+        }
+
+        let shell = format_variables(&vars(&[("A", "it's")]), VariableFileFormat::Shell).unwrap();
+        assert_eq!(
+            shlex::split(shell.trim()).unwrap(),
+            vec!["export", "A=it's"]
+        );
+    }
+
+    #[test]
+    fn yaml_and_json_scalars_become_strings() {
+        let parsed =
+            parse_variables("PORT: 8080\nDEBUG: true\n", VariableFileFormat::Yaml).unwrap();
+        assert_eq!(parsed, vars(&[("DEBUG", "true"), ("PORT", "8080")]));
+        assert!(parse_variables("{\"A\": {\"nested\": 1}}", VariableFileFormat::Json).is_err());
+    }
+
+    #[test]
+    fn diff_reports_added_changed_and_removed() {
+        let diff = VariableDiff::new(
+            &vars(&[("KEEP", "1"), ("CHANGE", "old"), ("DROP", "x")]),
+            &vars(&[("KEEP", "1"), ("CHANGE", "new"), ("ADD", "y")]),
+        );
+        assert_eq!(diff.added, vars(&[("ADD", "y")]));
+        assert_eq!(
+            diff.changed,
+            BTreeMap::from([("CHANGE".to_string(), ("old".to_string(), "new".to_string()))])
+        );
+        assert_eq!(diff.removed, vars(&[("DROP", "x")]));
+        assert_eq!(
+            diff.to_json(false),
+            serde_json::json!({"added": ["ADD"], "changed": ["CHANGE"], "removed": ["DROP"]})
+        );
+    }
+}
@@ -94,6 +94,9 @@ pub enum RailwayError {
     )]
     EnvironmentNotFound(String),
 
+    #[error("Profile \"{0}\" not found. Run `railway profile add {0}` to create it.")]
+    ProfileNotFound(String),
+
     #[error("Workspace \"{0}\" not found.")]
     WorkspaceNotFound(String),
 
@@ -184,6 +187,7 @@ impl RailwayError {
             RailwayError::EnvironmentRestricted(_) => "ENVIRONMENT_RESTRICTED",
             RailwayError::NoProjects => "NO_PROJECTS",
             RailwayError::EnvironmentNotFound(_) => "ENVIRONMENT_NOT_FOUND",
+            RailwayError::ProfileNotFound(_) => "PROFILE_NOT_FOUND",
             RailwayError::WorkspaceNotFound(_) => "WORKSPACE_NOT_FOUND",
             RailwayError::ServiceNotFound(_) => "SERVICE_NOT_FOUND",
             RailwayError::ProjectHasNoServices => "PROJECT_HAS_NO_SERVICES",
@@ -2,6 +2,7 @@ use graphql_client::GraphQLQuery;
 type EnvironmentVariables = std::collections::BTreeMap<String, String>;
 #[allow(clippy::upper_case_acronyms)] // graphql client expects a type called JSON
 type JSON = serde_json::Value;
+type NotificationChannelConfig = serde_json::Value;
 use chrono::{DateTime as DateTimeType, Utc};
 
 use crate::controllers;
@@ -18,6 +19,38 @@ type EnvironmentConfig = controllers::config::EnvironmentConfig;
 #[allow(dead_code)]
 pub struct CliEventTrack;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/DeploymentCancel.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct DeploymentCancel;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/DeploymentRollback.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct DeploymentRollback;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/DeploymentStop.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct DeploymentStop;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/DeploymentApprove.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct DeploymentApprove;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "src/gql/schema.json",
@@ -253,6 +286,14 @@ pub struct EnableServiceCdn;
 )]
 pub struct UpdateServiceEdgeConfig;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/UpdateServiceEdgeRules.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct UpdateServiceEdgeRules;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "src/gql/schema.json",
@@ -710,3 +751,123 @@ pub struct VolumeInstanceBackupRestore;
     skip_serializing_none
 )]
 pub struct VolumeInstanceBackupScheduleUpdate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectTokenCreate.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectTokenCreate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectTokenDelete.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectTokenDelete;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ApiTokenCreate.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ApiTokenCreate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ApiTokenDelete.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ApiTokenDelete;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectMemberAdd.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectMemberAdd;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectMemberRemove.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectMemberRemove;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectMemberUpdate.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectMemberUpdate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectInvitationCreate.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectInvitationCreate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectInvitationResend.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectInvitationResend;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/ProjectInvitationDelete.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectInvitationDelete;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/NotificationRuleCreate.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct NotificationRuleCreate;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/NotificationRuleDelete.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct NotificationRuleDelete;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/NotificationDeliveriesMarkAsRead.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct NotificationDeliveriesMarkAsRead;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/WebhookTest.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct WebhookTest;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/mutations/strings/DockerComposeImport.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct DockerComposeImport;
@@ -7,6 +7,8 @@ type EnvironmentVariables = std::collections::BTreeMap<String, Option<String>>;
 type DeploymentMeta = serde_json::Value;
 #[allow(clippy::upper_case_acronyms)] // graphql client expects a type called JSON
 type JSON = serde_json::Value;
+type NotificationChannelConfig = serde_json::Value;
+type NotificationPayload = serde_json::Value;
 
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -84,6 +86,24 @@ pub struct UserProjects;
 )]
 pub struct VariablesForServiceDeployment;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/SharedVariables.graphql",
+    response_derives = "Debug, Serialize, Clone",
+    skip_serializing_none
+)]
+pub struct SharedVariables;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/ServiceVariables.graphql",
+    response_derives = "Debug, Serialize, Clone",
+    skip_serializing_none
+)]
+pub struct ServiceVariables;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "src/gql/schema.json",
@@ -100,6 +120,14 @@ pub struct Deployments;
 )]
 pub struct DeploymentStatus;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/DeploymentEvents.graphql",
+    response_derives = "Debug, Serialize, Clone, PartialEq"
+)]
+pub struct DeploymentEvents;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "src/gql/schema.json",
@@ -156,6 +184,22 @@ pub struct Domains;
 )]
 pub struct ServiceEdgeConfig;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/ServiceEdgeRules.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ServiceEdgeRules;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/ValidateServiceEdgeRules.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ValidateServiceEdgeRules;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "src/gql/schema.json",
@@ -220,6 +264,54 @@ pub struct PrivateNetworkEndpointNameAvailable;
 )]
 pub struct ProjectToken;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/ProjectTokens.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ProjectTokens;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/ApiTokens.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct ApiTokens;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/ProjectMembers.graphql",
+    response_derives = "Debug, Serialize, Clone, PartialEq"
+)]
+pub struct ProjectMembers;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/WorkspaceMembers.graphql",
+    response_derives = "Debug, Serialize, Clone, PartialEq"
+)]
+pub struct WorkspaceMembers;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/AuditLogs.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct AuditLogs;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/AuditLogEventTypes.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct AuditLogEventTypes;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     schema_path = "src/gql/schema.json",
@@ -533,3 +625,19 @@ pub struct VolumeInstanceBackupList;
     response_derives = "Debug, Serialize, Clone"
 )]
 pub struct VolumeInstanceBackupScheduleList;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/NotificationRules.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct NotificationRules;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/gql/schema.json",
+    query_path = "src/gql/queries/strings/NotificationDeliveries.graphql",
+    response_derives = "Debug, Serialize, Clone"
+)]
+pub struct NotificationDeliveries;
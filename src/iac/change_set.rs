@@ -10,7 +10,7 @@ use super::partial::{
 
 pub const RAILWAY_CHANGE_SET_VERSION: u32 = 1;
 const MASKED_CREDENTIAL_VALUE: &str = "*****";
-const REDACTED_VARIABLE_VALUE: &str = "«hidden»";
+pub(super) const REDACTED_VARIABLE_VALUE: &str = "«hidden»";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ChangeSet {
@@ -206,6 +206,13 @@ pub fn diff_graphs(options: DiffOptions<'_>) -> ChangeSet {
     change_set_result(changes, diagnostics, options.partial, declared)
 }
 
+/// The `<type>.<name>` address in a `resources.<type>.<name>[.field…]` path.
+pub(super) fn address_from_path(path: &str) -> String {
+    path.strip_prefix("resources.")
+        .map(|rest| rest.splitn(3, '.').take(2).collect::<Vec<_>>().join("."))
+        .unwrap_or_default()
+}
+
 fn change_set_result(
     changes: Vec<Value>,
     diagnostics: Vec<Diagnostic>,
@@ -880,7 +887,7 @@ fn changed_leaf_paths(before: &Value, after: &Value, prefix: &str) -> Vec<String
         .collect()
 }
 
-fn flatten_for_diff(value: &Value, prefix: &str) -> Map<String, Value> {
+pub(super) fn flatten_for_diff(value: &Value, prefix: &str) -> Map<String, Value> {
     match value {
         Value::Object(map) if !map.is_empty() => {
             let mut out = Map::new();
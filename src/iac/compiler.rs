@@ -4,7 +4,7 @@ use super::graph::{
     Edge, EnvironmentNode, ProjectNode, RAILWAY_GRAPH_VERSION, RailwayGraph, resource_addr,
     resource_address, resource_name, resource_type,
 };
-use super::json::{field, field_str, prune_empty};
+use super::json::{field, field_str, merge_patch, prune_empty};
 
 #[derive(Debug, Clone, Default)]
 pub struct CompileOptions {
@@ -113,25 +113,83 @@ pub fn project_definition_to_graph(definition: &Value) -> RailwayGraph {
                 .unwrap_or("imported-project")
                 .to_string(),
         },
-        environments: definition
-            .get("environments")
-            .and_then(Value::as_array)
-            .map(|names| {
-                names
-                    .iter()
-                    .filter_map(|name| {
-                        name.as_str().map(|name| EnvironmentNode {
-                            name: name.to_string(),
-                        })
+        environments: match definition.get("environments") {
+            Some(Value::Array(names)) => names
+                .iter()
+                .filter_map(|name| {
+                    name.as_str().map(|name| EnvironmentNode {
+                        name: name.to_string(),
                     })
-                    .collect()
-            })
-            .unwrap_or_default(),
+                })
+                .collect(),
+            Some(Value::Object(overlays)) => overlays
+                .keys()
+                .map(|name| EnvironmentNode { name: name.clone() })
+                .collect(),
+            _ => Vec::new(),
+        },
         resources,
         edges,
     }
 }
 
+/// Merge the overlay block for `environment` into the base definition.
+///
+/// `environments` may be a list of names or an object keyed by environment
+/// name, where each value is `{ resources: { <address or name>: patch } }`.
+/// Patches are JSON merge patches, so `null` removes a field and arrays
+/// (e.g. custom domains) replace the base value. An environment without an
+/// overlay gets the base definition unchanged.
+pub fn apply_environment_overlay(
+    definition: &Value,
+    environment: Option<&str>,
+) -> Result<Value, String> {
+    let Some(overlays) = definition.get("environments").and_then(Value::as_object) else {
+        return Ok(definition.clone());
+    };
+    let Some(overlay) = environment.and_then(|name| overlays.get(name)) else {
+        return Ok(definition.clone());
+    };
+    let environment = environment.unwrap_or_default();
+    let patches = match overlay.get("resources") {
+        None | Some(Value::Null) => return Ok(definition.clone()),
+        Some(Value::Object(patches)) => patches,
+        Some(_) => {
+            return Err(format!(
+                "environments.{environment}.resources must be an object keyed by resource address or name"
+            ));
+        }
+    };
+
+    let mut resources = flatten_resources(
+        definition
+            .get("resources")
+            .or_else(|| definition.get("services")),
+    );
+    for (key, patch) in patches {
+        let matches = |resource: &Value| {
+            let name = resource_name(resource);
+            let kind = field_str(resource, "type").unwrap_or("service");
+            field_str(resource, "address") == Some(key.as_str())
+                || resource_address(kind, name) == *key
+                || name == key
+        };
+        let Some(resource) = resources.iter_mut().find(|resource| matches(resource)) else {
+            return Err(format!(
+                "environments.{environment}.resources.{key} does not match a declared resource"
+            ));
+        };
+        merge_patch(resource, patch);
+    }
+
+    let mut definition = definition.clone();
+    if let Some(object) = definition.as_object_mut() {
+        object.remove("services");
+        object.insert("resources".into(), Value::Array(resources));
+    }
+    Ok(definition)
+}
+
 fn flatten_resources(value: Option<&Value>) -> Vec<Value> {
     let Some(value) = value else {
         return Vec::new();
@@ -0,0 +1,374 @@
+//! Declarative `.railway/railway.{toml,yaml,json}` project definitions.
+//!
+//! These carry the same project definition the language SDKs export, so they
+//! compile through `project_definition_to_graph` unchanged. A few shorthands
+//! keep hand-written files short: plain variable values, `source` without a
+//! `type`, and `address`/`kind` derived from `type` and `name`.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Map, Value, json};
+
+use super::compiler::project_definition_to_graph;
+use super::graph::{Edge, resource_address};
+use super::json::field_str;
+
+/// JSON Schema for the declarative formats, printed by `railway config schema`.
+pub const PROJECT_SCHEMA: &str = include_str!("../../assets/iac/railway.schema.json");
+
+const RESOURCE_TYPES: &[&str] = &["service", "database", "volume", "bucket", "group"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarativeFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl DeclarativeFormat {
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// A parsed declarative file, along with the 1-based line each declared
+/// resource starts on (in `resources` order) for diagnostics.
+pub struct DeclarativeFile {
+    pub payload: Value,
+    pub resource_lines: Vec<Option<usize>>,
+}
+
+pub fn parse_file(file: &Path, format: DeclarativeFormat) -> Result<DeclarativeFile> {
+    let source = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    parse_source(&source, format).with_context(|| format!("Invalid {}", display_name(file)))
+}
+
+pub fn parse_source(source: &str, format: DeclarativeFormat) -> Result<DeclarativeFile> {
+    // Each parser's error already names the line and column.
+    let mut payload: Value = match format {
+        DeclarativeFormat::Toml => toml::from_str(source)?,
+        DeclarativeFormat::Yaml => serde_yaml::from_str(source)?,
+        DeclarativeFormat::Json => serde_json::from_str(source)?,
+    };
+    if let Some(object) = payload.as_object_mut() {
+        object.remove("$schema");
+    }
+
+    let count = payload
+        .get("resources")
+        .and_then(Value::as_array)
+        .map(Vec::len)
+        .unwrap_or(0);
+    let resource_lines = resource_lines(source, format, &payload, count);
+
+    let mut errors = Vec::new();
+    if !payload.is_object() {
+        bail!("Expected a table/object at the top level");
+    }
+    match payload.get_mut("resources") {
+        None => errors.push(("resources".to_string(), None, "is required".to_string())),
+        Some(Value::Array(resources)) => {
+            for (index, resource) in resources.iter_mut().enumerate() {
+                let line = resource_lines.get(index).copied().flatten();
+                if let Err(message) = normalize_resource(resource) {
+                    errors.push((format!("resources[{index}]"), line, message));
+                }
+            }
+        }
+        Some(_) => errors.push(("resources".to_string(), None, "must be a list".to_string())),
+    }
+    if !errors.is_empty() {
+        bail!(
+            "{}",
+            errors
+                .into_iter()
+                .map(|(path, line, message)| match line {
+                    Some(line) => format!("line {line}: {path} {message}"),
+                    None => format!("{path} {message}"),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    Ok(DeclarativeFile {
+        payload,
+        resource_lines,
+    })
+}
+
+pub fn display_name(file: &Path) -> String {
+    file.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| file.display().to_string())
+}
+
+/// Expand the hand-written shorthands into the SDK's graph shape.
+fn normalize_resource(resource: &mut Value) -> Result<(), String> {
+    let Some(object) = resource.as_object_mut() else {
+        return Err("must be a table/object".to_string());
+    };
+    let kind = object
+        .get("type")
+        .map(|value| value.as_str().ok_or("type must be a string"))
+        .transpose()?
+        .unwrap_or("service")
+        .to_string();
+    if !RESOURCE_TYPES.contains(&kind.as_str()) {
+        return Err(format!(
+            "has unknown type \"{kind}\" (expected one of {})",
+            RESOURCE_TYPES.join(", ")
+        ));
+    }
+    let Some(name) = object
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    else {
+        return Err("needs a name".to_string());
+    };
+    if name.is_empty() {
+        return Err("needs a name".to_string());
+    }
+    object.insert("type".into(), json!(kind));
+    object
+        .entry("address")
+        .or_insert_with(|| json!(resource_address(&kind, &name)));
+
+    if let Some(source) = object.get_mut("source").and_then(Value::as_object_mut) {
+        if !source.contains_key("type") {
+            if source.contains_key("image") {
+                source.insert("type".into(), json!("image"));
+            } else if source.contains_key("repo") {
+                source.insert("type".into(), json!("github"));
+            }
+        }
+    }
+    if kind == "service" && !object.contains_key("kind") {
+        let source = object.get("source");
+        let service_kind = match source.and_then(|s| field_str(s, "type")) {
+            Some("github") => "github",
+            Some("image") => "docker-image",
+            _ => "empty",
+        };
+        object.insert("kind".into(), json!(service_kind));
+    }
+
+    if let Some(variables) = object.get_mut("variables") {
+        let Some(variables) = variables.as_object_mut() else {
+            return Err("variables must be a table/object".to_string());
+        };
+        for (key, value) in variables.iter_mut() {
+            *value = match value {
+                Value::String(s) => json!({ "type": "literal", "value": s }),
+                Value::Number(n) => json!({ "type": "literal", "value": n.to_string() }),
+                Value::Bool(b) => json!({ "type": "literal", "value": b.to_string() }),
+                Value::Object(map) if map.contains_key("type") => continue,
+                Value::Object(map) if map.contains_key("value") => {
+                    json!({ "type": "literal", "value": map["value"] })
+                }
+                _ => {
+                    return Err(format!(
+                        "variable {key} must be a string, number, boolean, or {{ type = \"preserve\" | \"reference\" }}"
+                    ));
+                }
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Find the line each resource starts on. TOML resources are `[[resources]]`
+/// tables; YAML and JSON resources are found by their `name` in order.
+fn resource_lines(
+    source: &str,
+    format: DeclarativeFormat,
+    payload: &Value,
+    count: usize,
+) -> Vec<Option<usize>> {
+    let lines: Vec<&str> = source.lines().collect();
+    if format == DeclarativeFormat::Toml {
+        let mut headers = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.trim() == "[[resources]]")
+            .map(|(index, _)| Some(index + 1));
+        return (0..count).map(|_| headers.next().flatten()).collect();
+    }
+
+    let Some(resources) = payload.get("resources").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    let mut cursor = lines
+        .iter()
+        .position(|line| line.contains("resources"))
+        .unwrap_or(0);
+    resources
+        .iter()
+        .map(|resource| {
+            let name = field_str(resource, "name")?;
+            let found = lines[cursor..]
+                .iter()
+                .position(|line| declares_name(line, name, format))?;
+            cursor += found + 1;
+            Some(cursor)
+        })
+        .collect()
+}
+
+fn declares_name(line: &str, name: &str, format: DeclarativeFormat) -> bool {
+    let line = line
+        .trim()
+        .trim_start_matches("- ")
+        .trim_start_matches('{')
+        .trim();
+    let rest = match format {
+        DeclarativeFormat::Json => line.strip_prefix("\"name\""),
+        _ => line.strip_prefix("name"),
+    };
+    let Some(rest) = rest
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix(':'))
+    else {
+        return false;
+    };
+    let value = rest
+        .trim()
+        .trim_end_matches(',')
+        .trim_end_matches('}')
+        .trim();
+    value.trim_matches(|c| c == '"' || c == '\'') == name
+}
+
+/// Render a project definition (graph resources) as a declarative file,
+/// folding the graph shape back into the shorthands `parse_source` accepts.
+///
+/// Edges aren't written out: they're derived from volume attachments and
+/// reference variables when the file is read. Any of `edges` that the
+/// rendered file wouldn't reproduce is an error rather than silently lost.
+pub fn render_definition(
+    name: &str,
+    environments: &[String],
+    resources: Vec<Value>,
+    edges: &[Edge],
+    format: DeclarativeFormat,
+) -> Result<String> {
+    let resources: Vec<Value> = resources.into_iter().map(simplify_resource).collect();
+    let mut definition = Map::new();
+    if format == DeclarativeFormat::Json {
+        definition.insert("$schema".into(), json!("./railway.schema.json"));
+    }
+    definition.insert("name".into(), json!(name));
+    if !environments.is_empty() {
+        definition.insert("environments".into(), json!(environments));
+    }
+    definition.insert("resources".into(), Value::Array(resources));
+    let definition = Value::Object(definition);
+
+    let rendered = match format {
+        DeclarativeFormat::Toml => format!(
+            "#:schema ./railway.schema.json\n\n{}",
+            toml::to_string_pretty(&definition)?
+        ),
+        DeclarativeFormat::Yaml => format!(
+            "# yaml-language-server: $schema=./railway.schema.json\n{}",
+            serde_yaml::to_string(&definition)?
+        ),
+        DeclarativeFormat::Json => format!("{}\n", serde_json::to_string_pretty(&definition)?),
+    };
+
+    let reparsed = parse_source(&rendered, format)?;
+    let derived = project_definition_to_graph(&reparsed.payload).edges;
+    let lost: Vec<String> = edges
+        .iter()
+        .filter(|edge| !derived.contains(edge))
+        .map(|edge| format!("{} -> {} ({})", edge.from, edge.to, edge.kind))
+        .collect();
+    if !lost.is_empty() {
+        bail!(
+            "Can't express these connections in railway.{}, so they would be lost:\n  {}",
+            format.extension(),
+            lost.join("\n  ")
+        );
+    }
+    Ok(rendered)
+}
+
+fn simplify_resource(resource: Value) -> Value {
+    let Value::Object(mut object) = strip_nulls(resource) else {
+        return Value::Null;
+    };
+    let kind = field_str(&Value::Object(object.clone()), "type")
+        .unwrap_or("service")
+        .to_string();
+    let name = object
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    if object.get("address").and_then(Value::as_str) == Some(&resource_address(&kind, &name)) {
+        object.remove("address");
+    }
+
+    let source_type = object
+        .get("source")
+        .and_then(|source| field_str(source, "type"))
+        .map(str::to_string);
+    let inferred_kind = match source_type.as_deref() {
+        Some("github") => "github",
+        Some("image") => "docker-image",
+        _ => "empty",
+    };
+    if object.get("kind").and_then(Value::as_str) == Some(inferred_kind) {
+        object.remove("kind");
+    }
+    if let Some(source) = object.get_mut("source").and_then(Value::as_object_mut) {
+        let inferable = match source_type.as_deref() {
+            Some("image") => source.contains_key("image"),
+            Some("github") => source.contains_key("repo") && !source.contains_key("image"),
+            _ => false,
+        };
+        if inferable {
+            source.remove("type");
+        }
+    }
+    if let Some(variables) = object.get_mut("variables").and_then(Value::as_object_mut) {
+        for value in variables.values_mut() {
+            if field_str(value, "type") == Some("literal") {
+                if let Some(literal) = value.get("value").and_then(Value::as_str) {
+                    *value = json!(literal);
+                }
+            }
+        }
+    }
+
+    Value::Object(object)
+}
+
+fn strip_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, strip_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(strip_nulls).collect()),
+        other => other,
+    }
+}
@@ -0,0 +1,205 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::change_set::{ChangeSet, address_from_path};
+use super::graph::{RailwayGraph, resource_addr};
+use super::json::field_str;
+use super::partial::{IacPartials, effective_partial, owner_of};
+
+pub const RAILWAY_DRIFT_REPORT_VERSION: u32 = 1;
+
+/// Which side of a plan moved.
+///
+/// A resource the file does not own yet is waiting on its first apply. For
+/// one it owns, the file is compared with the graph recorded at the last
+/// apply: if the file changed there since, the change is pending; if it
+/// didn't, the live environment must have moved.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DriftOrigin {
+    /// The file declares something that hasn't been applied yet.
+    File,
+    /// The live environment was modified out-of-band.
+    Live,
+    /// Ownership or the last-applied graph isn't recorded, so the side is
+    /// unknown.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DriftEntry {
+    pub origin: DriftOrigin,
+    pub kind: String,
+    pub address: String,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    pub version: u32,
+    pub drifted: bool,
+    pub file_ahead: usize,
+    pub out_of_band: usize,
+    pub unknown: usize,
+    pub entries: Vec<DriftEntry>,
+}
+
+pub fn classify_drift(
+    change_set: &ChangeSet,
+    owners: Option<&IacPartials>,
+    partial: Option<&str>,
+    desired: &RailwayGraph,
+    applied: Option<&RailwayGraph>,
+) -> DriftReport {
+    let p = effective_partial(partial);
+    let entries: Vec<DriftEntry> = change_set
+        .changes
+        .iter()
+        .map(|change| {
+            let kind = field_str(change, "kind").unwrap_or("").to_string();
+            let address = change_address(change);
+            let owner = owner_of(owners, &address);
+            let owned = owner == Some(p);
+            let origin = match kind.as_str() {
+                _ if owners.is_none() => DriftOrigin::Unknown,
+                // Exists live but not in the file: an owned resource was
+                // dropped from the file, an unowned one was created by hand.
+                "resource.delete" if owned => DriftOrigin::File,
+                "resource.delete" => DriftOrigin::Live,
+                _ if !owned => DriftOrigin::File,
+                // Everything else touches a resource the file declares and
+                // has applied before.
+                _ => match applied {
+                    Some(applied) if file_changed_since(change, desired, applied) => {
+                        DriftOrigin::File
+                    }
+                    Some(_) => DriftOrigin::Live,
+                    None => DriftOrigin::Unknown,
+                },
+            };
+            DriftEntry {
+                origin,
+                kind,
+                summary: field_str(change, "summary").unwrap_or("change").to_string(),
+                owner: owner.map(str::to_string),
+                address,
+            }
+        })
+        .collect();
+
+    let count = |origin| entries.iter().filter(|e| e.origin == origin).count();
+    let (file_ahead, out_of_band, unknown) = (
+        count(DriftOrigin::File),
+        count(DriftOrigin::Live),
+        count(DriftOrigin::Unknown),
+    );
+    DriftReport {
+        version: RAILWAY_DRIFT_REPORT_VERSION,
+        drifted: out_of_band + unknown > 0,
+        file_ahead,
+        out_of_band,
+        unknown,
+        entries,
+    }
+}
+
+/// Whether the file's value at the change's path differs from what was last
+/// applied. Paths that don't resolve in either graph compare the whole
+/// resource instead.
+fn file_changed_since(change: &Value, desired: &RailwayGraph, applied: &RailwayGraph) -> bool {
+    let address = change_address(change);
+    let path = field_str(change, "path").unwrap_or("");
+    let field = path
+        .strip_prefix("resources.")
+        .and_then(|rest| rest.strip_prefix(address.as_str()))
+        .map(|rest| rest.trim_start_matches('.'))
+        .unwrap_or("");
+    let (file, last) = (
+        resource_at(desired, &address),
+        resource_at(applied, &address),
+    );
+    let (file_field, last_field) = (
+        file.and_then(|r| value_at(r, field)),
+        last.and_then(|r| value_at(r, field)),
+    );
+    if file_field.is_none() && last_field.is_none() {
+        file != last
+    } else {
+        file_field != last_field
+    }
+}
+
+fn resource_at<'a>(graph: &'a RailwayGraph, address: &str) -> Option<&'a Value> {
+    graph
+        .resources
+        .iter()
+        .find(|resource| resource_addr(resource) == address)
+}
+
+fn value_at<'a>(resource: &'a Value, field: &str) -> Option<&'a Value> {
+    if field.is_empty() {
+        return Some(resource);
+    }
+    field
+        .split('.')
+        .try_fold(resource, |value, segment| value.get(segment))
+}
+
+/// Where the graph last applied from `file` to an environment is recorded:
+/// beside the file, so a checkout that commits it gives CI the same
+/// baseline as the machine that applied.
+pub fn applied_graph_path(file: &Path, environment_id: &str) -> PathBuf {
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "railway".to_string());
+    file.with_file_name(format!(".{stem}.applied.{environment_id}.json"))
+}
+
+pub fn load_applied_graph(path: &Path) -> Option<RailwayGraph> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub fn save_applied_graph(path: &Path, graph: &RailwayGraph) -> anyhow::Result<()> {
+    crate::util::write_atomic(path, &serde_json::to_string_pretty(graph)?)
+}
+
+/// Record `graph` as the drift baseline if the apply left the environment
+/// matching it: the apply succeeded, or there was nothing to apply. Returns
+/// whether it was recorded.
+pub fn record_applied_graph(
+    path: &Path,
+    graph: &RailwayGraph,
+    change_set: &ChangeSet,
+    apply_result: Option<&Value>,
+) -> anyhow::Result<bool> {
+    let failed = |value: &Value| field_str(value, "status") == Some("failed");
+    let matches_live = match apply_result {
+        Some(result) => {
+            !failed(result)
+                && !result
+                    .get("changes")
+                    .and_then(Value::as_array)
+                    .is_some_and(|changes| changes.iter().any(failed))
+        }
+        None => change_set.changes.is_empty(),
+    };
+    if matches_live {
+        save_applied_graph(path, graph)?;
+    }
+    Ok(matches_live)
+}
+
+/// Backboard's preview doesn't always echo `address`, so fall back to the path.
+fn change_address(change: &Value) -> String {
+    match field_str(change, "address") {
+        Some(address) => address.to_string(),
+        None => address_from_path(field_str(change, "path").unwrap_or("")),
+    }
+}
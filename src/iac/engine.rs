@@ -12,9 +12,13 @@ use crate::{
 
 use super::change_set::{DiffOptions, diff_graphs, render_change_set};
 use super::compiler::{EnvironmentConfigToGraphOptions, environment_config_to_graph};
+use super::drift::{
+    DriftReport, applied_graph_path, classify_drift, load_applied_graph, record_applied_graph,
+};
 use super::eval::evaluate_file;
-use super::graph::validate_graph;
+use super::graph::graph_issues;
 use super::partial::needs_partial_claim_apply;
+use super::plan::{PlanContext, SavedPlan, build_plan, plan_mismatches};
 
 #[derive(Debug, Deserialize)]
 struct EnvQuery {
@@ -129,6 +133,21 @@ pub struct NativeRun {
     pub file: Option<std::path::PathBuf>,
     pub decrypt_variables: bool,
     pub show_values: bool,
+    /// Environment name or ID to plan against instead of the linked one. Its
+    /// overlay block, if the file declares one, is merged into the definition.
+    pub environment: Option<String>,
+    /// A saved plan the recomputed changes must still match before applying.
+    pub expected_plan: Option<SavedPlan>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectEnvironmentsQuery {
+    project: Option<ProjectEnvironments>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectEnvironments {
+    environments: Connection<Named>,
 }
 
 pub async fn run(
@@ -142,19 +161,30 @@ pub async fn run(
         .file
         .clone()
         .or_else(|| find_authoring_file(&cwd))
-        .context("Could not find .railway/railway.ts, railway.py, or railway.go")?;
-    let evaluated = evaluate_file(&file)?;
-    let diagnostics: Vec<Value> = validate_graph(&evaluated.graph)
-        .into_iter()
-        .map(|message| json!({ "severity": "error", "path": "graph", "message": message }))
-        .collect();
-
+        .context("Could not find .railway/railway.ts, railway.py, railway.go, railway.toml, railway.yaml, or railway.json")?;
     let client = GQLClient::new_authorized(configs)?;
     let endpoint = configs.get_backboard();
-    let environment_id = linked_project.environment_id()?;
+    let environment_id = match &args.environment {
+        Some(environment) => {
+            resolve_environment_id(&client, &endpoint, &linked_project.project, environment).await?
+        }
+        None => linked_project.environment_id()?.to_string(),
+    };
     let current =
-        fetch_current_environment(&client, &endpoint, environment_id, args.decrypt_variables)
+        fetch_current_environment(&client, &endpoint, &environment_id, args.decrypt_variables)
             .await?;
+    let evaluated = evaluate_file(&file, current.name.as_deref())?;
+    let diagnostics: Vec<Value> = graph_issues(&evaluated.graph)
+        .into_iter()
+        .map(|issue| {
+            let path = issue
+                .resource
+                .and_then(|index| evaluated.resource_location(index))
+                .unwrap_or_else(|| "graph".to_string());
+            json!({ "severity": "error", "path": path, "message": issue.message })
+        })
+        .collect();
+
     let mut options = EnvironmentConfigToGraphOptions {
         project_name: linked_project
             .name
@@ -222,6 +252,40 @@ pub async fn run(
         evaluated.partial.as_deref(),
     );
 
+    let plan = build_plan(
+        &change_set,
+        PlanContext {
+            file: evaluated.file.to_string_lossy().to_string(),
+            project_id: current.project_id.clone(),
+            environment_id: current.id.clone(),
+            environment_name: current.name.clone(),
+            config_etag: current.config_etag.clone(),
+            current: &current_graph,
+            desired: &evaluated.graph,
+            reveal_values: args.show_values,
+        },
+    );
+    if let Some(expected) = &args.expected_plan {
+        let mismatches = plan_mismatches(expected, &plan);
+        if !mismatches.is_empty() {
+            bail!(
+                "Refusing to apply the saved plan:\n  {}\nRun `railway config plan --out <file>` again and review the new plan.",
+                mismatches.join("\n  ")
+            );
+        }
+    }
+
+    let applied_path = applied_graph_path(&evaluated.file, &current.id);
+    let drift = (command == "drift").then(|| {
+        classify_drift(
+            &change_set,
+            owners.as_ref(),
+            evaluated.partial.as_deref(),
+            &evaluated.graph,
+            load_applied_graph(&applied_path).as_ref(),
+        )
+    });
+
     let mut apply_result = None;
     if command == "apply" && ok && (!change_set.changes.is_empty() || claim) {
         apply_result = Some(
@@ -235,6 +299,21 @@ pub async fn run(
             .await?,
         );
     }
+    // Record what this file looks like when applied, as the baseline drift
+    // checks compare against. Best effort: the apply itself is done.
+    if command == "apply" && ok {
+        if let Err(e) = record_applied_graph(
+            &applied_path,
+            &evaluated.graph,
+            &change_set,
+            apply_result.as_ref(),
+        ) {
+            eprintln!(
+                "Warning: couldn't record the applied configuration at {}: {e}",
+                applied_path.display()
+            );
+        }
+    }
 
     let serialized = serde_json::to_value(RunnerWire {
         ok,
@@ -255,6 +334,8 @@ pub async fn run(
         apply_result,
         claim,
         preview,
+        drift,
+        plan: ok.then_some(plan),
     })?;
     Ok(serialized)
 }
@@ -275,6 +356,43 @@ struct RunnerWire {
     claim: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     preview: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drift: Option<DriftReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plan: Option<SavedPlan>,
+}
+
+async fn resolve_environment_id(
+    client: &reqwest::Client,
+    endpoint: &str,
+    project_id: &str,
+    environment: &str,
+) -> Result<String> {
+    let data = post_graphql_raw::<ProjectEnvironmentsQuery, _>(
+        client,
+        endpoint,
+        "query IacProjectEnvironments($projectId: String!) { project(id: $projectId) { environments { edges { node { id name } } } } }",
+        json!({ "projectId": project_id }),
+    )
+    .await
+    .context("Failed to load project environments")?;
+    let environments = data
+        .project
+        .map(|project| project.environments.edges)
+        .unwrap_or_default();
+    environments
+        .iter()
+        .find(|edge| edge.node.id == environment)
+        .or_else(|| {
+            environments.iter().find(|edge| {
+                edge.node
+                    .name
+                    .as_deref()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(environment))
+            })
+        })
+        .map(|edge| edge.node.id.clone())
+        .with_context(|| format!("Environment \"{environment}\" not found in this project"))
 }
 
 async fn fetch_current_environment(
@@ -400,7 +518,15 @@ async fn fill_name_maps(
 }
 
 fn find_authoring_file(start: &std::path::Path) -> Option<std::path::PathBuf> {
-    const NAMES: &[&str] = &["railway.ts", "railway.py", "railway.go"];
+    const NAMES: &[&str] = &[
+        "railway.ts",
+        "railway.py",
+        "railway.go",
+        "railway.toml",
+        "railway.yaml",
+        "railway.yml",
+        "railway.json",
+    ];
     for directory in start.ancestors() {
         let railway_dir =
             if directory.file_name().and_then(|name| name.to_str()) == Some(".railway") {
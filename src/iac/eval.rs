@@ -7,7 +7,8 @@ use std::{
 use anyhow::{Context, Result, bail};
 use serde_json::{Value, json};
 
-use super::compiler::project_definition_to_graph;
+use super::compiler::{apply_environment_overlay, project_definition_to_graph};
+use super::declarative::{self, DeclarativeFormat};
 use super::graph::RailwayGraph;
 use super::partial::parse_partial_name;
 
@@ -15,16 +16,35 @@ pub struct EvaluatedFile {
     pub file: PathBuf,
     pub graph: RailwayGraph,
     pub partial: Option<String>,
+    /// Line each declared resource starts on, for declarative files only.
+    pub resource_lines: Vec<Option<usize>>,
 }
 
-pub fn evaluate_file(file: &Path) -> Result<EvaluatedFile> {
+impl EvaluatedFile {
+    /// `railway.toml:12` for a resource declared in a declarative file.
+    pub fn resource_location(&self, index: usize) -> Option<String> {
+        let line = self.resource_lines.get(index).copied().flatten()?;
+        Some(format!("{}:{line}", declarative::display_name(&self.file)))
+    }
+}
+
+/// Evaluate an authoring file, merging in the overlay for `environment` when
+/// the definition declares one.
+pub fn evaluate_file(file: &Path, environment: Option<&str>) -> Result<EvaluatedFile> {
     let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
     let ext = file
         .extension()
         .and_then(|ext| ext.to_str())
         .unwrap_or("")
         .to_ascii_lowercase();
+    let mut resource_lines = Vec::new();
     let payload = match ext.as_str() {
+        "toml" | "yaml" | "yml" | "json" => {
+            let format = DeclarativeFormat::from_extension(&ext).expect("declarative extension");
+            let parsed = declarative::parse_file(&file, format)?;
+            resource_lines = parsed.resource_lines;
+            parsed.payload
+        }
         "py" => evaluate_python(&file)?,
         "go" => evaluate_go(&file)?,
         "ts" | "mts" | "cts" | "js" | "mjs" | "cjs" => evaluate_javascript(&file)?,
@@ -37,11 +57,14 @@ pub fn evaluate_file(file: &Path) -> Result<EvaluatedFile> {
         .cloned()
         .or_else(|| payload.get("graph").cloned())
         .unwrap_or(payload.clone());
+    let project =
+        apply_environment_overlay(&project, environment).map_err(|err| anyhow::anyhow!(err))?;
     let graph = project_definition_to_graph(&normalize_project(project));
     Ok(EvaluatedFile {
         file,
         graph,
         partial,
+        resource_lines,
     })
 }
 
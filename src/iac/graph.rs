@@ -56,24 +56,54 @@ pub fn resource_addr(resource: &Value) -> String {
 }
 
 pub fn validate_graph(graph: &RailwayGraph) -> Vec<String> {
-    let mut errors = Vec::new();
+    graph_issues(graph)
+        .into_iter()
+        .map(|issue| issue.message)
+        .collect()
+}
+
+/// A validation error, tied to the index of the resource it concerns when
+/// there is one so callers can point at where it was declared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphIssue {
+    pub resource: Option<usize>,
+    pub message: String,
+}
+
+pub fn graph_issues(graph: &RailwayGraph) -> Vec<GraphIssue> {
+    let mut issues = Vec::new();
     if graph.version != RAILWAY_GRAPH_VERSION {
-        errors.push(format!("Unsupported graph version: {}", graph.version));
+        issues.push(GraphIssue {
+            resource: None,
+            message: format!("Unsupported graph version: {}", graph.version),
+        });
     }
-    let mut addresses = std::collections::HashSet::new();
-    for resource in &graph.resources {
+    let mut addresses = std::collections::HashMap::new();
+    for (index, resource) in graph.resources.iter().enumerate() {
         let address = resource_addr(resource);
-        if !addresses.insert(address.clone()) {
-            errors.push(format!("Duplicate resource address: {address}"));
+        if let std::collections::hash_map::Entry::Vacant(entry) = addresses.entry(address.clone()) {
+            entry.insert(index);
+        } else {
+            issues.push(GraphIssue {
+                resource: Some(index),
+                message: format!("Duplicate resource address: {address}"),
+            });
         }
     }
     for edge in &graph.edges {
-        if !addresses.contains(&edge.from) {
-            errors.push(format!("Edge references missing source: {}", edge.from));
+        let from = addresses.get(&edge.from).copied();
+        if from.is_none() {
+            issues.push(GraphIssue {
+                resource: None,
+                message: format!("Edge references missing source: {}", edge.from),
+            });
         }
-        if !addresses.contains(&edge.to) {
-            errors.push(format!("Edge references missing target: {}", edge.to));
+        if !addresses.contains_key(&edge.to) {
+            issues.push(GraphIssue {
+                resource: from,
+                message: format!("Edge references missing target: {}", edge.to),
+            });
         }
     }
-    errors
+    issues
 }
@@ -91,3 +91,23 @@ pub fn merge_objects(base: Value, extra: Value) -> Value {
         (_, extra) => extra,
     }
 }
+
+/// RFC 7396 JSON merge patch: objects merge recursively, `null` removes a key,
+/// and anything else (arrays included) replaces the target.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let target = target.as_object_mut().expect("target is an object");
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
@@ -6,11 +6,14 @@
 
 mod change_set;
 mod compiler;
+mod declarative;
+mod drift;
 mod engine;
 mod eval;
 mod graph;
 mod json;
 mod partial;
+mod plan;
 
 #[allow(dead_code)]
 pub use change_set::{ChangeSet, RAILWAY_CHANGE_SET_VERSION, diff_graphs, render_change_set};
@@ -19,13 +22,16 @@ pub use compiler::{
     CompileOptions, EnvironmentConfigToGraphOptions, environment_config_to_graph,
     graph_to_environment_config, project_definition_to_graph,
 };
+pub use declarative::{DeclarativeFormat, PROJECT_SCHEMA, render_definition};
+pub use drift::{DriftOrigin, DriftReport};
 pub use engine::{NativeRun, run as run_native};
 #[allow(dead_code)]
 pub use eval::{EvaluatedFile, evaluate_file};
 #[allow(dead_code)]
-pub use graph::{RAILWAY_GRAPH_VERSION, RailwayGraph, resource_address, validate_graph};
+pub use graph::{Edge, RAILWAY_GRAPH_VERSION, RailwayGraph, resource_address, validate_graph};
 #[allow(dead_code)]
 pub use partial::{needs_partial_claim_apply, parse_partial_name};
+pub use plan::SavedPlan;
 
 pub fn use_legacy_ts_runner(explicit_runner: Option<&str>) -> bool {
     if explicit_runner.is_some() {
@@ -0,0 +1,245 @@
+//! Saved plans: the versioned, machine-readable form of a ChangeSet.
+//!
+//! A plan records what it was computed against (environment, config etag,
+//! and fingerprints of the live and desired graphs) so `railway config apply
+//! --plan` can refuse to apply once either side has moved. Plans never carry
+//! variable values unless they were made with `--show-values`; applying
+//! recomputes the ChangeSet and checks it still matches the plan.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::change_set::{
+    ChangeSet, RAILWAY_CHANGE_SET_VERSION, REDACTED_VARIABLE_VALUE, address_from_path,
+};
+use super::graph::RailwayGraph;
+use super::json::{field_str, stable_stringify};
+
+pub const RAILWAY_PLAN_KIND: &str = "railway.plan";
+pub const RAILWAY_PLAN_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedPlan {
+    pub kind: String,
+    pub version: u32,
+    pub change_set_version: u32,
+    pub created_at: String,
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub environment_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial: Option<String>,
+    pub base: PlanBase,
+    pub changes: Vec<PlanChange>,
+}
+
+/// What the plan was computed against.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanBase {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_etag: Option<String>,
+    pub live_fingerprint: String,
+    pub desired_fingerprint: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PlanAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanChange {
+    /// Stable across runs for the same change to the same path.
+    pub id: String,
+    pub address: String,
+    pub action: PlanAction,
+    pub kind: String,
+    /// Changed field paths relative to the resource, e.g. `deploy.numReplicas`.
+    pub fields: Vec<String>,
+    pub summary: String,
+    pub severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deploy_effect: Option<String>,
+    /// True when `before`/`after` hold the redaction marker instead of values.
+    pub redacted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+pub struct PlanContext<'a> {
+    pub file: String,
+    pub project_id: Option<String>,
+    pub environment_id: String,
+    pub environment_name: Option<String>,
+    pub config_etag: Option<String>,
+    pub current: &'a RailwayGraph,
+    pub desired: &'a RailwayGraph,
+    pub reveal_values: bool,
+}
+
+pub fn build_plan(change_set: &ChangeSet, context: PlanContext<'_>) -> SavedPlan {
+    SavedPlan {
+        kind: RAILWAY_PLAN_KIND.to_string(),
+        version: RAILWAY_PLAN_VERSION,
+        change_set_version: RAILWAY_CHANGE_SET_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        file: context.file,
+        project_id: context.project_id,
+        environment_id: context.environment_id,
+        environment_name: context.environment_name,
+        partial: change_set.partial.clone(),
+        base: PlanBase {
+            config_etag: context.config_etag,
+            live_fingerprint: fingerprint(context.current),
+            desired_fingerprint: fingerprint(context.desired),
+        },
+        changes: change_set
+            .changes
+            .iter()
+            .map(|change| plan_change(change, context.reveal_values))
+            .collect(),
+    }
+}
+
+/// Reasons `actual` (recomputed now) no longer matches the saved `expected`
+/// plan. Empty when it's safe to apply.
+pub fn plan_mismatches(expected: &SavedPlan, actual: &SavedPlan) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if expected.kind != RAILWAY_PLAN_KIND || expected.version != RAILWAY_PLAN_VERSION {
+        reasons.push(format!(
+            "Unsupported plan format {} v{} (this CLI writes {RAILWAY_PLAN_KIND} v{RAILWAY_PLAN_VERSION}).",
+            expected.kind, expected.version
+        ));
+        return reasons;
+    }
+    if expected.environment_id != actual.environment_id {
+        reasons.push(format!(
+            "The plan was made for environment {}, not {}.",
+            expected
+                .environment_name
+                .as_deref()
+                .unwrap_or(&expected.environment_id),
+            actual
+                .environment_name
+                .as_deref()
+                .unwrap_or(&actual.environment_id)
+        ));
+        return reasons;
+    }
+    let etag_moved = matches!(
+        (&expected.base.config_etag, &actual.base.config_etag),
+        (Some(before), Some(now)) if before != now
+    );
+    if etag_moved || expected.base.live_fingerprint != actual.base.live_fingerprint {
+        reasons.push("The live environment changed since the plan was computed.".to_string());
+    }
+    if expected.base.desired_fingerprint != actual.base.desired_fingerprint {
+        reasons.push("The configuration file changed since the plan was computed.".to_string());
+    }
+    let ids = |plan: &SavedPlan| {
+        let mut ids: Vec<_> = plan.changes.iter().map(|c| c.id.clone()).collect();
+        ids.sort();
+        ids
+    };
+    if reasons.is_empty() && ids(expected) != ids(actual) {
+        reasons.push("The changes Railway would make no longer match the plan.".to_string());
+    }
+    reasons
+}
+
+fn fingerprint(graph: &RailwayGraph) -> String {
+    let value = serde_json::to_value(graph).unwrap_or(Value::Null);
+    format!("{:x}", Sha256::digest(stable_stringify(&value)))
+}
+
+fn change_id(kind: &str, path: &str) -> String {
+    let digest = format!("{:x}", Sha256::digest(format!("{kind}\0{path}")));
+    format!("chg_{}", &digest[..12])
+}
+
+fn plan_change(change: &Value, reveal_values: bool) -> PlanChange {
+    let kind = field_str(change, "kind").unwrap_or("change").to_string();
+    let path = field_str(change, "path").unwrap_or("").to_string();
+    let address = field_str(change, "address")
+        .map(str::to_string)
+        .unwrap_or_else(|| address_from_path(&path));
+    let action = match kind.as_str() {
+        "resource.create" | "domain.create" => PlanAction::Create,
+        "resource.delete" | "variable.delete" => PlanAction::Delete,
+        _ => PlanAction::Update,
+    };
+    let relative = path
+        .strip_prefix(&format!("resources.{address}"))
+        .map(|rest| rest.trim_start_matches('.').to_string())
+        .unwrap_or_default();
+
+    let is_variable = kind.starts_with("variable.");
+    let before = change.get("before").cloned().or_else(|| {
+        is_variable
+            .then(|| change.get("previous").cloned())
+            .flatten()
+    });
+    let after = change.get("after").cloned();
+    let fields = match (kind.as_str(), &before, &after) {
+        ("resource.update", Some(before), Some(after)) => changed_fields(
+            before,
+            after,
+            field_str(change, "field").unwrap_or(&relative),
+        ),
+        _ if relative.is_empty() => Vec::new(),
+        _ => vec![relative],
+    };
+
+    // Whole resources can embed variable values, so creates and deletes only
+    // list their address; variable values are redacted unless revealed.
+    let (redacted, before, after) = match action {
+        PlanAction::Create | PlanAction::Delete if !is_variable => (false, None, None),
+        _ if is_variable && !reveal_values => {
+            let marker = |value: &Option<Value>| {
+                value.as_ref().map(|_| Value::from(REDACTED_VARIABLE_VALUE))
+            };
+            (true, marker(&before), marker(&after))
+        }
+        _ => (false, before, after),
+    };
+
+    PlanChange {
+        id: change_id(&kind, &path),
+        address,
+        action,
+        kind,
+        fields,
+        summary: field_str(change, "summary").unwrap_or("change").to_string(),
+        severity: field_str(change, "severity").unwrap_or("safe").to_string(),
+        deploy_effect: field_str(change, "deployEffect").map(str::to_string),
+        redacted,
+        before,
+        after,
+    }
+}
+
+fn changed_fields(before: &Value, after: &Value, field: &str) -> Vec<String> {
+    let before = super::change_set::flatten_for_diff(before, field);
+    let after = super::change_set::flatten_for_diff(after, field);
+    let mut keys: Vec<_> = before.keys().chain(after.keys()).cloned().collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| {
+            stable_stringify(before.get(key).unwrap_or(&Value::Null))
+                != stable_stringify(after.get(key).unwrap_or(&Value::Null))
+        })
+        .collect()
+}
@@ -2,8 +2,8 @@ use serde_json::{Value, json};
 
 use super::change_set::{DiffOptions, RAILWAY_CHANGE_SET_VERSION, diff_graphs, render_change_set};
 use super::compiler::{
-    CompileOptions, EnvironmentConfigToGraphOptions, environment_config_to_graph,
-    graph_to_environment_config, project_definition_to_graph,
+    CompileOptions, EnvironmentConfigToGraphOptions, apply_environment_overlay,
+    environment_config_to_graph, graph_to_environment_config, project_definition_to_graph,
 };
 use super::eval::evaluate_file;
 use super::graph::RAILWAY_GRAPH_VERSION;
@@ -982,7 +982,7 @@ export default () => ({
 "#,
     )
     .unwrap();
-    let evaluated = evaluate_file(&file).expect("node should evaluate railway.ts");
+    let evaluated = evaluate_file(&file, None).expect("node should evaluate railway.ts");
     assert_eq!(evaluated.partial.as_deref(), Some("api"));
     assert!(
         evaluated
@@ -1006,7 +1006,7 @@ def main(ctx=None):
 "#,
     )
     .unwrap();
-    let evaluated = evaluate_file(&file).expect("python3 should evaluate railway.py");
+    let evaluated = evaluate_file(&file, None).expect("python3 should evaluate railway.py");
     assert_eq!(evaluated.partial.as_deref(), Some("api"));
     assert!(
         evaluated
@@ -1028,3 +1028,478 @@ fn legacy_runner_only_when_explicitly_requested() {
     assert!(!super::use_legacy_ts_runner(None));
     assert!(super::use_legacy_ts_runner(Some("railway-iac-ts")));
 }
+
+fn drift_change_set(changes: Vec<Value>) -> super::change_set::ChangeSet {
+    super::change_set::ChangeSet {
+        version: RAILWAY_CHANGE_SET_VERSION,
+        changes,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn drift_classifies_changes_by_ownership() {
+    let change_set = drift_change_set(vec![
+        json!({ "kind": "variable.set", "address": "service.web", "summary": "Update variable web.PORT" }),
+        json!({ "kind": "resource.create", "address": "service.worker", "summary": "Create service worker" }),
+        json!({ "kind": "resource.delete", "address": "service.adhoc", "summary": "Delete service adhoc" }),
+        json!({ "kind": "resource.delete", "address": "service.old", "summary": "Delete service old" }),
+        json!({ "kind": "resource.update", "path": "resources.service.web.deploy", "summary": "Update web deploy" }),
+    ]);
+    let mut owners = IacPartials::new();
+    owners.insert("service.web".into(), "*".into());
+    owners.insert("service.old".into(), "*".into());
+
+    // The file hasn't changed since it was last applied.
+    let graph = graph_from(vec![service(
+        "web",
+        json!({ "variables": { "PORT": "8080" } }),
+    )]);
+    let report =
+        super::drift::classify_drift(&change_set, Some(&owners), None, &graph, Some(&graph));
+    let origins: Vec<_> = report
+        .entries
+        .iter()
+        .map(|entry| (entry.address.as_str(), entry.origin))
+        .collect();
+    assert_eq!(
+        origins,
+        vec![
+            ("service.web", super::DriftOrigin::Live),
+            ("service.worker", super::DriftOrigin::File),
+            ("service.adhoc", super::DriftOrigin::Live),
+            ("service.old", super::DriftOrigin::File),
+            ("service.web", super::DriftOrigin::Live),
+        ]
+    );
+    assert!(report.drifted);
+    assert_eq!((report.file_ahead, report.out_of_band), (2, 3));
+}
+
+#[test]
+fn drift_is_unknown_without_recorded_ownership() {
+    let change_set = drift_change_set(vec![json!({
+        "kind": "variable.set",
+        "address": "service.web",
+        "summary": "Set variable web.PORT",
+    })]);
+    let graph = graph_from(vec![service("web", json!({}))]);
+    let report = super::drift::classify_drift(&change_set, None, None, &graph, Some(&graph));
+    assert_eq!(report.entries[0].origin, super::DriftOrigin::Unknown);
+    assert!(report.drifted);
+
+    let clean = super::drift::classify_drift(&drift_change_set(vec![]), None, None, &graph, None);
+    assert!(!clean.drifted);
+}
+
+#[test]
+fn drift_blames_the_file_when_it_changed_since_the_last_apply() {
+    let change_set = drift_change_set(vec![
+        json!({ "kind": "variable.set", "address": "service.web", "path": "resources.service.web.variables.PORT", "summary": "Update variable web.PORT" }),
+        json!({ "kind": "variable.set", "address": "service.web", "path": "resources.service.web.variables.DEBUG", "summary": "Update variable web.DEBUG" }),
+    ]);
+    let mut owners = IacPartials::new();
+    owners.insert("service.web".into(), "*".into());
+    let applied = graph_from(vec![service(
+        "web",
+        json!({ "variables": { "PORT": "8080", "DEBUG": "0" } }),
+    )]);
+    // PORT was edited in the file and not applied yet; DEBUG was changed live.
+    let edited = graph_from(vec![service(
+        "web",
+        json!({ "variables": { "PORT": "9090", "DEBUG": "0" } }),
+    )]);
+
+    let report =
+        super::drift::classify_drift(&change_set, Some(&owners), None, &edited, Some(&applied));
+    let origins: Vec<_> = report.entries.iter().map(|entry| entry.origin).collect();
+    assert_eq!(
+        origins,
+        vec![super::DriftOrigin::File, super::DriftOrigin::Live]
+    );
+
+    // Without a recorded apply there's nothing to tell the two apart.
+    let report = super::drift::classify_drift(&change_set, Some(&owners), None, &edited, None);
+    assert!(
+        report
+            .entries
+            .iter()
+            .all(|entry| entry.origin == super::DriftOrigin::Unknown)
+    );
+}
+
+#[test]
+fn applied_graph_round_trips_beside_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("railway.toml");
+    let path = super::drift::applied_graph_path(&file, "env-1");
+    assert_eq!(path, dir.path().join(".railway.applied.env-1.json"));
+    assert!(super::drift::load_applied_graph(&path).is_none());
+
+    let graph = graph_from(vec![service("web", json!({}))]);
+    super::drift::save_applied_graph(&path, &graph).unwrap();
+    assert_eq!(super::drift::load_applied_graph(&path), Some(graph));
+}
+
+#[test]
+fn applied_graph_is_only_recorded_when_live_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".railway.applied.env-1.json");
+    let graph = graph_from(vec![service("web", json!({}))]);
+    let change_set = super::change_set::ChangeSet {
+        changes: vec![json!({ "kind": "update", "path": "services.web" })],
+        ..Default::default()
+    };
+
+    let failed = json!({ "id": "apply-1", "status": "failed", "changes": [] });
+    let recorded =
+        super::drift::record_applied_graph(&path, &graph, &change_set, Some(&failed)).unwrap();
+    assert!(!recorded);
+    assert!(!path.exists());
+
+    let partly_failed = json!({
+        "id": "apply-2",
+        "status": "applied",
+        "changes": [{ "kind": "update", "status": "failed" }],
+    });
+    let recorded =
+        super::drift::record_applied_graph(&path, &graph, &change_set, Some(&partly_failed))
+            .unwrap();
+    assert!(!recorded);
+    assert!(!path.exists());
+
+    // Planned changes that never reached the server don't count either.
+    assert!(!super::drift::record_applied_graph(&path, &graph, &change_set, None).unwrap());
+    assert!(!path.exists());
+
+    let applied = json!({
+        "id": "apply-3",
+        "status": "applied",
+        "changes": [{ "kind": "update", "status": "applied" }],
+    });
+    assert!(
+        super::drift::record_applied_graph(&path, &graph, &change_set, Some(&applied)).unwrap()
+    );
+    assert_eq!(super::drift::load_applied_graph(&path), Some(graph.clone()));
+
+    // Nothing to apply: live already matches the file.
+    std::fs::remove_file(&path).unwrap();
+    let empty = super::change_set::ChangeSet::default();
+    assert!(super::drift::record_applied_graph(&path, &graph, &empty, None).unwrap());
+    assert!(path.exists());
+}
+
+#[test]
+fn environment_overlay_merges_into_the_selected_environment() {
+    let definition = json!({
+        "name": "app",
+        "resources": [
+            service("web", json!({
+                "deploy": { "numReplicas": 1, "region": "us-west2" },
+                "variables": { "LOG_LEVEL": "debug", "DEBUG": "1" },
+            })),
+            service("worker", json!({})),
+        ],
+        "environments": {
+            "staging": {},
+            "production": {
+                "resources": {
+                    "service.web": {
+                        "deploy": { "numReplicas": 3 },
+                        "variables": { "LOG_LEVEL": "info", "DEBUG": null },
+                    },
+                },
+            },
+        },
+    });
+
+    let production = project_definition_to_graph(
+        &apply_environment_overlay(&definition, Some("production")).unwrap(),
+    );
+    let web = production
+        .resources
+        .iter()
+        .find(|r| r["address"] == "service.web")
+        .unwrap();
+    assert_eq!(
+        web["deploy"],
+        json!({ "numReplicas": 3, "region": "us-west2" })
+    );
+    assert_eq!(web["variables"], json!({ "LOG_LEVEL": "info" }));
+    assert_eq!(production.resources.len(), 2);
+    let names: Vec<_> = production
+        .environments
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["production", "staging"]);
+
+    let staging = apply_environment_overlay(&definition, Some("staging")).unwrap();
+    assert_eq!(staging, definition);
+    assert_eq!(
+        apply_environment_overlay(&definition, None).unwrap(),
+        definition
+    );
+}
+
+#[test]
+fn environment_overlay_rejects_unknown_resources() {
+    let definition = json!({
+        "name": "app",
+        "resources": [service("web", json!({}))],
+        "environments": {
+            "production": { "resources": { "api": { "deploy": { "numReplicas": 2 } } } },
+        },
+    });
+    let error = apply_environment_overlay(&definition, Some("production")).unwrap_err();
+    assert!(error.contains("environments.production.resources.api"));
+}
+
+const DECLARATIVE_TOML: &str = r#"
+name = "app"
+
+[[resources]]
+name = "web"
+source = { repo = "railwayapp/demo", branch = "main" }
+start = "npm start"
+variables = { PORT = 8080, DATABASE_URL = { type = "reference", resource = "database.db", output = "DATABASE_URL" }, SECRET = { type = "preserve" } }
+
+[[resources]]
+type = "database"
+name = "db"
+engine = "postgres"
+"#;
+
+#[test]
+fn declarative_formats_compile_to_the_same_graph() {
+    use super::declarative::{DeclarativeFormat, parse_source};
+
+    let toml = parse_source(DECLARATIVE_TOML, DeclarativeFormat::Toml).unwrap();
+    assert_eq!(toml.resource_lines, vec![Some(4), Some(10)]);
+    let web = &toml.payload["resources"][0];
+    assert_eq!(web["address"], "service.web");
+    assert_eq!(web["kind"], "github");
+    assert_eq!(web["source"]["type"], "github");
+    assert_eq!(
+        web["variables"]["PORT"],
+        json!({ "type": "literal", "value": "8080" })
+    );
+
+    let yaml = r#"
+name: app
+resources:
+  - name: web
+    source: { repo: railwayapp/demo, branch: main }
+    start: npm start
+    variables:
+      PORT: 8080
+      DATABASE_URL: { type: reference, resource: database.db, output: DATABASE_URL }
+      SECRET: { type: preserve }
+  - type: database
+    name: db
+    engine: postgres
+"#;
+    let yaml = parse_source(yaml, DeclarativeFormat::Yaml).unwrap();
+    assert_eq!(yaml.resource_lines, vec![Some(4), Some(12)]);
+    assert_eq!(yaml.payload, toml.payload);
+
+    let rendered = super::render_definition(
+        "app",
+        &[],
+        yaml.payload["resources"].as_array().unwrap().clone(),
+        &[],
+        DeclarativeFormat::Json,
+    )
+    .unwrap();
+    assert!(rendered.contains("\"PORT\": \"8080\""));
+    let json = parse_source(&rendered, DeclarativeFormat::Json).unwrap();
+    assert_eq!(json.payload, toml.payload);
+    assert_eq!(json.resource_lines.len(), 2);
+    assert!(json.resource_lines.iter().all(Option::is_some));
+
+    let rendered = super::render_definition(
+        "app",
+        &[],
+        toml.payload["resources"].as_array().unwrap().clone(),
+        &[],
+        DeclarativeFormat::Toml,
+    )
+    .unwrap();
+    let reparsed = parse_source(&rendered, DeclarativeFormat::Toml).unwrap();
+    assert_eq!(reparsed.payload, toml.payload);
+}
+
+#[test]
+fn rendered_definitions_keep_environments_and_refuse_to_drop_edges() {
+    use super::declarative::{DeclarativeFormat, parse_source};
+
+    let graph = graph_from(vec![
+        service(
+            "web",
+            json!({
+                "variables": {
+                    "DATABASE_URL": { "type": "reference", "resource": "database.db", "output": "DATABASE_URL" },
+                },
+            }),
+        ),
+        json!({ "address": "database.db", "type": "database", "name": "db", "engine": "postgres" }),
+    ]);
+    assert_eq!(graph.edges.len(), 1);
+
+    let environments = vec!["production".to_string(), "staging".to_string()];
+    for format in [
+        DeclarativeFormat::Toml,
+        DeclarativeFormat::Yaml,
+        DeclarativeFormat::Json,
+    ] {
+        let rendered = super::render_definition(
+            "app",
+            &environments,
+            graph.resources.clone(),
+            &graph.edges,
+            format,
+        )
+        .unwrap();
+        let reparsed = parse_source(&rendered, format).unwrap();
+        assert_eq!(
+            reparsed.payload["environments"],
+            json!(["production", "staging"])
+        );
+        assert_eq!(
+            project_definition_to_graph(&reparsed.payload).edges,
+            graph.edges
+        );
+    }
+
+    let orphan = super::Edge {
+        from: "service.web".to_string(),
+        to: "volume.data".to_string(),
+        kind: "mount".to_string(),
+        key: Some("/data".to_string()),
+    };
+    let error = super::render_definition(
+        "app",
+        &[],
+        graph.resources.clone(),
+        &[orphan],
+        DeclarativeFormat::Toml,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        error.contains("service.web -> volume.data (mount)"),
+        "{error}"
+    );
+}
+
+#[test]
+fn declarative_errors_name_the_line() {
+    use super::declarative::{DeclarativeFormat, parse_source};
+
+    let source = "name = \"app\"\n\n[[resources]]\nname = \"web\"\n\n[[resources]]\ntype = \"lambda\"\nname = \"fn\"\n";
+    let error = parse_source(source, DeclarativeFormat::Toml)
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(
+        error.starts_with("line 6: resources[1] has unknown type \"lambda\""),
+        "{error}"
+    );
+
+    let dir = tempfile_dir("railway-iac-toml-");
+    let file = dir.join("railway.toml");
+    std::fs::write(
+        &file,
+        "[[resources]]\nname = \"web\"\n\n[[resources]]\nname = \"web\"\n",
+    )
+    .unwrap();
+    let evaluated = evaluate_file(&file, None).unwrap();
+    let issues = super::graph::graph_issues(&evaluated.graph);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(
+        issues[0]
+            .resource
+            .and_then(|index| evaluated.resource_location(index))
+            .as_deref(),
+        Some("railway.toml:4")
+    );
+}
+
+fn plan_for(
+    current: &super::graph::RailwayGraph,
+    desired: &super::graph::RailwayGraph,
+    config_etag: &str,
+) -> super::SavedPlan {
+    super::plan::build_plan(
+        &diff(current, desired),
+        super::plan::PlanContext {
+            file: "railway.toml".into(),
+            project_id: Some("project".into()),
+            environment_id: "env".into(),
+            environment_name: Some("production".into()),
+            config_etag: Some(config_etag.into()),
+            current,
+            desired,
+            reveal_values: false,
+        },
+    )
+}
+
+#[test]
+fn saved_plans_have_stable_ids_and_redact_values() {
+    let secret = "sk-super-secret-value-123";
+    let current = env_config(json!({ "services": { "web": { "source": { "repo": "r" } } } }));
+    let desired = graph_from(vec![service(
+        "web",
+        json!({
+            "source": github("r"),
+            "variables": { "API_KEY": { "type": "literal", "value": secret } }
+        }),
+    )]);
+
+    let first = plan_for(&current, &desired, "etag-1");
+    let second = plan_for(&current, &desired, "etag-1");
+    let ids = |plan: &super::SavedPlan| {
+        plan.changes
+            .iter()
+            .map(|c| c.id.clone())
+            .collect::<Vec<_>>()
+    };
+    assert!(!first.changes.is_empty());
+    assert_eq!(ids(&first), ids(&second));
+    assert!(ids(&first).iter().all(|id| id.starts_with("chg_")));
+
+    let serialized = serde_json::to_string(&first).unwrap();
+    assert!(!serialized.contains(secret));
+    let variable = first
+        .changes
+        .iter()
+        .find(|c| c.kind.starts_with("variable."))
+        .unwrap();
+    assert!(variable.redacted);
+    assert_eq!(variable.address, "service.web");
+    assert!(super::plan::plan_mismatches(&first, &second).is_empty());
+}
+
+#[test]
+fn saved_plans_refuse_once_live_state_moves() {
+    let current = env_config(json!({ "services": { "web": { "source": { "repo": "r" } } } }));
+    let desired = graph_from(vec![service(
+        "web",
+        json!({ "source": github("r"), "deploy": { "numReplicas": 2 } }),
+    )]);
+    let saved = plan_for(&current, &desired, "etag-1");
+
+    let moved = env_config(json!({
+        "services": { "web": { "source": { "repo": "r" }, "deploy": { "startCommand": "npm start" } } }
+    }));
+    let reasons = super::plan::plan_mismatches(&saved, &plan_for(&moved, &desired, "etag-2"));
+    assert_eq!(
+        reasons,
+        vec!["The live environment changed since the plan was computed.".to_string()]
+    );
+
+    let mut elsewhere = plan_for(&current, &desired, "etag-1");
+    elsewhere.environment_id = "other".into();
+    assert_eq!(super::plan::plan_mismatches(&saved, &elsewhere).len(), 1);
+}
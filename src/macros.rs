@@ -20,7 +20,14 @@ macro_rules! commands {
                         "\n\nTip: Using an AI coding agent? Run `railway setup agent -y` to install Railway skills and the Railway MCP server."
                     ))
                     .long_about(None)
-                    .version(clap::crate_version!());
+                    .version(clap::crate_version!())
+                    .arg(
+                        clap::Arg::new("profile")
+                            .long("profile")
+                            .value_name("NAME")
+                            .global(true)
+                            .help("Run under a named login profile (overrides RAILWAY_PROFILE)"),
+                    );
                 $(
                     {
                         let command_name = $crate::commands!(@command_name $module $(as $name)?);
@@ -43,6 +43,7 @@ commands!(
     add,
     agent,
     api,
+    audit,
     autoupdate,
     bucket,
     cdn,
@@ -58,8 +59,10 @@ commands!(
     domain,
     docs,
     down,
+    edge,
     environment(env),
     flag(flags),
+    import,
     init,
     link,
     list,
@@ -68,9 +71,11 @@ commands!(
     logs,
     mcp,
     metrics,
+    notifications as "notify"(notifications),
     open,
     outbound_networking as "outbound-network",
     postgres,
+    profile,
     project,
     private_network as "private-network",
     run(local),
@@ -85,6 +90,7 @@ commands!(
     telemetry_cmd(telemetry),
     templates,
     tcp_proxy as "tcp-proxy",
+    token(tokens),
     unlink,
     up,
     upgrade,
@@ -186,8 +192,23 @@ fn spawn_update_task(
     })
 }
 
+/// First positional argument, i.e. the subcommand name as typed. Skips the
+/// value of a space-separated global `--profile NAME`, which would otherwise
+/// be mistaken for the subcommand.
+fn raw_subcommand_arg(raw_args: &[String]) -> Option<&String> {
+    let mut args = raw_args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            args.next();
+        } else if !arg.starts_with('-') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
 fn project_command_suggestion(raw_args: &[String]) -> Option<&'static str> {
-    let raw_subcommand = raw_args.iter().find(|arg| !arg.starts_with('-'))?;
+    let raw_subcommand = raw_subcommand_arg(raw_args)?;
 
     if raw_subcommand == "projects" {
         Some("I think you meant `railway project`; running that command instead.")
@@ -271,7 +292,7 @@ async fn main() -> Result<()> {
         .skip(1)
         .map(|arg| arg.to_string_lossy().into_owned())
         .collect();
-    let raw_subcommand = raw_args.iter().find(|a| !a.starts_with('-')).cloned();
+    let raw_subcommand = raw_subcommand_arg(&raw_args).cloned();
 
     let is_update_management_cmd = matches!(
         raw_subcommand.as_deref(),
@@ -431,6 +452,10 @@ async fn main() -> Result<()> {
         }
     };
 
+    if let Some(profile) = cli.get_one::<String>("profile") {
+        Configs::set_profile_override(profile);
+    }
+
     if let Some(suggestion) = project_command_suggestion(&raw_args) {
         eprintln!("{suggestion}");
     }
@@ -510,6 +535,7 @@ fn command_needs_refresh(cli: &clap::ArgMatches) -> bool {
         "autoupdate",
         "telemetry_cmd",
         "check_updates",
+        "profile",
     ];
 
     let is_mcp_install = matches!(
@@ -585,6 +611,25 @@ mod cli_tests {
             assert_subcommand(&["tcp-proxy", "list"], "tcp-proxy");
         }
 
+        #[test]
+        fn global_profile_flag_parses() {
+            assert_subcommand(&["--profile", "work", "whoami"], "whoami");
+            assert_subcommand(&["whoami", "--profile", "work"], "whoami");
+            assert_subcommand(&["profile", "add", "work", "--use"], "profile");
+            assert_subcommand(&["profile", "use", "default"], "profile");
+        }
+
+        #[test]
+        fn raw_subcommand_skips_profile_value() {
+            let args = ["--profile", "work", "upgrade"].map(String::from);
+            assert_eq!(
+                raw_subcommand_arg(&args).map(String::as_str),
+                Some("upgrade")
+            );
+            let args = ["--profile=work", "logs"].map(String::from);
+            assert_eq!(raw_subcommand_arg(&args).map(String::as_str), Some("logs"));
+        }
+
         #[test]
         fn variable_aliases() {
             assert_subcommand(&["variable"], "variable");
@@ -614,6 +659,215 @@ mod cli_tests {
             ]);
         }
 
+        #[test]
+        fn project_member_subcommands_parse() {
+            assert_parses(&["project", "members"]);
+            assert_parses(&["project", "members", "--json", "--project", "api"]);
+            assert_parses(&["project", "members", "list", "--json"]);
+            assert_parses(&[
+                "project",
+                "members",
+                "add",
+                "a@example.com",
+                "--role",
+                "viewer",
+            ]);
+            assert_parses(&["project", "members", "remove", "a@example.com", "--yes"]);
+            assert_parses(&["project", "members", "role", "a@example.com", "admin"]);
+            assert!(parse(&["project", "members", "role", "a@example.com", "owner"]).is_err());
+            assert_parses(&[
+                "project",
+                "invite",
+                "b@example.com",
+                "--role",
+                "admin",
+                "--json",
+            ]);
+            assert_parses(&["project", "invite", "b@example.com", "--resend"]);
+        }
+
+        #[test]
+        fn audit_flags_parse() {
+            assert_parses(&[
+                "audit",
+                "--workspace",
+                "acme",
+                "--since",
+                "30d",
+                "--event-type",
+                "variable.*",
+                "--actor",
+                "alice@x",
+            ]);
+            assert_parses(&["audit", "--format", "csv", "--limit", "0"]);
+            assert_parses(&["audit", "-t", "variable.*", "--follow", "--json"]);
+            assert_parses(&["audit", "--list-event-types"]);
+            assert!(parse(&["audit", "--json", "--format", "csv"]).is_err());
+            assert!(parse(&["audit", "--follow", "--until", "1d"]).is_err());
+        }
+
+        #[test]
+        fn notify_subcommands_parse() {
+            assert_parses(&["notify", "rules", "list", "--project", "--json"]);
+            assert_parses(&[
+                "notify",
+                "rules",
+                "add",
+                "--project",
+                "api",
+                "--on",
+                "deploy-failed",
+                "--on",
+                "crashed",
+                "--to",
+                "slack:https://hooks.slack.com/x",
+                "--severity",
+                "critical",
+            ]);
+            assert!(parse(&["notify", "rules", "add", "--on", "crashed"]).is_err());
+            assert!(
+                parse(&[
+                    "notify",
+                    "rules",
+                    "ls",
+                    "--workspace",
+                    "a",
+                    "--project",
+                    "b"
+                ])
+                .is_err()
+            );
+            assert_parses(&["notify", "rules", "rm", "rule-id", "--yes"]);
+            assert_parses(&["notify", "test", "https://example.com/hook"]);
+            assert_parses(&[
+                "notifications",
+                "inbox",
+                "--unread",
+                "--mark-read",
+                "-n",
+                "5",
+            ]);
+        }
+
+        #[test]
+        fn import_compose_parses() {
+            assert_parses(&["import", "compose"]);
+            assert_parses(&[
+                "import",
+                "compose",
+                "./stack/compose.yaml",
+                "--environment",
+                "staging",
+                "--message",
+                "Migrate from compose",
+                "--yes",
+                "--json",
+            ]);
+            assert_parses(&["import", "compose", "--dry-run"]);
+        }
+
+        #[test]
+        fn service_duplicate_parses() {
+            assert_parses(&["service", "duplicate", "worker", "--name", "worker-2"]);
+            assert_parses(&[
+                "service",
+                "duplicate",
+                "worker",
+                "--name",
+                "worker",
+                "--environment",
+                "staging",
+                "--with-variables",
+                "--with-volumes=empty",
+                "--deploy",
+                "--yes",
+            ]);
+            assert_parses(&["service", "clone", "-n", "copy", "--with-volumes"]);
+            assert!(parse(&["service", "duplicate", "worker"]).is_err());
+            assert!(
+                parse(&["service", "duplicate", "--name", "x", "--with-volumes=full"]).is_err()
+            );
+        }
+
+        #[test]
+        fn edge_rules_subcommands_parse() {
+            assert_parses(&["edge", "rules", "pull", "--service", "web"]);
+            assert_parses(&["edge", "rules", "pull", "-o", "rules.yaml", "--json"]);
+            assert_parses(&["edge", "rules", "validate", "rules.yaml"]);
+            assert_parses(&[
+                "edge",
+                "rule",
+                "push",
+                "rules.yaml",
+                "--environment",
+                "production",
+                "--yes",
+            ]);
+            assert!(parse(&["edge", "rules", "push"]).is_err());
+        }
+
+        #[test]
+        fn token_subcommands_parse() {
+            assert_parses(&[
+                "token",
+                "create",
+                "--project",
+                "--environment",
+                "production",
+                "--name",
+                "ci",
+            ]);
+            assert_parses(&[
+                "token",
+                "create",
+                "--project",
+                "api",
+                "-e",
+                "staging",
+                "-n",
+                "ci",
+            ]);
+            assert_parses(&["token", "create", "--name", "laptop", "--workspace", "team"]);
+            assert!(parse(&["token", "create", "--environment", "production"]).is_err());
+            assert_parses(&["token", "list", "--project", "--json"]);
+            assert_parses(&["tokens", "ls"]);
+            assert_parses(&[
+                "token",
+                "revoke",
+                "token-id",
+                "--project",
+                "--yes",
+                "--json",
+            ]);
+        }
+
+        #[test]
+        fn deployment_lifecycle_subcommands_parse() {
+            assert_parses(&["deployment", "rollback", "--service", "api", "--yes"]);
+            assert_parses(&[
+                "deployment",
+                "rollback",
+                "--to",
+                "dep-id",
+                "--json",
+                "--yes",
+            ]);
+            assert_parses(&["deployment", "rollback", "--previous"]);
+            assert!(parse(&["deployment", "rollback", "--to", "dep-id", "--previous"]).is_err());
+            assert_parses(&["deployment", "cancel", "--service", "api", "--yes"]);
+            assert_parses(&["deployment", "stop", "dep-id", "--json", "--yes"]);
+            assert_parses(&["deployment", "approve", "-s", "api", "-e", "production"]);
+            assert_parses(&["deployment", "timeline"]);
+            assert_parses(&[
+                "deployment",
+                "timeline",
+                "dep-id",
+                "--compare",
+                "other-id",
+                "--json",
+            ]);
+        }
+
         #[test]
         fn logs_http_examples_parse() {
             assert_parses(&["logs", "--http", "--lines", "50"]);
@@ -718,6 +972,38 @@ mod cli_tests {
             assert_parses(&["variable", "delete", "KEY"]);
             assert_parses(&["variable", "rm", "KEY"]); // alias
             assert_parses(&["variable", "delete", "KEY", "--json"]);
+            assert_parses(&["variable", "export", "--format", "yaml"]);
+            assert_parses(&["variable", "import", ".env", "--no-delete", "--yes"]);
+            assert_parses(&[
+                "variable",
+                "diff",
+                "--from",
+                "staging",
+                "--to",
+                "production",
+            ]);
+        }
+
+        #[test]
+        fn functions_serve_parses() {
+            assert_parses(&["functions", "serve"]);
+            assert_parses(&["functions", "serve", "hello.ts", "--port", "8080"]);
+            assert_parses(&["fn", "serve", "cron.ts", "--trigger-cron"]);
+            assert_parses(&["functions", "-e", "staging", "serve", "--no-watch"]);
+        }
+
+        #[test]
+        fn variable_shared_and_share_parse() {
+            assert_parses(&["variable", "--shared"]);
+            assert_parses(&["variable", "--shared", "--set", "KEY=value"]);
+            assert_parses(&["variable", "--shared", "list", "--json"]);
+            assert_parses(&["variable", "list", "--shared", "-e", "production"]);
+            assert_parses(&["variable", "--shared", "set", "KEY=value", "--skip-deploys"]);
+            assert_parses(&["variable", "--shared", "delete", "KEY"]);
+            assert_parses(&["variable", "share", "KEY", "--to", "api,worker"]);
+            assert_parses(&[
+                "variable", "share", "A", "B", "--to", "api", "--to", "worker", "--yes", "--json",
+            ]);
         }
 
         #[test]
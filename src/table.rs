@@ -6,6 +6,7 @@ use std::collections::BTreeMap;
 const FIRST_COLUMN_MIN_WIDTH: usize = 10;
 const MIN_BOX_WIDTH: usize = 20;
 const MAX_BOX_WIDTH: usize = 80;
+const MAX_LIST_WIDTH: usize = 120;
 
 pub struct Table {
     name: String,
@@ -174,3 +175,169 @@ fn print_row(
 
     output
 }
+
+/// A titled table with a header row and any number of columns, drawn in the
+/// same box style as [`Table`]. Cells are truncated rather than wrapped so
+/// each row stays on one line.
+pub struct ListTable {
+    title: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ListTable {
+    pub fn new(title: String, headers: &[&str], rows: Vec<Vec<String>>) -> Self {
+        Self {
+            title,
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows,
+        }
+    }
+
+    pub fn get_string(&self) -> String {
+        let columns = self.headers.len();
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .map(|h| console::measure_text_width(h))
+            .collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(console::measure_text_width(cell));
+            }
+        }
+
+        let edge = format!("{} ", box_drawing::double::VERTICAL);
+        let right_edge = edge.chars().rev().collect::<String>();
+        let middle = format!(" {} ", box_drawing::light::VERTICAL);
+        let fixed = console::measure_text_width(&edge) * 2
+            + console::measure_text_width(&middle) * columns.saturating_sub(1);
+        // Shrink the widest column until the table fits.
+        while fixed + widths.iter().sum::<usize>() > MAX_LIST_WIDTH {
+            let Some(widest) = widths.iter_mut().max() else {
+                break;
+            };
+            if *widest <= FIRST_COLUMN_MIN_WIDTH {
+                break;
+            }
+            *widest -= 1;
+        }
+        let natural = fixed + widths.iter().sum::<usize>();
+        if let Some(last) = widths.last_mut() {
+            *last += MIN_BOX_WIDTH.saturating_sub(natural);
+        }
+        let box_width = fixed + widths.iter().sum::<usize>();
+
+        let title = format!(" {} ", self.title);
+        let title_width = console::measure_text_width(&title);
+        let side = box_width.saturating_sub(title_width + 2) as f64 / 2.0;
+        let top = format!(
+            "{}{}{}{}{}",
+            box_drawing::double::DOWN_RIGHT.cyan().dimmed(),
+            str::repeat(box_drawing::double::HORIZONTAL, side.ceil() as usize)
+                .cyan()
+                .dimmed(),
+            title.magenta().bold(),
+            str::repeat(box_drawing::double::HORIZONTAL, side.floor() as usize)
+                .cyan()
+                .dimmed(),
+            box_drawing::double::DOWN_LEFT.cyan().dimmed(),
+        );
+        let separator = format!(
+            "{}{}{}",
+            box_drawing::double::VERTICAL.cyan().dimmed(),
+            str::repeat(box_drawing::light::HORIZONTAL, box_width - 2)
+                .cyan()
+                .dimmed(),
+            box_drawing::double::VERTICAL.cyan().dimmed()
+        );
+        let bottom = format!(
+            "{}{}{}",
+            box_drawing::double::UP_RIGHT.cyan().dimmed(),
+            str::repeat(box_drawing::double::HORIZONTAL, box_width - 2)
+                .cyan()
+                .dimmed(),
+            box_drawing::double::UP_LEFT.cyan().dimmed()
+        );
+
+        let line = |cells: &[String], header: bool| {
+            let padded = widths
+                .iter()
+                .enumerate()
+                .map(|(index, width)| {
+                    let cell = cells.get(index).map(String::as_str).unwrap_or("");
+                    // console 0.15 also cuts cells that fit exactly.
+                    let cell = if console::measure_text_width(cell) > *width {
+                        console::truncate_str(cell, *width, "…")
+                    } else {
+                        cell.into()
+                    };
+                    let cell = console::pad_str(&cell, *width, console::Alignment::Left, None);
+                    if header {
+                        cell.bold().to_string()
+                    } else {
+                        cell.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(&middle.cyan().dimmed().to_string());
+            format!(
+                "{}{}{}",
+                edge.cyan().dimmed(),
+                padded,
+                right_edge.cyan().dimmed()
+            )
+        };
+
+        let mut lines = vec![top, line(&self.headers, true), separator];
+        lines.extend(self.rows.iter().map(|row| line(row, false)));
+        lines.push(bottom);
+        lines.join("\n")
+    }
+
+    pub fn print(&self) {
+        println!("{}", self.get_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(rows: Vec<Vec<&str>>) -> Vec<String> {
+        colored::control::set_override(false);
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(str::to_string).collect())
+            .collect();
+        ListTable::new("Things".to_string(), &["Name", "Value"], rows)
+            .get_string()
+            .lines()
+            .map(|line| console::strip_ansi_codes(line).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn list_table_sizes_columns_to_their_widest_cell() {
+        let lines = table(vec![vec!["a", "1"], vec!["longer-name", "22"]]);
+        let widths: Vec<usize> = lines
+            .iter()
+            .map(|line| console::measure_text_width(line))
+            .collect();
+        assert!(widths.iter().all(|width| *width == widths[0]), "{lines:?}");
+        assert!(lines[1].starts_with("║ Name        │ Value"), "{lines:?}");
+        assert!(lines[3].starts_with("║ a           │ 1"), "{lines:?}");
+        assert!(lines[4].starts_with("║ longer-name │ 22"), "{lines:?}");
+    }
+
+    #[test]
+    fn list_table_truncates_to_the_maximum_width() {
+        let long = "x".repeat(300);
+        let lines = table(vec![vec!["short", &long]]);
+        for line in &lines {
+            assert_eq!(console::measure_text_width(line), MAX_LIST_WIDTH, "{line}");
+        }
+        assert!(lines[3].starts_with("║ short │"));
+        assert!(lines[3].trim_end_matches(" ║").ends_with('…'));
+    }
+}
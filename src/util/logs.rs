@@ -1,8 +1,11 @@
 use crate::{queries, subscriptions};
-use colored::Colorize;
+use chrono::{DateTime, Utc};
+use colored::{Color, Colorize};
 use serde::Serialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::time::{Duration, Instant};
 
 const NETWORK_FLOW_TIME_WIDTH: usize = 30;
 const NETWORK_FLOW_DIR_WIDTH: usize = 3;
@@ -98,6 +101,32 @@ pub enum LogFormat {
     Full,
 }
 
+/// The JSON shape of a log line: message, timestamp, and every attribute
+/// lifted to a top-level key (parsed as JSON when it looks like JSON).
+pub fn log_json_map<T: LogLike>(log: &T) -> HashMap<String, Value> {
+    let mut map: HashMap<String, Value> = HashMap::new();
+
+    map.insert(
+        "message".to_string(),
+        serde_json::to_value(log.message()).unwrap(),
+    );
+    map.insert(
+        "timestamp".to_string(),
+        serde_json::to_value(log.timestamp()).unwrap(),
+    );
+
+    // Insert dynamic attributes
+    for (key, value) in log.attributes() {
+        let parsed_value = match value.trim_matches('"').parse::<Value>() {
+            Ok(v) => v,
+            Err(_) => serde_json::to_value(value.trim_matches('"')).unwrap(),
+        };
+        map.insert(key.to_string(), parsed_value);
+    }
+
+    map
+}
+
 /// Format a log entry as a string based
 pub fn format_log_string<T>(log: T, json: bool, format: LogFormat) -> String
 where
@@ -105,27 +134,7 @@ where
 {
     if json {
         // For JSON output, handle attributes specially
-        let mut map: HashMap<String, Value> = HashMap::new();
-
-        map.insert(
-            "message".to_string(),
-            serde_json::to_value(log.message()).unwrap(),
-        );
-        map.insert(
-            "timestamp".to_string(),
-            serde_json::to_value(log.timestamp()).unwrap(),
-        );
-
-        // Insert dynamic attributes
-        for (key, value) in log.attributes() {
-            let parsed_value = match value.trim_matches('"').parse::<Value>() {
-                Ok(v) => v,
-                Err(_) => serde_json::to_value(value.trim_matches('"')).unwrap(),
-            };
-            map.insert(key.to_string(), parsed_value);
-        }
-
-        serde_json::to_string(&map).unwrap()
+        serde_json::to_string(&log_json_map(&log)).unwrap()
     } else {
         match format {
             LogFormat::LevelOnly => format_attr_log_string(&log, false),
@@ -149,6 +158,140 @@ where
     }
 }
 
+/// Prefix colors for merged multi-service output, in the spirit of
+/// `docker compose logs`.
+const SERVICE_LABEL_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Yellow,
+    Color::Green,
+    Color::Magenta,
+    Color::Blue,
+    Color::BrightRed,
+];
+
+/// Identifies which service a line came from when several services' logs are
+/// merged into one stream.
+#[derive(Clone, Debug)]
+pub struct ServiceLogLabel {
+    pub name: String,
+    color: Color,
+    width: usize,
+}
+
+impl ServiceLogLabel {
+    /// `width` pads every prefix to the same column so messages line up. The
+    /// color is derived from the name alone, so a service keeps its color
+    /// across runs regardless of which other services are being followed.
+    pub fn new(name: String, width: usize) -> Self {
+        let hash = name.bytes().fold(0usize, |acc, b| {
+            acc.wrapping_mul(31).wrapping_add(b as usize)
+        });
+        Self {
+            color: SERVICE_LABEL_COLORS[hash % SERVICE_LABEL_COLORS.len()],
+            width,
+            name,
+        }
+    }
+
+    fn prefix(&self) -> String {
+        format!("{:<width$} |", self.name, width = self.width)
+            .color(self.color)
+            .bold()
+            .to_string()
+    }
+}
+
+/// Format a log line from a merged multi-service stream: prefixed with the
+/// service name for humans, or with a `service` field for JSON.
+pub fn format_service_log_string<T>(log: &T, label: &ServiceLogLabel, json: bool) -> String
+where
+    T: LogLike,
+{
+    if json {
+        let mut map = log_json_map(log);
+        map.insert("service".to_string(), Value::String(label.name.clone()));
+        serde_json::to_string(&map).unwrap()
+    } else {
+        // Strip the remote text before adding our own (colored) prefix.
+        format!(
+            "{} {}",
+            label.prefix(),
+            strip_terminal_controls(&format_attr_log_string(log, true))
+        )
+    }
+}
+
+/// Orders lines from several concurrent log streams by timestamp.
+///
+/// Each stream is ordered on its own, but across streams lines arrive in
+/// whatever order the subscriptions deliver them. Lines are held for `delay`
+/// after arrival so a slightly late line from one service still lands before
+/// newer lines from another; anything older than that is released in
+/// timestamp order.
+pub struct TimestampMerger {
+    pending: BinaryHeap<Reverse<PendingLine>>,
+    delay: Duration,
+    sequence: u64,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct PendingLine {
+    timestamp: DateTime<Utc>,
+    // Tie-breaker that keeps arrival order for identical timestamps.
+    sequence: u64,
+    arrived: Instant,
+    line: String,
+}
+
+impl TimestampMerger {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            pending: BinaryHeap::new(),
+            delay,
+            sequence: 0,
+        }
+    }
+
+    pub fn push(&mut self, timestamp: &str, line: String, now: Instant) {
+        self.sequence += 1;
+        self.pending.push(Reverse(PendingLine {
+            timestamp: parse_log_timestamp(timestamp),
+            sequence: self.sequence,
+            arrived: now,
+            line,
+        }));
+    }
+
+    /// Lines whose hold period has elapsed, oldest timestamp first.
+    pub fn drain_ready(&mut self, now: Instant) -> Vec<String> {
+        let mut ready = Vec::new();
+        while let Some(Reverse(next)) = self.pending.peek() {
+            if now.duration_since(next.arrived) < self.delay {
+                break;
+            }
+            ready.push(self.pending.pop().unwrap().0.line);
+        }
+        ready
+    }
+
+    /// Every remaining line, oldest timestamp first.
+    pub fn drain_all(&mut self) -> Vec<String> {
+        let mut lines = Vec::with_capacity(self.pending.len());
+        while let Some(Reverse(next)) = self.pending.pop() {
+            lines.push(next.line);
+        }
+        lines
+    }
+}
+
+/// Log timestamps are RFC 3339 but not uniformly precise, so compare them as
+/// instants rather than strings. Unparseable ones sort first.
+pub fn parse_log_timestamp(timestamp: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(timestamp.trim_matches('"'))
+        .map(|date| date.with_timezone(&Utc))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
 pub trait HttpLogLike: serde::Serialize {
     fn timestamp(&self) -> &str;
     fn method(&self) -> &str;
@@ -953,6 +1096,49 @@ mod tests {
         assert_eq!(json["level"], "warn");
         assert_eq!(json["count"], 42); // This parses as a number
     }
+
+    #[test]
+    fn test_service_log_json_carries_service_field() {
+        let log = TestLog {
+            message: "listening".to_string(),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            attributes: vec![("level".to_string(), "info".to_string())],
+        };
+        let label = ServiceLogLabel::new("api".to_string(), 6);
+
+        let output = format_service_log_string(&log, &label, true);
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["service"], "api");
+        assert_eq!(json["message"], "listening");
+    }
+
+    #[test]
+    fn test_service_log_label_color_is_stable() {
+        let first = ServiceLogLabel::new("worker".to_string(), 6);
+        let second = ServiceLogLabel::new("worker".to_string(), 12);
+        assert_eq!(first.color, second.color);
+    }
+
+    #[test]
+    fn test_timestamp_merger_orders_across_streams() {
+        let start = Instant::now();
+        let mut merger = TimestampMerger::new(Duration::from_millis(500));
+        merger.push("2025-01-01T00:00:02Z", "api 2".to_string(), start);
+        merger.push("2025-01-01T00:00:01.5Z", "worker 1.5".to_string(), start);
+        merger.push("2025-01-01T00:00:03Z", "api 3".to_string(), start);
+
+        // Nothing is released before the hold period elapses.
+        assert!(merger.drain_ready(start).is_empty());
+
+        let later = start + Duration::from_millis(600);
+        merger.push("2025-01-01T00:00:02.5Z", "worker 2.5".to_string(), later);
+        assert_eq!(
+            merger.drain_ready(later),
+            vec!["worker 1.5", "api 2"],
+            "a fresh line blocks release of anything newer than it"
+        );
+        assert_eq!(merger.drain_all(), vec!["worker 2.5", "api 3"]);
+    }
 }
 
 /// Strip terminal control sequences from a remotely sourced log line.
@@ -1,17 +1,50 @@
 use anyhow::Result;
 use futures::Future;
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 
+type PathFilter = Box<dyn Fn(&Path) -> bool + Send + Sync>;
+
 pub struct FileWatcher {
     path: PathBuf,
+    /// Set for directory watches: which changed paths count.
+    filter: Option<PathFilter>,
+    debounce: Duration,
+    trigger_on_start: bool,
 }
 
 impl FileWatcher {
+    /// Watch a single file.
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            filter: None,
+            debounce: Duration::from_millis(100),
+            trigger_on_start: false,
+        }
+    }
+
+    /// Watch a directory tree, reacting to changes under it that `filter`
+    /// accepts. Uses a longer debounce since saves, checkouts and builds
+    /// touch many files at once.
+    pub fn directory(
+        path: PathBuf,
+        filter: impl Fn(&Path) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            path,
+            filter: Some(Box::new(filter)),
+            debounce: Duration::from_millis(500),
+            trigger_on_start: false,
+        }
+    }
+
+    /// Run the handler once right away, before any change.
+    pub fn trigger_on_start(mut self) -> Self {
+        self.trigger_on_start = true;
+        self
     }
 
     pub async fn watch<F, Fut>(&self, handler: F) -> Result<()>
@@ -22,8 +55,9 @@ impl FileWatcher {
         let (watch_tx, mut file_change) = tokio::sync::mpsc::unbounded_channel();
         let _watcher = self.setup_file_watcher(watch_tx)?;
         let mut current_cancel_token: Option<CancellationToken> = None;
-        let mut debounce_timer: Option<tokio::time::Instant> = None;
-        let debounce_duration = Duration::from_millis(100);
+        let mut debounce_timer: Option<tokio::time::Instant> =
+            self.trigger_on_start.then(tokio::time::Instant::now);
+        let debounce_duration = self.debounce;
 
         loop {
             tokio::select! {
@@ -64,6 +98,16 @@ impl FileWatcher {
     }
 
     fn should_handle_event(&self, event: &Event) -> bool {
+        if let Some(filter) = &self.filter {
+            return match &event.kind {
+                EventKind::Create(_) | EventKind::Remove(_) => true,
+                // Permission and timestamp changes don't change what's deployed
+                EventKind::Modify(notify::event::ModifyKind::Metadata(_)) => false,
+                EventKind::Modify(_) => true,
+                _ => false,
+            } && event.paths.iter().any(|p| filter(p));
+        }
+
         // Filter out temporary files and focus on actual content changes
         match &event.kind {
             EventKind::Create(_) | EventKind::Remove(_) => {
@@ -87,7 +131,12 @@ impl FileWatcher {
             },
             Config::default(),
         )?;
-        watcher.watch(&self.path, RecursiveMode::NonRecursive)?;
+        let mode = if self.filter.is_some() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(&self.path, mode)?;
         Ok(watcher)
     }
 }